@@ -0,0 +1,126 @@
+use super::app_state::MAX_TOKEN;
+use super::format::SheetFormat;
+use super::types::TabType;
+
+/// File proposto per l'esportazione della scheda
+pub const DEFAULT_EXPORT_FILE: &str = "scheda.md";
+
+/// Azione eseguibile dai tasti o dalla palette dei comandi
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    SwitchTab(TabType),
+    ToggleTrait(usize),
+    ToggleMisfortune(usize),
+    SetWhiteBalls(usize),
+    SetRedBalls(usize),
+    /// Conferma la prova con i token impostati
+    StartDraw,
+    Reset,
+    /// Scrive la scheda in Markdown nel file indicato
+    ExportSheet(String),
+    /// Legge una scheda dal CSV indicato e ne mostra l'anteprima
+    ImportSheet(String),
+    /// Salva la scheda in un altro formato
+    ConvertSheet(SheetFormat),
+    /// Personaggio della campagna da mettere in uso
+    SwitchCharacter(String),
+    OpenTimeline,
+    OpenSessions,
+    OpenCampaigns,
+    OpenTraitSearch,
+    OpenTraitPicker,
+    OpenQuickEntry,
+    ToggleManual,
+    StartWizard,
+}
+
+/// Operazione che sovrascrive dati e va confermata prima di eseguirla
+#[derive(Debug, Clone, PartialEq)]
+pub enum Confirm {
+    /// Creazione guidata su una scheda già compilata
+    NewCharacter,
+    /// Esportazione sopra un file esistente
+    OverwriteExport(String),
+    /// Svuotamento di un tratto dal menu contestuale
+    ClearTrait(usize),
+}
+
+impl Confirm {
+    /// Avviso mostrato nel popup di conferma
+    pub fn message(&self) -> String {
+        match self {
+            Confirm::NewCharacter => {
+                "La creazione guidata sostituirà l'intera scheda attuale, avanzamento compreso"
+                    .to_string()
+            }
+            Confirm::OverwriteExport(path) => format!("{} esiste già: sovrascriverlo?", path),
+            Confirm::ClearTrait(_) => {
+                "Il tratto verrà svuotato e tolto dalla prova: continuare?".to_string()
+            }
+        }
+    }
+}
+
+/// Argomento chiesto dalla palette prima di eseguire un'azione
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Prompt {
+    WhiteBalls,
+    RedBalls,
+    ExportFile,
+    ImportFile,
+}
+
+impl Prompt {
+    pub fn label(&self) -> String {
+        match self {
+            Prompt::WhiteBalls => format!("Token bianchi (0-{})", MAX_TOKEN),
+            Prompt::RedBalls => format!("Token rossi (0-{})", MAX_TOKEN),
+            Prompt::ExportFile => format!("File di destinazione (vuoto: {})", DEFAULT_EXPORT_FILE),
+            Prompt::ImportFile => "File CSV da importare".to_string(),
+        }
+    }
+
+    /// Azione corrispondente all'argomento digitato
+    pub fn action(&self, arg: &str) -> Result<Action, String> {
+        let arg = arg.trim();
+        match self {
+            Prompt::WhiteBalls => parse_count(arg).map(Action::SetWhiteBalls),
+            Prompt::RedBalls => parse_count(arg).map(Action::SetRedBalls),
+            Prompt::ExportFile if arg.is_empty() => {
+                Ok(Action::ExportSheet(DEFAULT_EXPORT_FILE.to_string()))
+            }
+            Prompt::ExportFile => Ok(Action::ExportSheet(arg.to_string())),
+            Prompt::ImportFile if arg.is_empty() => Err("Indicare il file da importare".into()),
+            Prompt::ImportFile => Ok(Action::ImportSheet(arg.to_string())),
+        }
+    }
+}
+
+/// Numero di token tra 0 e il massimo consentito
+fn parse_count(arg: &str) -> Result<usize, String> {
+    match arg.parse::<usize>() {
+        Ok(n) if n <= MAX_TOKEN => Ok(n),
+        _ => Err(format!("Inserire un numero da 0 a {}", MAX_TOKEN)),
+    }
+}
+
+#[cfg(test)]
+mod action_tests {
+    use crate::app::action::*;
+
+    #[test]
+    fn test_prompt_arguments() {
+        assert_eq!(Prompt::RedBalls.action(" 3 "), Ok(Action::SetRedBalls(3)));
+        assert!(Prompt::WhiteBalls.action("tanti").is_err());
+        assert!(Prompt::WhiteBalls.action("99").is_err());
+        assert_eq!(
+            Prompt::ExportFile.action(""),
+            Ok(Action::ExportSheet(DEFAULT_EXPORT_FILE.to_string()))
+        );
+        assert!(Prompt::ImportFile.action(" ").is_err());
+        assert_eq!(
+            Prompt::ImportFile.action("pg.csv"),
+            Ok(Action::ImportSheet("pg.csv".to_string()))
+        );
+    }
+}
@@ -0,0 +1,212 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use super::campaign::character_file;
+use super::format::SheetFormat;
+use super::history::{deserialize_optional_time, deserialize_time};
+
+/// Lezione appresa durante la campagna
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LessonRecord {
+    pub slot: usize,
+    pub text: String,
+    #[serde(deserialize_with = "deserialize_time")]
+    pub time: DateTime<Local>,
+    /// Numero della prova (1-based) da cui è nata la lezione
+    pub draw: Option<usize>,
+    /// Data della prova, conservata perché lo storico non viene salvato
+    #[serde(default, deserialize_with = "deserialize_optional_time")]
+    pub draw_time: Option<DateTime<Local>>,
+    /// Nodo della griglia sbloccato grazie alla lezione
+    pub unlocked_node: Option<usize>,
+}
+
+/// Singola modifica alla scheda del personaggio
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SheetChange {
+    #[serde(deserialize_with = "deserialize_time")]
+    pub time: DateTime<Local>,
+    pub field: String,
+    pub before: String,
+    pub after: String,
+    /// Indice della lezione in `Advancement::lessons`, se la modifica ne deriva
+    pub lesson: Option<usize>,
+}
+
+/// Registro dell'avanzamento del personaggio
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct Advancement {
+    #[serde(default)]
+    pub lessons: Vec<LessonRecord>,
+    #[serde(default)]
+    pub changes: Vec<SheetChange>,
+}
+
+impl Advancement {
+    /// Registra una modifica, ignorando quelle che non cambiano il valore
+    pub fn record_change(
+        &mut self,
+        time: DateTime<Local>,
+        field: String,
+        before: &str,
+        after: &str,
+    ) {
+        if before == after {
+            return;
+        }
+        self.changes.push(SheetChange {
+            time,
+            field,
+            before: before.to_string(),
+            after: after.to_string(),
+            lesson: None,
+        });
+    }
+
+    /// Registra una lezione appresa, eventualmente collegata a una prova
+    pub fn learn_lesson(
+        &mut self,
+        time: DateTime<Local>,
+        slot: usize,
+        text: &str,
+        draw: Option<(usize, DateTime<Local>)>,
+    ) {
+        let (draw, draw_time) = match draw {
+            Some((n, t)) => (Some(n), Some(t)),
+            None => (None, None),
+        };
+        self.lessons.push(LessonRecord {
+            slot,
+            text: text.to_string(),
+            time,
+            draw,
+            draw_time,
+            unlocked_node: None,
+        });
+        if let Some(change) = self.changes.last_mut()
+            && change.field == format!("Lezione {}", slot + 1)
+        {
+            change.lesson = Some(self.lessons.len() - 1);
+        }
+    }
+
+    /// Ultima lezione che non ha ancora sbloccato uno slot
+    pub fn pending_lesson(&self) -> Option<usize> {
+        self.lessons.iter().rposition(|l| l.unlocked_node.is_none())
+    }
+
+    /// Nodi sbloccati dalle lezioni
+    pub fn unlocked_nodes(&self) -> Vec<usize> {
+        self.lessons
+            .iter()
+            .filter_map(|l| l.unlocked_node)
+            .collect()
+    }
+
+    pub(crate) fn load_advancement() -> Self {
+        let path = character_file();
+        if let Ok(contents) = fs::read_to_string(&path)
+            && let Ok(data) = SheetFormat::from_path(&path).parse::<AdvancementData>(&contents)
+        {
+            return data.advancement;
+        }
+        Advancement::default()
+    }
+}
+
+/// Struttura per serializzazione/deserializzazione dell'avanzamento
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub(crate) struct AdvancementData {
+    #[serde(default)]
+    pub advancement: Advancement,
+}
+
+#[cfg(test)]
+mod advancement_tests {
+    use chrono::{NaiveDate, NaiveTime};
+
+    use crate::app::advancement::*;
+
+    #[test]
+    fn test_record_change_skips_identical_values() {
+        let mut adv = Advancement::default();
+        adv.record_change(Local::now(), "Nome".into(), "Aria", "Aria");
+        assert!(adv.changes.is_empty());
+
+        adv.record_change(Local::now(), "Nome".into(), "", "Aria");
+        assert_eq!(adv.changes.len(), 1);
+        assert_eq!(adv.changes[0].after, "Aria");
+    }
+
+    #[test]
+    fn test_learn_lesson_links_change_and_draw() {
+        let yesterday = Local::now() - chrono::TimeDelta::days(1);
+        let mut adv = Advancement::default();
+        adv.record_change(Local::now(), "Lezione 2".into(), "", "Il mare è crudele");
+        adv.learn_lesson(Local::now(), 1, "Il mare è crudele", Some((3, yesterday)));
+
+        assert_eq!(adv.lessons[0].draw, Some(3));
+        assert_eq!(adv.lessons[0].draw_time, Some(yesterday));
+        assert_eq!(adv.changes[0].lesson, Some(0));
+        assert_eq!(adv.pending_lesson(), Some(0));
+    }
+
+    #[test]
+    fn test_unlocked_nodes() {
+        let mut adv = Advancement::default();
+        adv.learn_lesson(Local::now(), 0, "a", None);
+        adv.learn_lesson(Local::now(), 1, "b", None);
+        adv.lessons[1].unlocked_node = Some(0);
+
+        assert_eq!(adv.unlocked_nodes(), vec![0]);
+        assert_eq!(adv.pending_lesson(), Some(0));
+    }
+
+    #[test]
+    fn test_advancement_round_trip() {
+        let mut data = AdvancementData::default();
+        data.advancement
+            .record_change(Local::now(), "Qualità 1".into(), "", "Tenace");
+        data.advancement.learn_lesson(Local::now(), 0, "a", None);
+
+        let serialized = toml::to_string_pretty(&data).unwrap();
+        let restored = toml::from_str::<AdvancementData>(&serialized).unwrap();
+        assert_eq!(restored.advancement, data.advancement);
+    }
+
+    #[test]
+    fn test_legacy_times() {
+        let data: AdvancementData = toml::from_str(
+            r#"
+[[advancement.lessons]]
+slot = 0
+text = "Il mare è crudele"
+time = "Monday  3 March 2025, 18:04:05"
+draw = 2
+draw_time = "Monday  3 March 2025, 17:50:00"
+
+[[advancement.changes]]
+time = "Monday  3 March 2025, 18:04:05"
+field = "Lezione 1"
+before = ""
+after = "Il mare è crudele"
+"#,
+        )
+        .unwrap();
+        let lesson = &data.advancement.lessons[0];
+        assert_eq!(
+            lesson.time.naive_local(),
+            NaiveDate::from_ymd_opt(2025, 3, 3)
+                .unwrap()
+                .and_hms_opt(18, 4, 5)
+                .unwrap()
+        );
+        assert_eq!(
+            lesson.draw_time.unwrap().naive_local().time(),
+            NaiveTime::from_hms_opt(17, 50, 0).unwrap()
+        );
+        assert_eq!(data.advancement.changes[0].time, lesson.time);
+        assert_eq!(lesson.unlocked_node, None);
+    }
+}
@@ -1,11 +1,26 @@
 use ratatui::prelude::Rect;
 use ratatui::widgets::ScrollbarState;
+use std::fs;
+use std::path::PathBuf;
 
+use super::action::{Confirm, Prompt};
+use super::advancement::Advancement;
+use super::campaign::{Campaign, CampaignFocus, CampaignInput, character_file};
 use super::character::{CharacterBaseInformation, CharacterSection};
+use super::context_menu::ContextMenu;
 use super::history::DrawHistory;
-use super::honeycomb::HoneycombNode;
+use super::hit::{HitId, HitMap};
+use super::honeycomb::{HoneycombNode, NodeField};
+use super::import::SheetImport;
+use super::journal::Journal;
 use super::list::{ListData, ListSection};
-use super::types::{BallType, FocusedSection, PopupType, TabType};
+use super::log_filter::{LogFilter, LogFilterField};
+use super::narrator::{AppMode, CastSection, Narrator, NarratorField, NarratorFocus};
+use super::proof::PendingDraw;
+use super::sheet::SheetConflict;
+use super::types::{BallType, FocusedSection, PlayPane, PopupType, TabType};
+use super::wizard::CharacterWizard;
+use crate::session::Message;
 
 pub const MAX_TOKEN: usize = 20;
 pub const MAX_DRAW: usize = 4;
@@ -20,6 +35,8 @@ pub struct App {
     pub draw_count: usize,
     pub focused_section: FocusedSection,
     pub popup: PopupType,
+    /// Operazione in attesa di conferma nel popup
+    pub confirm: Option<Confirm>,
     pub drawn_balls: Vec<BallType>,
     pub pool: Vec<BallType>,
     pub current_tab: TabType,
@@ -29,38 +46,45 @@ pub struct App {
     pub current_first_draw: Vec<BallType>,
     pub vertical_scroll: usize,
     pub vertical_scroll_state: ScrollbarState,
+    /// Riga iniziale di ogni prova filtrata, calcolata al rendering
+    pub log_offsets: Vec<usize>,
+    /// Righe totali del log, a capo compresi
+    pub log_height: usize,
+    pub log_area: Rect,
+    /// Prova selezionata tra quelle filtrate, dalla più recente
+    pub log_selected: usize,
+    pub log_filter: LogFilter,
+    pub log_filter_field: LogFilterField,
 
     // Areas for mouse interaction
-    pub tab_areas: Vec<Rect>,
-    pub white_balls_area: Rect,
-    pub red_balls_area: Rect,
-    pub draw_input_area: Rect,
-    pub random_mode_area: Rect,
-    pub forced_four_area: Rect,
-    pub misfortunes_area: [Rect; 4],
-    pub misfortunes_red_balls_area: [Rect; 4],
-    pub resources_area: [Rect; 2],
-    pub lections_area: [Rect; 3],
+    pub hits: HitMap,
+    /// Ultima posizione del mouse
+    pub hover: Option<(u16, u16)>,
+    /// Elemento sotto il puntatore, da evidenziare
+    pub hovered: Option<HitId>,
+    pub context_menu: Option<ContextMenu>,
 
     // Character data
     pub character_base_info: CharacterBaseInformation,
     pub editing_character_info: bool,
     pub character_edit_buffer: String,
     pub selected_character_info: CharacterSection,
-    pub character_name_area: Rect,
-    pub character_objective_area: Rect,
 
     // Honeycomb grid
     pub honeycomb_nodes: Vec<HoneycombNode>,
     pub selected_node: Option<usize>,
     pub editing_node: bool,
     pub node_edit_buffer: String,
-    pub graph_area: Rect,
+    pub node_field: NodeField,
     pub used_traits: Vec<usize>,
 
     // New modes
     pub random_mode: bool,
     pub forced_four_mode: bool,
+    pub manual_mode: bool,
+    /// Esito del sacchetto fisico: bianchi e rossi del primo tentativo e del rischio
+    pub manual_result: [usize; 4],
+    pub manual_field: usize,
 
     // List tab data
     pub list_data: ListData,
@@ -72,6 +96,93 @@ pub struct App {
     pub editing_list_item: bool,
     pub list_edit_buffer: String,
     pub additional_red_balls: [usize; 4],
+
+    // Character creation wizard
+    pub wizard: Option<CharacterWizard>,
+
+    // Advancement and lessons
+    pub advancement: Advancement,
+    pub timeline_scroll: usize,
+
+    // Game sessions journal
+    pub journal: Journal,
+    pub sessions_selected: usize,
+    pub sessions_scroll: usize,
+    /// Titolo della nuova sessione in digitazione
+    pub session_title: Option<String>,
+
+    // Campaign
+    pub campaign: Option<Campaign>,
+    /// Cartelle e nomi delle campagne, letti all'apertura della finestra
+    pub campaign_list: Vec<(std::path::PathBuf, String)>,
+    /// Personaggi della campagna aperta, riletti quando cambiano
+    pub campaign_characters: Vec<String>,
+    pub campaign_focus: CampaignFocus,
+    pub campaign_selected: usize,
+    pub character_selected: usize,
+    pub campaign_input: Option<(CampaignInput, String)>,
+
+    // Trait search
+    pub search_query: String,
+    pub search_selected: usize,
+
+    // Challenge description and trait suggestions
+    pub challenge_description: String,
+    pub picker_selected: usize,
+
+    // Play screen
+    pub play_focus: PlayPane,
+
+    // Command palette
+    pub palette_query: String,
+    pub palette_selected: usize,
+    /// Argomento richiesto dalla voce scelta
+    pub palette_prompt: Option<Prompt>,
+
+    // Quick challenge entry
+    pub quick_entry: String,
+
+    // Sheet file sync
+    /// File della scheda in uso, risolto al caricamento e non a ogni frame
+    pub sheet_file: PathBuf,
+    /// Contenuto del file della scheda letto o scritto per ultimo
+    pub sheet_on_disk: String,
+    pub sheet_conflicts: Vec<SheetConflict>,
+    /// Contenuto del file che ha prodotto i conflitti
+    pub sheet_conflict_disk: String,
+    pub sheet_conflict_scroll: u16,
+
+    // CSV import
+    /// File letto, in attesa di conferma
+    pub sheet_import: Option<SheetImport>,
+    pub sheet_import_selected: usize,
+
+    // Narrator mode
+    pub mode: AppMode,
+    pub narrator: Narrator,
+    pub narrator_focus: NarratorFocus,
+    pub selected_npc: usize,
+    pub cast_section: CastSection,
+    pub selected_cast: usize,
+    pub selected_player: usize,
+    pub narrator_field: Option<NarratorField>,
+    pub narrator_edit_buffer: String,
+
+    // Multiplayer session
+    pub session_label: Option<String>,
+    pub session_host: bool,
+    pub session_peers: usize,
+    pub outbox: Vec<Message>,
+    pub authoritative: bool,
+    pub draw_request: Option<u64>,
+    pub draw_nonce: String,
+    pub next_draw_id: u64,
+    pub pending_draws: Vec<PendingDraw>,
+    pub commitments: Vec<(u64, String)>,
+    pub nonces: Vec<(u64, String)>,
+
+    // Feedback message shown in the tab bar
+    pub status_message: Option<String>,
 }
 
 impl App {
@@ -82,6 +193,7 @@ impl App {
             draw_count: 1,
             focused_section: FocusedSection::WhiteBalls,
             popup: PopupType::None,
+            confirm: None,
             drawn_balls: Vec::new(),
             pool: Vec::new(),
             current_tab: TabType::DrawTab,
@@ -90,44 +202,35 @@ impl App {
             current_first_draw: Vec::new(),
             vertical_scroll: 0,
             vertical_scroll_state: ScrollbarState::default(),
+            log_offsets: Vec::new(),
+            log_height: 0,
+            log_area: Rect::default(),
+            log_selected: 0,
+            log_filter: LogFilter::default(),
+            log_filter_field: LogFilterField::Query,
             // Areas for mouse interaction
-            tab_areas: Vec::new(),
-            white_balls_area: Rect::default(),
-            red_balls_area: Rect::default(),
-            draw_input_area: Rect::default(),
-            random_mode_area: Rect::default(),
-            forced_four_area: Rect::default(),
-            misfortunes_area: [
-                Rect::default(),
-                Rect::default(),
-                Rect::default(),
-                Rect::default(),
-            ],
-            misfortunes_red_balls_area: [
-                Rect::default(),
-                Rect::default(),
-                Rect::default(),
-                Rect::default(),
-            ],
-            resources_area: [Rect::default(), Rect::default()],
-            lections_area: [Rect::default(), Rect::default(), Rect::default()],
+            hits: HitMap::default(),
+            hover: None,
+            hovered: None,
+            context_menu: None,
             // Character data
             character_base_info: CharacterBaseInformation::load_character_base_info(),
             editing_character_info: false,
             character_edit_buffer: String::new(),
             selected_character_info: CharacterSection::None,
-            character_name_area: Rect::default(),
-            character_objective_area: Rect::default(),
             // Honeycomb grid
             honeycomb_nodes: HoneycombNode::load_honeycomb_data(),
             selected_node: Some(9), // central node: archetipo
             editing_node: false,
             node_edit_buffer: String::new(),
-            graph_area: Rect::default(),
+            node_field: NodeField::Title,
             used_traits: Vec::new(),
             // New mode
             random_mode: false,
             forced_four_mode: false,
+            manual_mode: false,
+            manual_result: [0; 4],
+            manual_field: 0,
             // List tab data
             list_data: ListData::load_list_data(),
             notes_vertical_scroll: 0,
@@ -142,6 +245,72 @@ impl App {
             editing_list_item: false,
             list_edit_buffer: String::new(),
             additional_red_balls: [0, 0, 0, 0],
+            // Character creation wizard
+            wizard: None,
+            // Advancement and lessons
+            advancement: Advancement::load_advancement(),
+            timeline_scroll: 0,
+            // Game sessions journal
+            journal: Journal::load_journal(),
+            sessions_selected: 0,
+            sessions_scroll: 0,
+            session_title: None,
+            // Campaign
+            campaign: None,
+            campaign_list: Vec::new(),
+            campaign_characters: Vec::new(),
+            campaign_focus: CampaignFocus::Campaigns,
+            campaign_selected: 0,
+            character_selected: 0,
+            campaign_input: None,
+            // Trait search
+            search_query: String::new(),
+            search_selected: 0,
+            // Challenge description and trait suggestions
+            challenge_description: String::new(),
+            picker_selected: 0,
+            // Play screen
+            play_focus: PlayPane::Traits,
+            // Command palette
+            palette_query: String::new(),
+            palette_selected: 0,
+            palette_prompt: None,
+            // Quick challenge entry
+            quick_entry: String::new(),
+            // Sheet file sync
+            sheet_file: character_file(),
+            sheet_on_disk: fs::read_to_string(character_file()).unwrap_or_default(),
+            sheet_conflicts: Vec::new(),
+            sheet_conflict_disk: String::new(),
+            sheet_conflict_scroll: 0,
+            // CSV import
+            sheet_import: None,
+            sheet_import_selected: 0,
+            // Narrator mode
+            mode: AppMode::Player,
+            narrator: Narrator::default(),
+            narrator_focus: NarratorFocus::Difficulty,
+            selected_npc: 0,
+            cast_section: CastSection::Npcs,
+            selected_cast: 0,
+            selected_player: 0,
+            narrator_field: None,
+            narrator_edit_buffer: String::new(),
+            // Multiplayer session
+            session_label: None,
+            session_host: false,
+            session_peers: 0,
+            outbox: Vec::new(),
+            authoritative: false,
+            draw_request: None,
+            draw_nonce: String::new(),
+            // Ogni istanza numera le proprie richieste da un punto diverso
+            next_draw_id: u64::from(rand::random::<u32>()) << 32,
+            pending_draws: Vec::new(),
+            commitments: Vec::new(),
+            nonces: Vec::new(),
+            // Feedback message shown in the tab bar
+            status_message: None,
         }
     }
 }
@@ -0,0 +1,121 @@
+use rand::Rng;
+
+use super::types::BallType;
+
+/// Riempie il sacchetto con i token della prova.
+///
+/// Con la Confusione ogni token bianco diventa casualmente bianco o rosso.
+pub fn fill_pool(white: usize, red: usize, confused: bool, rng: &mut impl Rng) -> Vec<BallType> {
+    use BallType::*;
+    let mut pool = Vec::with_capacity(white + red);
+    for _ in 0..white {
+        if confused && !rng.random::<bool>() {
+            pool.push(Red);
+        } else {
+            pool.push(White);
+        }
+    }
+    for _ in 0..red {
+        pool.push(Red);
+    }
+    pool
+}
+
+/// Estrae fino a `count` token dal sacchetto, rimuovendoli
+pub fn draw(pool: &mut Vec<BallType>, count: usize, rng: &mut impl Rng) -> Vec<BallType> {
+    let mut drawn = Vec::new();
+    for _ in 0..count.min(pool.len()) {
+        let pos = rng.random_range(0..pool.len());
+        drawn.push(pool.remove(pos));
+    }
+    drawn
+}
+
+/// Controlla un esito pescato da un sacchetto fisico.
+///
+/// `first` e `risk` sono le coppie (bianchi, rossi) del primo tentativo e del
+/// rischio; un rischio vuoto indica che non si è rischiato.
+pub fn validate_manual(
+    white: usize,
+    red: usize,
+    confused: bool,
+    count: usize,
+    first: [usize; 2],
+    risk: [usize; 2],
+) -> Result<(), String> {
+    let pool = white + red;
+    let first_total = first[0] + first[1];
+    let expected = count.min(pool);
+    if first_total != expected {
+        return Err(format!("Il primo tentativo deve avere {} token", expected));
+    }
+
+    let risk_total = risk[0] + risk[1];
+    let remaining = (5 - first_total.min(5)).min(pool - first_total);
+    if risk_total != 0 && risk_total != remaining {
+        return Err(format!("Rischiando si pescano {} token", remaining));
+    }
+
+    // Con la Confusione un token bianco può uscire rosso, mai il contrario
+    if first[0] + risk[0] > white {
+        return Err(format!(
+            "Nel sacchetto ci sono solo {} token bianchi",
+            white
+        ));
+    }
+    if !confused && first[1] + risk[1] > red {
+        return Err(format!("Nel sacchetto ci sono solo {} token rossi", red));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod bag_tests {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use crate::app::bag::*;
+
+    #[test]
+    fn test_fill_pool() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let pool = fill_pool(3, 2, false, &mut rng);
+        assert_eq!(pool.iter().filter(|b| **b == BallType::White).count(), 3);
+        assert_eq!(pool.iter().filter(|b| **b == BallType::Red).count(), 2);
+    }
+
+    #[test]
+    fn test_confused_pool_keeps_size() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let pool = fill_pool(10, 2, true, &mut rng);
+        assert_eq!(pool.len(), 12);
+        assert!(pool.iter().filter(|b| **b == BallType::Red).count() >= 2);
+    }
+
+    #[test]
+    fn test_draw_removes_tokens() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut pool = fill_pool(2, 2, false, &mut rng);
+        let drawn = draw(&mut pool, 3, &mut rng);
+        assert_eq!(drawn.len(), 3);
+        assert_eq!(pool.len(), 1);
+
+        let drawn = draw(&mut pool, 5, &mut rng);
+        assert_eq!(drawn.len(), 1);
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn test_validate_manual() {
+        assert!(validate_manual(3, 2, false, 2, [1, 1], [0, 0]).is_ok());
+        assert!(validate_manual(3, 2, false, 2, [1, 1], [2, 1]).is_ok());
+        // Numero di token sbagliato
+        assert!(validate_manual(3, 2, false, 2, [1, 0], [0, 0]).is_err());
+        assert!(validate_manual(3, 2, false, 2, [1, 1], [1, 0]).is_err());
+        // Più rossi di quanti ce ne siano nel sacchetto
+        assert!(validate_manual(3, 2, false, 2, [0, 2], [2, 1]).is_err());
+        assert!(validate_manual(3, 2, true, 2, [0, 2], [2, 1]).is_ok());
+        // Sacchetto più piccolo dei token richiesti
+        assert!(validate_manual(1, 1, false, 4, [1, 1], [0, 0]).is_ok());
+    }
+}
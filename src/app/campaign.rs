@@ -0,0 +1,364 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use super::format::{is_sheet_file, sheet_path};
+use super::history::deserialize_time;
+
+/// Cartella che raccoglie le campagne
+pub const CAMPAIGNS_DIR: &str = "campagne";
+/// Scheda usata fuori da una campagna
+const CHARACTER_FILE: &str = "character_sheet.toml";
+const CAMPAIGN_FILE: &str = "campaign.toml";
+const CHARACTERS_DIR: &str = "personaggi";
+
+/// Cartella della campagna attiva e scheda del personaggio in uso
+struct Scope {
+    dir: PathBuf,
+    character: PathBuf,
+}
+
+/// Campagna attiva: tutti i file di dati vengono letti e scritti al suo interno
+#[cfg(not(test))]
+static ACTIVE: RwLock<Option<Scope>> = RwLock::new(None);
+
+#[cfg(test)]
+thread_local! {
+    /// Nei test ogni thread ha la sua campagna attiva, così i test in parallelo non si disturbano
+    static ACTIVE: RwLock<Option<Scope>> = const { RwLock::new(None) };
+}
+
+#[cfg(not(test))]
+fn active<T>(f: impl FnOnce(&RwLock<Option<Scope>>) -> T) -> T {
+    f(&ACTIVE)
+}
+
+#[cfg(test)]
+fn active<T>(f: impl FnOnce(&RwLock<Option<Scope>>) -> T) -> T {
+    ACTIVE.with(f)
+}
+
+/// Legge la campagna attiva
+fn with_scope<T>(f: impl FnOnce(Option<&Scope>) -> T) -> T {
+    active(|active| match active.read().ok().as_deref() {
+        Some(Some(scope)) => f(Some(scope)),
+        _ => f(None),
+    })
+}
+
+/// Cambia la campagna attiva
+fn set_scope(scope: Option<Scope>) {
+    active(|active| {
+        if let Ok(mut active) = active.write() {
+            *active = scope;
+        }
+    });
+}
+
+/// Torna a leggere i file dalla cartella corrente
+#[cfg(test)]
+pub(crate) fn deactivate() {
+    set_scope(None);
+}
+
+/// Percorso di un file di dati nella campagna attiva, o nella cartella corrente
+pub(crate) fn data_file(name: &str) -> PathBuf {
+    with_scope(|scope| match scope {
+        Some(scope) => scope.dir.join(name),
+        None => PathBuf::from(name),
+    })
+}
+
+/// Percorso della scheda del personaggio in uso, in qualunque formato sia salvata
+pub(crate) fn character_file() -> PathBuf {
+    with_scope(|scope| match scope {
+        Some(scope) => sheet_path(&scope.character),
+        None => sheet_path(Path::new(CHARACTER_FILE)),
+    })
+}
+
+/// Nome utilizzabile come file o cartella: minuscolo, senza spazi né simboli
+fn slug(name: &str) -> String {
+    let slug = name
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect::<String>();
+    slug.split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Elenco attivo nella finestra delle campagne
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CampaignFocus {
+    Campaigns,
+    Characters,
+}
+
+impl CampaignFocus {
+    pub fn next(&self) -> Self {
+        match self {
+            CampaignFocus::Campaigns => CampaignFocus::Characters,
+            CampaignFocus::Characters => CampaignFocus::Campaigns,
+        }
+    }
+}
+
+/// Testo in digitazione nella finestra delle campagne
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CampaignInput {
+    NewCampaign,
+    NewCharacter,
+    Chronicle,
+}
+
+impl CampaignInput {
+    /// Etichetta mostrata accanto al testo
+    pub fn label(&self) -> &'static str {
+        match self {
+            CampaignInput::NewCampaign => "Nuova campagna",
+            CampaignInput::NewCharacter => "Nuovo personaggio",
+            CampaignInput::Chronicle => "Cronaca",
+        }
+    }
+}
+
+/// Voce della cronaca della campagna
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChronicleEntry {
+    #[serde(deserialize_with = "deserialize_time")]
+    pub time: DateTime<Local>,
+    pub text: String,
+}
+
+/// Campagna: personaggi, registro delle sessioni, note del narratore e cronaca.
+///
+/// Struttura della cartella:
+/// ```text
+/// campagne/<nome>/
+///     campaign.toml        nome, personaggio in uso e cronaca
+///     personaggi/<pg>.toml schede dei personaggi
+///     sessions.toml        diario delle sessioni
+///     draws.toml           prove fatte durante le sessioni
+///     narrator.toml        note condivise del narratore
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct Campaign {
+    #[serde(skip)]
+    pub dir: PathBuf,
+    pub name: String,
+    /// Personaggio in uso, come nome del file in `personaggi/` senza estensione
+    #[serde(default)]
+    pub character: String,
+    #[serde(default)]
+    pub chronicle: Vec<ChronicleEntry>,
+}
+
+impl Campaign {
+    /// Crea una nuova campagna nella cartella `root`
+    pub fn create(root: &Path, name: &str) -> io::Result<Self> {
+        let dir_name = slug(name);
+        if dir_name.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Nome della campagna non valido",
+            ));
+        }
+        let dir = root.join(dir_name);
+        if dir.join(CAMPAIGN_FILE).exists() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "La campagna esiste già",
+            ));
+        }
+        fs::create_dir_all(dir.join(CHARACTERS_DIR))?;
+        let campaign = Campaign {
+            dir,
+            name: name.trim().to_string(),
+            ..Default::default()
+        };
+        campaign.save()?;
+        Ok(campaign)
+    }
+
+    /// Apre la campagna contenuta in `dir`
+    pub fn open(dir: &Path) -> io::Result<Self> {
+        let contents = fs::read_to_string(dir.join(CAMPAIGN_FILE))?;
+        let mut campaign = toml::from_str::<Campaign>(&contents)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        campaign.dir = dir.to_path_buf();
+        Ok(campaign)
+    }
+
+    /// Cartelle delle campagne presenti in `root`, in ordine alfabetico
+    pub fn list(root: &Path) -> Vec<PathBuf> {
+        let mut dirs = fs::read_dir(root)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok().map(|e| e.path()))
+                    .filter(|path| path.join(CAMPAIGN_FILE).is_file())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        dirs.sort();
+        dirs
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let string = toml::to_string_pretty(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(self.dir.join(CAMPAIGN_FILE), string)
+    }
+
+    /// Personaggi della campagna, in ordine alfabetico
+    pub fn characters(&self) -> Vec<String> {
+        let mut characters = fs::read_dir(self.dir.join(CHARACTERS_DIR))
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok().map(|e| e.path()))
+                    .filter(|path| is_sheet_file(path))
+                    .filter_map(|path| Some(path.file_stem()?.to_string_lossy().to_string()))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        characters.sort();
+        // Stesso personaggio salvato in più formati
+        characters.dedup();
+        characters
+    }
+
+    /// Aggiunge un personaggio con la scheda vuota e lo mette in uso
+    pub fn add_character(&mut self, name: &str) -> io::Result<()> {
+        let character = slug(name);
+        if character.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Nome del personaggio non valido",
+            ));
+        }
+        let path = self
+            .dir
+            .join(CHARACTERS_DIR)
+            .join(format!("{}.toml", character));
+        if !sheet_path(&path).exists() {
+            fs::write(&path, "")?;
+        }
+        self.character = character;
+        self.save()
+    }
+
+    /// Mette in uso un personaggio già presente, senza riscrivere `campaign.toml`
+    pub fn select_character(&mut self, name: &str) -> io::Result<()> {
+        if !self.characters().iter().any(|c| c == name) {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "Personaggio non trovato",
+            ));
+        }
+        self.character = name.to_string();
+        Ok(())
+    }
+
+    /// Aggiunge una voce alla cronaca
+    pub fn add_chronicle(&mut self, text: &str) -> io::Result<()> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Ok(());
+        }
+        self.chronicle.push(ChronicleEntry {
+            time: Local::now(),
+            text: text.to_string(),
+        });
+        self.save()
+    }
+
+    /// Rende la campagna quella attiva per tutti i file di dati
+    pub fn activate(&self) {
+        let character = if self.character.is_empty() {
+            self.dir.join(CHARACTER_FILE)
+        } else {
+            self.dir
+                .join(CHARACTERS_DIR)
+                .join(format!("{}.toml", self.character))
+        };
+        set_scope(Some(Scope {
+            dir: self.dir.clone(),
+            character,
+        }));
+    }
+}
+
+#[cfg(test)]
+mod campaign_tests {
+    use crate::app::campaign::*;
+    use crate::test_support::{ActiveScope, TempDir};
+
+    #[test]
+    fn test_slug() {
+        assert_eq!(slug("  La Torre d'Ebano "), "la-torre-d-ebano");
+        assert_eq!(slug("!!"), "");
+    }
+
+    #[test]
+    fn test_create_and_open_campaign() {
+        let root = TempDir::new("campaign");
+        let mut campaign = Campaign::create(root.path(), "La Torre").unwrap();
+        assert!(Campaign::create(root.path(), "la torre").is_err());
+
+        campaign.add_character("Aria").unwrap();
+        campaign.add_character("Bruno il Rosso").unwrap();
+        campaign.add_chronicle("Arrivo al porto").unwrap();
+
+        let opened = Campaign::open(&campaign.dir).unwrap();
+        assert_eq!(opened, campaign);
+        assert_eq!(opened.character, "bruno-il-rosso");
+        assert_eq!(opened.characters(), vec!["aria", "bruno-il-rosso"]);
+        assert_eq!(Campaign::list(root.path()), vec![campaign.dir.clone()]);
+    }
+
+    #[test]
+    fn test_active_scope_redirects_files() {
+        let root = TempDir::new("scope");
+        let mut campaign = Campaign::create(root.path(), "La Torre").unwrap();
+        campaign.add_character("Aria").unwrap();
+        assert_eq!(character_file(), Path::new(CHARACTER_FILE));
+        {
+            let _scope = ActiveScope::new(&campaign);
+            assert_eq!(
+                data_file("sessions.toml"),
+                campaign.dir.join("sessions.toml")
+            );
+            assert_eq!(
+                character_file(),
+                campaign.dir.join(CHARACTERS_DIR).join("aria.toml")
+            );
+            // Gli altri thread, come gli altri test, non vedono la campagna
+            let other = std::thread::spawn(character_file).join().unwrap();
+            assert_eq!(other, Path::new(CHARACTER_FILE));
+        }
+        assert_eq!(character_file(), Path::new(CHARACTER_FILE));
+        assert_eq!(data_file("sessions.toml"), Path::new("sessions.toml"));
+    }
+
+    #[test]
+    fn test_select_character_keeps_campaign_file() {
+        let root = TempDir::new("select");
+        let mut campaign = Campaign::create(root.path(), "La Torre").unwrap();
+        campaign.add_character("Aria").unwrap();
+        campaign.add_character("Bruno").unwrap();
+        let saved = fs::read_to_string(campaign.dir.join(CAMPAIGN_FILE)).unwrap();
+
+        campaign.select_character("aria").unwrap();
+        assert_eq!(campaign.character, "aria");
+        assert!(campaign.select_character("carla").is_err());
+        assert_eq!(campaign.character, "aria");
+        let after = fs::read_to_string(campaign.dir.join(CAMPAIGN_FILE)).unwrap();
+        assert_eq!(after, saved);
+    }
+}
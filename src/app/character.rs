@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::fs;
 
-const DATA_FILE: &str = "character_sheet.toml";
+use super::campaign::character_file;
+use super::format::SheetFormat;
 
 /// Sezione del personaggio in fase di modifica
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -35,12 +36,15 @@ impl CharacterBaseInformation {
     }
 
     pub(crate) fn load_character_base_info() -> Self {
-        if let Ok(contents) = fs::read_to_string(DATA_FILE)
-            && let Ok(data) = toml::from_str::<CharacterBaseInformation>(&contents)
-        {
-            return data;
-        }
-        CharacterBaseInformation::default()
+        let path = character_file();
+        fs::read_to_string(&path)
+            .map(|contents| Self::from_contents(&contents, SheetFormat::from_path(&path)))
+            .unwrap_or_default()
+    }
+
+    /// Informazioni lette dal contenuto del file della scheda
+    pub(crate) fn from_contents(contents: &str, format: SheetFormat) -> Self {
+        format.parse(contents).unwrap_or_default()
     }
 }
 
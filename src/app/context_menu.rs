@@ -0,0 +1,96 @@
+use ratatui::layout::Rect;
+
+/// Voce di un menu contestuale, con l'indice dell'elemento su cui agisce
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MenuAction {
+    EditTrait(usize),
+    ToggleTrait(usize),
+    ClearTrait(usize),
+    ToggleMisfortune(usize),
+    EditDifficulty(usize),
+}
+
+impl MenuAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            MenuAction::EditTrait(_) => "Modifica tratto",
+            MenuAction::ToggleTrait(_) => "Attiva / disattiva",
+            MenuAction::ClearTrait(_) => "Svuota",
+            MenuAction::ToggleMisfortune(_) => "Attiva / disattiva",
+            MenuAction::EditDifficulty(_) => "Modifica difficoltà",
+        }
+    }
+}
+
+/// Menu aperto con il tasto destro nel punto cliccato
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContextMenu {
+    pub x: u16,
+    pub y: u16,
+    pub actions: Vec<MenuAction>,
+    pub selected: usize,
+}
+
+impl ContextMenu {
+    /// Menu di un tratto della griglia
+    pub fn for_trait(idx: usize, x: u16, y: u16) -> Self {
+        ContextMenu {
+            x,
+            y,
+            actions: vec![
+                MenuAction::EditTrait(idx),
+                MenuAction::ToggleTrait(idx),
+                MenuAction::ClearTrait(idx),
+            ],
+            selected: 0,
+        }
+    }
+
+    /// Menu di una sventura
+    pub fn for_misfortune(idx: usize, x: u16, y: u16) -> Self {
+        ContextMenu {
+            x,
+            y,
+            actions: vec![
+                MenuAction::ToggleMisfortune(idx),
+                MenuAction::EditDifficulty(idx),
+            ],
+            selected: 0,
+        }
+    }
+
+    /// Riquadro del menu, spostato quanto serve per restare dentro `screen`
+    pub fn area(&self, screen: Rect) -> Rect {
+        let width = self
+            .actions
+            .iter()
+            .map(|a| a.label().chars().count() as u16)
+            .max()
+            .unwrap_or(0)
+            + 4;
+        let height = self.actions.len() as u16 + 2;
+        let width = width.min(screen.width);
+        let height = height.min(screen.height);
+        Rect {
+            x: self.x.min(screen.right().saturating_sub(width)),
+            y: self.y.min(screen.bottom().saturating_sub(height)),
+            width,
+            height,
+        }
+    }
+}
+
+#[cfg(test)]
+mod context_menu_tests {
+    use crate::app::context_menu::*;
+
+    #[test]
+    fn test_menu_stays_on_screen() {
+        let screen = Rect::new(0, 0, 80, 24);
+        let menu = ContextMenu::for_trait(3, 79, 23);
+        let area = menu.area(screen);
+        assert!(area.right() <= screen.right());
+        assert!(area.bottom() <= screen.bottom());
+        assert_eq!(area.height, 5);
+    }
+}
@@ -0,0 +1,126 @@
+use std::fmt::Write;
+
+use super::character::CharacterBaseInformation;
+use super::honeycomb::{HoneycombNode, TraitKind};
+use super::list::ListData;
+
+/// Scheda del personaggio in Markdown, da condividere o stampare
+pub fn sheet_markdown(
+    info: &CharacterBaseInformation,
+    nodes: &[HoneycombNode],
+    list: &ListData,
+) -> String {
+    let mut out = String::new();
+    let name = if info.name.is_empty() {
+        "Personaggio senza nome"
+    } else {
+        &info.name
+    };
+    let _ = writeln!(out, "# {}\n", name);
+    if !info.objective.is_empty() {
+        let _ = writeln!(
+            out,
+            "**Per cosa sono disposto a rischiare:** {}\n",
+            info.objective
+        );
+    }
+
+    for (kind, title) in [
+        (TraitKind::Archetype, "Archetipo"),
+        (TraitKind::Quality, "Qualità"),
+        (TraitKind::Ability, "Abilità"),
+    ] {
+        let traits: Vec<&HoneycombNode> = nodes
+            .iter()
+            .enumerate()
+            .filter(|(i, n)| TraitKind::of(*i) == kind && !n.text.is_empty())
+            .map(|(_, n)| n)
+            .collect();
+        if traits.is_empty() {
+            continue;
+        }
+        let _ = writeln!(out, "## {}\n", title);
+        for node in traits {
+            let _ = write!(out, "- **{}**", node.text);
+            if !node.description.is_empty() {
+                let _ = write!(out, ": {}", node.description);
+            }
+            if !node.tags.is_empty() {
+                let _ = write!(out, " #{}", node.tags.join(" #"));
+            }
+            out.push('\n');
+        }
+        out.push('\n');
+    }
+
+    let misfortunes: Vec<String> = list
+        .misfortunes
+        .iter()
+        .zip(list.misfortunes_red_balls.iter())
+        .filter(|(m, _)| !m.is_empty())
+        .map(|(m, d)| match d.trim() {
+            "" => format!("- {}", m),
+            d => format!("- {} (difficoltà {})", m, d),
+        })
+        .collect();
+    section(&mut out, "Sventure", &misfortunes);
+
+    let resources: Vec<String> = list
+        .left_resources
+        .iter()
+        .filter(|r| !r.is_empty())
+        .map(|r| format!("- {}", r))
+        .collect();
+    section(&mut out, "Risorse", &resources);
+
+    let lessons: Vec<String> = list
+        .lessons
+        .iter()
+        .filter(|l| !l.is_empty())
+        .map(|l| format!("- {}", l))
+        .collect();
+    section(&mut out, "Lezioni", &lessons);
+
+    if !list.notes.trim().is_empty() {
+        let _ = writeln!(out, "## Note\n\n{}\n", list.notes.trim());
+    }
+    out
+}
+
+/// Sezione con un elenco puntato, omessa se vuota
+fn section(out: &mut String, title: &str, lines: &[String]) {
+    if lines.is_empty() {
+        return;
+    }
+    let _ = writeln!(out, "## {}\n\n{}\n", title, lines.join("\n"));
+}
+
+#[cfg(test)]
+mod export_tests {
+    use crate::app::export::*;
+    use crate::app::honeycomb::HONEYCOMB_SIZE;
+
+    #[test]
+    fn test_markdown_lists_filled_fields_only() {
+        let info = CharacterBaseInformation {
+            name: "Lia".into(),
+            objective: "Ritrovare il fratello".into(),
+        };
+        let mut texts = vec![String::new(); HONEYCOMB_SIZE];
+        texts[9] = "Cartografa".into();
+        texts[0] = "Nuotare".into();
+        let mut nodes = HoneycombNode::create_honeycomb_layout_with_data(texts);
+        nodes[0].tags = vec!["acqua".into()];
+        let mut list = ListData::default();
+        list.misfortunes[1] = "Ferita".into();
+        list.misfortunes_red_balls[1] = "2".into();
+
+        let md = sheet_markdown(&info, &nodes, &list);
+        assert!(md.starts_with("# Lia\n"));
+        assert!(md.contains("## Archetipo\n\n- **Cartografa**\n"));
+        assert!(md.contains("- **Nuotare** #acqua\n"));
+        assert!(md.contains("## Sventure\n\n- Ferita (difficoltà 2)\n"));
+        assert!(!md.contains("## Qualità"));
+        assert!(!md.contains("## Lezioni"));
+    }
+}
@@ -0,0 +1,267 @@
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use super::advancement::AdvancementData;
+use super::character::CharacterBaseInformation;
+use super::honeycomb::HoneycombData;
+use super::list::ListData;
+
+/// Estensioni riconosciute per la scheda, in ordine di precedenza
+const SHEET_EXTENSIONS: [&str; 4] = ["toml", "json", "yaml", "yml"];
+
+/// Formato del file della scheda, scelto dall'estensione
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SheetFormat {
+    Toml,
+    Json,
+    Yaml,
+}
+
+impl fmt::Display for SheetFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            SheetFormat::Toml => write!(f, "TOML"),
+            SheetFormat::Json => write!(f, "JSON"),
+            SheetFormat::Yaml => write!(f, "YAML"),
+        }
+    }
+}
+
+/// Scheda completa, con le sezioni allo stesso livello come nel file TOML
+#[derive(Serialize)]
+struct SheetDocument<'a> {
+    #[serde(flatten)]
+    info: &'a CharacterBaseInformation,
+    #[serde(flatten)]
+    nodes: &'a HoneycombData,
+    #[serde(flatten)]
+    list: &'a ListData,
+    #[serde(flatten)]
+    advancement: &'a AdvancementData,
+}
+
+impl SheetFormat {
+    pub const ALL: [SheetFormat; 3] = [SheetFormat::Toml, SheetFormat::Json, SheetFormat::Yaml];
+
+    /// Formato indicato dall'estensione; senza estensione nota è TOML
+    pub fn from_path(path: &Path) -> Self {
+        let ext = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase());
+        match ext.as_deref() {
+            Some("json") => SheetFormat::Json,
+            Some("yaml" | "yml") => SheetFormat::Yaml,
+            _ => SheetFormat::Toml,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            SheetFormat::Toml => "toml",
+            SheetFormat::Json => "json",
+            SheetFormat::Yaml => "yaml",
+        }
+    }
+
+    /// Legge una parte della scheda dal contenuto del file
+    pub fn parse<T: DeserializeOwned>(&self, contents: &str) -> Result<T, String> {
+        match self {
+            SheetFormat::Toml => toml::from_str(contents).map_err(|e| e.to_string()),
+            SheetFormat::Json => serde_json::from_str(contents).map_err(|e| e.to_string()),
+            SheetFormat::Yaml => serde_norway::from_str(contents).map_err(|e| e.to_string()),
+        }
+    }
+
+    /// Contenuto del file della scheda
+    pub(crate) fn sheet_document(
+        &self,
+        info: &CharacterBaseInformation,
+        nodes: &HoneycombData,
+        list: &ListData,
+        advancement: &AdvancementData,
+    ) -> String {
+        let document = SheetDocument {
+            info,
+            nodes,
+            list,
+            advancement,
+        };
+        match self {
+            SheetFormat::Toml => {
+                let mut string = String::new();
+                if let Ok(toml_string) = toml::to_string_pretty(info) {
+                    string.push_str(&toml_string);
+                }
+                if let Ok(toml_string) = toml::to_string_pretty(nodes) {
+                    string.push_str(&toml_string);
+                }
+                if let Ok(toml_string) = toml::to_string_pretty(list) {
+                    string.push_str(&toml_string);
+                }
+                // Le tabelle vanno in fondo per non catturare le chiavi precedenti
+                if let Ok(toml_string) = toml::to_string_pretty(advancement) {
+                    string.push_str(&toml_string);
+                }
+                string
+            }
+            SheetFormat::Json => serde_json::to_string_pretty(&document)
+                .map(|json| json + "\n")
+                .unwrap_or_default(),
+            SheetFormat::Yaml => serde_norway::to_string(&document).unwrap_or_default(),
+        }
+    }
+}
+
+/// Scheda esistente con lo stesso nome in uno dei formati, altrimenti quella TOML
+pub(crate) fn sheet_path(path: &Path) -> PathBuf {
+    SHEET_EXTENSIONS
+        .iter()
+        .map(|ext| path.with_extension(ext))
+        .find(|candidate| candidate.is_file())
+        .unwrap_or_else(|| path.with_extension("toml"))
+}
+
+/// Il file ha l'estensione di una scheda
+pub(crate) fn is_sheet_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| SHEET_EXTENSIONS.iter().any(|e| ext == *e))
+}
+
+#[cfg(test)]
+mod format_tests {
+    use chrono::Local;
+    use std::fs;
+
+    use crate::app::advancement::{Advancement, AdvancementData};
+    use crate::app::format::*;
+    use crate::app::honeycomb::HoneycombNode;
+    use crate::app::sheet::{Sheet, SheetField};
+    use crate::test_support::TempDir;
+
+    /// Scheda con tutte le sezioni compilate, caratteri speciali compresi
+    fn full_sheet() -> (Sheet, AdvancementData) {
+        let mut sheet = Sheet::empty();
+        for (i, field) in SheetField::all().into_iter().enumerate() {
+            let value = match field {
+                SheetField::Node(_, crate::app::NodeField::Tags) => format!("tag{}, mare", i),
+                _ => format!("Valore {}: \"ü\" # {} \\ 'x'\nseconda riga", i, i),
+            };
+            sheet.set(field, value);
+        }
+        sheet.list.misfortunes_red_balls[0] = "2".into();
+        let mut advancement = Advancement::default();
+        advancement.record_change(Local::now(), "Nome".into(), "Lia", "Aria");
+        (sheet, AdvancementData { advancement })
+    }
+
+    fn document(format: SheetFormat, sheet: &Sheet, advancement: &AdvancementData) -> String {
+        format.sheet_document(
+            &sheet.info,
+            &HoneycombData::from_nodes(&sheet.nodes),
+            &sheet.list,
+            advancement,
+        )
+    }
+
+    #[test]
+    fn test_round_trip_is_identical_across_formats() {
+        let (sheet, advancement) = full_sheet();
+        let toml = document(SheetFormat::Toml, &sheet, &advancement);
+
+        for format in SheetFormat::ALL {
+            let contents = document(format, &sheet, &advancement);
+            let restored = Sheet::read(&contents, format).unwrap();
+            assert!(restored.differences(&sheet).is_empty(), "{}", format);
+            let restored_advancement = format.parse::<AdvancementData>(&contents).unwrap();
+            assert_eq!(restored_advancement.advancement, advancement.advancement);
+
+            // Riscritta in TOML è identica all'originale
+            let back = document(SheetFormat::Toml, &restored, &restored_advancement);
+            assert_eq!(back, toml, "{}", format);
+        }
+    }
+
+    #[test]
+    fn test_read_rejects_scalars_and_truncated_files() {
+        for scalar in ["42", "\"Lia\"", "null", "true", "[]"] {
+            assert!(
+                Sheet::read(scalar, SheetFormat::Json).is_none(),
+                "{}",
+                scalar
+            );
+            assert!(
+                Sheet::read(scalar, SheetFormat::Yaml).is_none(),
+                "{}",
+                scalar
+            );
+        }
+
+        let (sheet, advancement) = full_sheet();
+        for format in SheetFormat::ALL {
+            let contents = document(format, &sheet, &advancement);
+            assert!(Sheet::read(&contents, format).is_some(), "{}", format);
+            // Interrotto a ogni riga, come durante una scrittura a metà. Il JSON
+            // è chiuso dalle parentesi; in TOML e YAML un taglio tra le ultime
+            // voci lascia un file valido, più corto.
+            let complete = match format {
+                SheetFormat::Json => contents.trim_end().len(),
+                _ => contents.find("\nlessons").unwrap(),
+            };
+            for (cut, _) in contents
+                .match_indices('\n')
+                .filter(|(cut, _)| *cut < complete)
+            {
+                assert!(
+                    Sheet::read(&contents[..cut], format).is_none(),
+                    "{} troncato a {}",
+                    format,
+                    cut
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_format_from_extension() {
+        assert_eq!(
+            SheetFormat::from_path(Path::new("pg.json")),
+            SheetFormat::Json
+        );
+        assert_eq!(
+            SheetFormat::from_path(Path::new("pg.YML")),
+            SheetFormat::Yaml
+        );
+        assert_eq!(
+            SheetFormat::from_path(Path::new("pg.toml")),
+            SheetFormat::Toml
+        );
+        assert_eq!(SheetFormat::from_path(Path::new("pg")), SheetFormat::Toml);
+
+        assert!(Sheet::read("", SheetFormat::Json).is_none());
+        assert!(Sheet::read("{ \"name\": ", SheetFormat::Json).is_none());
+        assert!(Sheet::read("name: [Lia", SheetFormat::Yaml).is_none());
+        assert!(
+            SheetFormat::Json
+                .parse::<CharacterBaseInformation>("")
+                .is_err()
+        );
+        assert_eq!(Sheet::parse("", SheetFormat::Yaml).nodes.len(), 19);
+        assert!(!HoneycombNode::from_contents("{}", SheetFormat::Json).is_empty());
+    }
+
+    #[test]
+    fn test_existing_sheet_is_found() {
+        let dir = TempDir::new("format");
+        let base = dir.join("lia.toml");
+        assert_eq!(sheet_path(&base), base);
+
+        fs::write(dir.join("lia.yml"), "name: Lia\n").unwrap();
+        assert_eq!(sheet_path(&base), dir.join("lia.yml"));
+        fs::write(dir.join("lia.json"), "{}").unwrap();
+        assert_eq!(sheet_path(&base), dir.join("lia.json"));
+        assert!(is_sheet_file(&dir.join("lia.json")));
+        assert!(!is_sheet_file(&dir.join("lia.toml.bak")));
+    }
+}
@@ -1,21 +1,105 @@
+use chrono::prelude::*;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use super::app_state::{MAX_DRAW, MAX_TOKEN, MIN_DRAW};
+use super::proof::DrawProof;
 use super::types::BallType;
 
+/// Formato leggibile di data e ora usato da log e registri
+const TIME_FORMAT: &str = "%A %e %B %Y, %T";
+
+/// Data e ora in forma leggibile
+pub(crate) fn format_time(time: &DateTime<Local>) -> String {
+    time.format(TIME_FORMAT).to_string()
+}
+
+/// Orario in RFC 3339 o nel vecchio formato testuale
+fn parse_time(text: &str) -> Option<DateTime<Local>> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(text) {
+        return Some(time.with_timezone(&Local));
+    }
+    NaiveDateTime::parse_from_str(text.trim(), TIME_FORMAT)
+        .ok()
+        .and_then(|naive| Local.from_local_datetime(&naive).earliest())
+}
+
+/// Legge un orario salvato in RFC 3339 o nel vecchio formato testuale
+pub(crate) fn deserialize_time<'de, D>(deserializer: D) -> Result<DateTime<Local>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let text = String::deserialize(deserializer)?;
+    parse_time(&text)
+        .ok_or_else(|| serde::de::Error::custom(format!("orario non valido: {}", text)))
+}
+
+/// Come `deserialize_time`, per gli orari facoltativi
+pub(crate) fn deserialize_optional_time<'de, D>(
+    deserializer: D,
+) -> Result<Option<DateTime<Local>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|text| {
+            parse_time(&text)
+                .ok_or_else(|| serde::de::Error::custom(format!("orario non valido: {}", text)))
+        })
+        .transpose()
+}
+
+/// Numero di token estratti al primo tentativo ammesso dal gioco
+pub(crate) fn valid_count(count: usize) -> bool {
+    (MIN_DRAW..=MAX_DRAW).contains(&count)
+}
+
 /// Cronologia di una singola estrazione
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct DrawHistory {
-    pub time: String,
+    #[serde(deserialize_with = "deserialize_time")]
+    pub time: DateTime<Local>,
+    /// Chi ha effettuato la prova
+    #[serde(default)]
+    pub player: String,
+    /// Descrizione libera della prova
+    pub description: String,
     pub white_balls: usize,
     pub traits: Vec<usize>,
+    /// Nomi dei tratti, leggibili anche da chi non ha la scheda
+    #[serde(default)]
+    pub trait_names: Vec<String>,
     pub red_balls: usize,
     pub misfortunes: [usize; 4],
+    /// Nomi delle sventure messe in gioco
+    #[serde(default)]
+    pub misfortune_names: Vec<String>,
     pub first_draw: Vec<BallType>,
     pub risked: bool,
     pub risk_draw: Vec<BallType>,
     pub confused: bool,
     pub adrenalined: bool,
+    /// Esito pescato da un sacchetto fisico e inserito a mano
+    #[serde(default)]
+    pub manual: bool,
+    /// Seme e impronta delle prove estratte dall'host
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proof: Option<DrawProof>,
 }
 
 impl DrawHistory {
+    /// Orario della prova in forma leggibile
+    pub fn time_label(&self) -> String {
+        format_time(&self.time)
+    }
+
+    /// Sacchetto e token estratti entro i limiti del gioco; le prove ricevute
+    /// da altre istanze che li superano vengono scartate
+    pub fn within_limits(&self) -> bool {
+        self.white_balls <= MAX_TOKEN
+            && self.red_balls <= MAX_TOKEN
+            && self.proof.as_ref().is_none_or(|p| valid_count(p.count))
+    }
+
     pub fn format_balls(&self, balls: &[BallType]) -> String {
         if balls.is_empty() {
             return String::from("-");
@@ -28,3 +112,38 @@ impl DrawHistory {
             .join(", ")
     }
 }
+
+#[cfg(test)]
+mod history_tests {
+    use crate::app::history::*;
+
+    #[derive(Deserialize)]
+    struct Entry {
+        #[serde(deserialize_with = "deserialize_time")]
+        time: DateTime<Local>,
+    }
+
+    #[test]
+    fn test_time_round_trip() {
+        let entry = DrawHistory {
+            time: Local::now(),
+            ..Default::default()
+        };
+        let text = toml::to_string(&entry).unwrap();
+        let back: DrawHistory = toml::from_str(&text).unwrap();
+        assert_eq!(back, entry);
+    }
+
+    #[test]
+    fn test_legacy_time() {
+        let entry: Entry = toml::from_str("time = \"Monday  3 March 2025, 18:04:05\"").unwrap();
+        assert_eq!(
+            entry.time.naive_local(),
+            NaiveDate::from_ymd_opt(2025, 3, 3)
+                .unwrap()
+                .and_hms_opt(18, 4, 5)
+                .unwrap()
+        );
+        assert!(toml::from_str::<Entry>("time = \"ieri\"").is_err());
+    }
+}
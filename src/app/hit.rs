@@ -0,0 +1,105 @@
+use ratatui::layout::Rect;
+
+/// Livello dei tab e dei loro contenuti
+pub const Z_BASE: u8 = 0;
+/// Livello dei popup, che coprono i tab
+pub const Z_POPUP: u8 = 10;
+/// Livello dei menu contestuali, sopra a tutto
+pub const Z_MENU: u8 = 20;
+
+/// Elemento cliccabile dell'interfaccia
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HitId {
+    Tab(usize),
+    WhiteBalls,
+    RedBalls,
+    DrawInput,
+    RandomMode,
+    ForcedFour,
+    CharacterName,
+    CharacterObjective,
+    Node(usize),
+    Misfortune(usize),
+    MisfortuneDifficulty(usize),
+    Resources,
+    Notes,
+    Lesson(usize),
+    Log,
+    /// Voce del menu contestuale
+    MenuAction(usize),
+    /// Popup che copre gli elementi sottostanti
+    Popup,
+}
+
+/// Area di un elemento registrata durante il rendering
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HitRegion {
+    pub id: HitId,
+    pub area: Rect,
+    pub z: u8,
+}
+
+/// Elementi cliccabili dell'ultimo frame disegnato.
+///
+/// Ogni frame riparte da zero: i renderer registrano le aree dei propri elementi
+/// e il mouse viene risolto sull'elemento più in alto nel punto indicato.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HitMap {
+    regions: Vec<HitRegion>,
+}
+
+impl HitMap {
+    pub fn clear(&mut self) {
+        self.regions.clear();
+    }
+
+    pub fn register(&mut self, id: HitId, area: Rect, z: u8) {
+        self.regions.push(HitRegion { id, area, z });
+    }
+
+    /// Elemento nel punto indicato; a parità di livello vince l'ultimo registrato
+    pub fn at(&self, x: u16, y: u16) -> Option<HitId> {
+        self.regions
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| r.area.contains((x, y).into()))
+            .max_by_key(|(i, r)| (r.z, *i))
+            .map(|(_, r)| r.id)
+    }
+
+    /// Area registrata per un elemento nell'ultimo frame
+    pub fn area(&self, id: HitId) -> Option<Rect> {
+        self.regions.iter().find(|r| r.id == id).map(|r| r.area)
+    }
+}
+
+#[cfg(test)]
+mod hit_tests {
+    use crate::app::hit::*;
+
+    #[test]
+    fn test_topmost_region_wins() {
+        let mut hits = HitMap::default();
+        hits.register(HitId::WhiteBalls, Rect::new(0, 0, 10, 5), Z_BASE);
+        hits.register(HitId::Popup, Rect::new(5, 0, 10, 5), Z_POPUP);
+        hits.register(HitId::Node(3), Rect::new(8, 0, 10, 5), Z_BASE);
+
+        assert_eq!(hits.at(2, 2), Some(HitId::WhiteBalls));
+        assert_eq!(hits.at(6, 2), Some(HitId::Popup));
+        assert_eq!(hits.at(9, 2), Some(HitId::Popup));
+        assert_eq!(hits.at(16, 2), Some(HitId::Node(3)));
+        assert_eq!(hits.at(30, 2), None);
+    }
+
+    #[test]
+    fn test_later_region_wins_on_same_level() {
+        let mut hits = HitMap::default();
+        hits.register(HitId::Node(1), Rect::new(0, 0, 10, 5), Z_BASE);
+        hits.register(HitId::Node(2), Rect::new(5, 0, 10, 5), Z_BASE);
+        assert_eq!(hits.at(7, 2), Some(HitId::Node(2)));
+        assert_eq!(hits.area(HitId::Node(1)), Some(Rect::new(0, 0, 10, 5)));
+
+        hits.clear();
+        assert_eq!(hits.at(7, 2), None);
+    }
+}
@@ -1,12 +1,128 @@
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::fs;
 
-const DATA_FILE: &str = "character_sheet.toml";
+use super::campaign::character_file;
+use super::format::SheetFormat;
+use super::search::parse_tags;
+
+/// Numero di nodi della griglia esagonale
+pub const HONEYCOMB_SIZE: usize = 19;
+
+/// Nodo centrale della griglia (Archetipo)
+pub const ARCHETYPE_NODE: usize = 9;
+
+/// Nodi della griglia occupati dalle Qualità
+pub const QUALITY_NODES: [usize; 6] = [4, 5, 8, 10, 13, 14];
+
+//            /‾‾‾\             5 //              |‾‾‾‾|
+//       /‾‾‾\\___//‾‾‾\        4 //        |‾‾‾‾||____||‾‾‾‾|
+//  /‾‾‾\\___//‾‾‾\\___//‾‾‾\   3 //  |‾‾‾‾||____||‾‾‾‾||____||‾‾‾‾|
+//  \___//‾‾‾\\___//‾‾‾\\___/   2 //  |____||‾‾‾‾||____||‾‾‾‾||____|
+//  /‾‾‾\\___//‾‾‾\\___//‾‾‾\   1 //  |‾‾‾‾||____||‾‾‾‾||____||‾‾‾‾|
+//  \___//‾‾‾\\___//‾‾‾\\___/   0 //  |____||‾‾‾‾||____||‾‾‾‾||____|
+//  /‾‾‾\\___//‾‾‾\\___//‾‾‾\  -1 //  |‾‾‾‾||____||‾‾‾‾||____||‾‾‾‾|
+//  \___//‾‾‾\\___//‾‾‾\\___/  -2 //  |____||‾‾‾‾||____||‾‾‾‾||____|
+//       \___//‾‾‾\\___/       -3 //        |____||‾‾‾‾||____|
+//            \___/            -4 //              |____|
+//   -2   -1    0    1    2      //    -2    -1     0     1     2
+/// Posizione (colonna, riga) di ogni nodo, contando da sinistra a destra e
+/// dall'alto in basso (0 a 18). Le righe avanzano a passi di mezza cella.
+const HEX_POSITIONS: [(i16, i16); HONEYCOMB_SIZE] = [
+    // column -2
+    (-2, -2),
+    (-2, 0),
+    (-2, 2), // 0,1,2
+    // column -1
+    (-1, -3),
+    (-1, -1),
+    (-1, 1),
+    (-1, 3), // 3,4,5,6
+    // column 0
+    (0, -4),
+    (0, -2),
+    (0, 0),
+    (0, 2),
+    (0, 4), // 7,8,9,10,11
+    // column 1
+    (1, -3),
+    (1, -1),
+    (1, 1),
+    (1, 3), // 12,13,14,15
+    // column 2
+    (2, -2),
+    (2, 0),
+    (2, 2), // 16,17,18
+];
+
+/// Campo di un nodo in fase di modifica
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NodeField {
+    Title,
+    Description,
+    Tags,
+}
+
+impl NodeField {
+    pub fn length(&self) -> usize {
+        match *self {
+            NodeField::Title => 35,
+            NodeField::Description => 500,
+            NodeField::Tags => 100,
+        }
+    }
+}
+
+impl fmt::Display for NodeField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            NodeField::Title => write!(f, "Titolo"),
+            NodeField::Description => write!(f, "Descrizione"),
+            NodeField::Tags => write!(f, "Tag (separati da virgola)"),
+        }
+    }
+}
+
+/// Tipo di tratto associato a una posizione della griglia
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TraitKind {
+    Archetype,
+    Quality,
+    Ability,
+}
+
+impl TraitKind {
+    pub fn of(idx: usize) -> Self {
+        use TraitKind::*;
+        if idx == ARCHETYPE_NODE {
+            Archetype
+        } else if QUALITY_NODES.contains(&idx) {
+            Quality
+        } else {
+            Ability
+        }
+    }
+}
+
+impl fmt::Display for TraitKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            TraitKind::Archetype => write!(f, "Archetipo"),
+            TraitKind::Quality => write!(f, "Qualità"),
+            TraitKind::Ability => write!(f, "Abilità"),
+        }
+    }
+}
 
 /// Nodo della griglia esagonale (tratti del personaggio)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HoneycombNode {
+    /// Titolo del tratto
     pub text: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
     #[serde(skip)]
     pub x: i16,
     #[serde(skip)]
@@ -27,45 +143,7 @@ impl HoneycombNode {
         let total_width = node_width + spacing_x;
         let total_height = node_height + spacing_y;
 
-        //            /‾‾‾\             5 //              |‾‾‾‾|
-        //       /‾‾‾\\___//‾‾‾\        4 //        |‾‾‾‾||____||‾‾‾‾|
-        //  /‾‾‾\\___//‾‾‾\\___//‾‾‾\   3 //  |‾‾‾‾||____||‾‾‾‾||____||‾‾‾‾|
-        //  \___//‾‾‾\\___//‾‾‾\\___/   2 //  |____||‾‾‾‾||____||‾‾‾‾||____|
-        //  /‾‾‾\\___//‾‾‾\\___//‾‾‾\   1 //  |‾‾‾‾||____||‾‾‾‾||____||‾‾‾‾|
-        //  \___//‾‾‾\\___//‾‾‾\\___/   0 //  |____||‾‾‾‾||____||‾‾‾‾||____|
-        //  /‾‾‾\\___//‾‾‾\\___//‾‾‾\  -1 //  |‾‾‾‾||____||‾‾‾‾||____||‾‾‾‾|
-        //  \___//‾‾‾\\___//‾‾‾\\___/  -2 //  |____||‾‾‾‾||____||‾‾‾‾||____|
-        //       \___//‾‾‾\\___/       -3 //        |____||‾‾‾‾||____|
-        //            \___/            -4 //              |____|
-        //   -2   -1    0    1    2      //    -2    -1     0     1     2
-        let positions = [
-            // column -2
-            (-2, -2),
-            (-2, 0),
-            (-2, 2), // 0,1,2
-            // column -1
-            (-1, -3),
-            (-1, -1),
-            (-1, 1),
-            (-1, 3), // 3,4,5,6
-            // column 0
-            (0, -4),
-            (0, -2),
-            (0, 0),
-            (0, 2),
-            (0, 4), // 7,8,9,10,11
-            // column 1
-            (1, -3),
-            (1, -1),
-            (1, 1),
-            (1, 3), // 12,13,14,15
-            // column 2
-            (2, -2),
-            (2, 0),
-            (2, 2), // 16,17,18
-        ];
-
-        for (i, &(col, row)) in positions.iter().enumerate() {
+        for (i, &(col, row)) in HEX_POSITIONS.iter().enumerate() {
             let text = if i < texts.len() {
                 texts[i].clone()
             } else {
@@ -74,6 +152,8 @@ impl HoneycombNode {
 
             nodes.push(HoneycombNode {
                 text,
+                description: String::new(),
+                tags: Vec::new(),
                 x: col * total_width as i16,
                 y: (row * total_height as i16) / 2,
                 width: node_width,
@@ -85,17 +165,122 @@ impl HoneycombNode {
     }
 
     fn create_honeycomb_layout() -> Vec<Self> {
-        let texts = vec![String::new(); 19];
+        let texts = vec![String::new(); HONEYCOMB_SIZE];
         Self::create_honeycomb_layout_with_data(texts)
     }
 
     pub(crate) fn load_honeycomb_data() -> Vec<Self> {
-        if let Ok(contents) = fs::read_to_string(DATA_FILE)
-            && let Ok(data) = toml::from_str::<HoneycombData>(&contents)
-        {
-            return Self::create_honeycomb_layout_with_data(data.nodes);
+        let path = character_file();
+        fs::read_to_string(&path)
+            .map(|contents| Self::from_contents(&contents, SheetFormat::from_path(&path)))
+            .unwrap_or_else(|_| Self::create_honeycomb_layout())
+    }
+
+    /// Griglia letta dal contenuto del file della scheda
+    pub(crate) fn from_contents(contents: &str, format: SheetFormat) -> Vec<Self> {
+        match format.parse::<HoneycombData>(contents) {
+            Ok(data) => Self::from_data(data),
+            Err(_) => Self::create_honeycomb_layout(),
+        }
+    }
+
+    /// Griglia con i testi salvati
+    pub(crate) fn from_data(data: HoneycombData) -> Vec<Self> {
+        let mut nodes = Self::create_honeycomb_layout_with_data(data.nodes);
+        for (node, description) in nodes.iter_mut().zip(data.descriptions) {
+            node.description = description;
+        }
+        for (node, tags) in nodes.iter_mut().zip(data.tags) {
+            node.tags = tags;
+        }
+        nodes
+    }
+
+    /// Testo del campo indicato
+    pub fn field(&self, field: NodeField) -> String {
+        match field {
+            NodeField::Title => self.text.clone(),
+            NodeField::Description => self.description.clone(),
+            NodeField::Tags => self.tags.join(", "),
+        }
+    }
+
+    /// Aggiorna il campo indicato a partire dal testo inserito
+    pub fn set_field(&mut self, field: NodeField, value: &str) {
+        match field {
+            NodeField::Title => self.text = value.trim().to_string(),
+            NodeField::Description => self.description = value.trim().to_string(),
+            NodeField::Tags => self.tags = parse_tags(value),
         }
-        Self::create_honeycomb_layout()
+    }
+
+    /// Posizione (colonna, riga) del nodo nella griglia, con le righe a passi di mezza cella
+    pub fn grid_position(idx: usize) -> (i16, i16) {
+        HEX_POSITIONS[idx]
+    }
+
+    /// Restituisce i nodi adiacenti a `idx` nella griglia esagonale.
+    ///
+    /// Due celle sono adiacenti se stanno nella stessa colonna a distanza di
+    /// una cella intera, oppure in colonne vicine sfalsate di mezza cella.
+    pub fn neighbors(idx: usize) -> Vec<usize> {
+        let Some(&(col, row)) = HEX_POSITIONS.get(idx) else {
+            return Vec::new();
+        };
+
+        HEX_POSITIONS
+            .iter()
+            .enumerate()
+            .filter(|&(_, &(c, r))| {
+                let (dc, dr) = ((c - col).abs(), (r - row).abs());
+                (dc == 0 && dr == 2) || (dc == 1 && dr == 1)
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Grafo di adiacenza completo, calcolato dalla tabella delle posizioni
+    pub fn adjacency() -> Vec<Vec<usize>> {
+        (0..HONEYCOMB_SIZE).map(Self::neighbors).collect()
+    }
+
+    /// Abilità compilate che non sono collegate a nessuna Qualità compilata
+    pub fn invalid_abilities(nodes: &[Self]) -> Vec<usize> {
+        let graph = Self::adjacency();
+        let filled = |i: usize| nodes.get(i).is_some_and(|n| !n.text.is_empty());
+
+        (0..nodes.len().min(HONEYCOMB_SIZE))
+            .filter(|&i| TraitKind::of(i) == TraitKind::Ability && filled(i))
+            .filter(|&i| {
+                !graph[i]
+                    .iter()
+                    .any(|&n| TraitKind::of(n) == TraitKind::Quality && filled(n))
+            })
+            .collect()
+    }
+
+    /// Nodi vuoti che possono essere compilati come passo successivo.
+    ///
+    /// Si parte dall'Archetipo, le Qualità lo circondano e le Abilità si
+    /// agganciano alle Qualità già compilate.
+    pub fn legal_slots(nodes: &[Self]) -> Vec<usize> {
+        let graph = Self::adjacency();
+        let filled = |i: usize| nodes.get(i).is_some_and(|n| !n.text.is_empty());
+
+        if !filled(ARCHETYPE_NODE) {
+            return vec![ARCHETYPE_NODE];
+        }
+
+        (0..nodes.len().min(HONEYCOMB_SIZE))
+            .filter(|&i| !filled(i))
+            .filter(|&i| match TraitKind::of(i) {
+                TraitKind::Archetype => true,
+                TraitKind::Quality => graph[i].contains(&ARCHETYPE_NODE),
+                TraitKind::Ability => graph[i]
+                    .iter()
+                    .any(|&n| TraitKind::of(n) == TraitKind::Quality && filled(n)),
+            })
+            .collect()
     }
 }
 
@@ -103,4 +288,130 @@ impl HoneycombNode {
 #[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct HoneycombData {
     pub nodes: Vec<String>,
+    #[serde(default)]
+    pub descriptions: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<Vec<String>>,
+}
+
+impl HoneycombData {
+    pub fn from_nodes(nodes: &[HoneycombNode]) -> Self {
+        HoneycombData {
+            nodes: nodes.iter().map(|n| n.text.clone()).collect(),
+            descriptions: nodes.iter().map(|n| n.description.clone()).collect(),
+            tags: nodes.iter().map(|n| n.tags.clone()).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod honeycomb_tests {
+    use crate::app::honeycomb::*;
+
+    fn nodes_with(filled: &[(usize, &str)]) -> Vec<HoneycombNode> {
+        let mut texts = vec![String::new(); HONEYCOMB_SIZE];
+        for &(i, t) in filled {
+            texts[i] = t.to_string();
+        }
+        HoneycombNode::create_honeycomb_layout_with_data(texts)
+    }
+
+    #[test]
+    fn test_trait_kind_of() {
+        assert_eq!(TraitKind::of(9), TraitKind::Archetype);
+        for i in QUALITY_NODES {
+            assert_eq!(TraitKind::of(i), TraitKind::Quality);
+        }
+        assert_eq!(TraitKind::of(0), TraitKind::Ability);
+        assert_eq!(TraitKind::of(18), TraitKind::Ability);
+    }
+
+    #[test]
+    fn test_archetype_surrounded_by_qualities() {
+        let mut neighbors = HoneycombNode::neighbors(ARCHETYPE_NODE);
+        neighbors.sort();
+        assert_eq!(neighbors, QUALITY_NODES.to_vec());
+    }
+
+    #[test]
+    fn test_adjacency_is_symmetric() {
+        let graph = HoneycombNode::adjacency();
+        assert_eq!(graph.len(), HONEYCOMB_SIZE);
+        for (i, neighbors) in graph.iter().enumerate() {
+            assert!(!neighbors.contains(&i));
+            for n in neighbors {
+                assert!(graph[*n].contains(&i));
+            }
+        }
+    }
+
+    #[test]
+    fn test_corner_neighbors() {
+        let mut neighbors = HoneycombNode::neighbors(0);
+        neighbors.sort();
+        assert_eq!(neighbors, vec![1, 3, 4]);
+        assert!(HoneycombNode::neighbors(99).is_empty());
+    }
+
+    #[test]
+    fn test_legal_slots_empty_sheet() {
+        let nodes = nodes_with(&[]);
+        assert_eq!(HoneycombNode::legal_slots(&nodes), vec![ARCHETYPE_NODE]);
+    }
+
+    #[test]
+    fn test_legal_slots_after_archetype() {
+        let nodes = nodes_with(&[(9, "Cavaliere")]);
+        assert_eq!(HoneycombNode::legal_slots(&nodes), QUALITY_NODES.to_vec());
+    }
+
+    #[test]
+    fn test_legal_slots_after_quality() {
+        let nodes = nodes_with(&[(9, "Cavaliere"), (4, "Coraggioso")]);
+        let slots = HoneycombNode::legal_slots(&nodes);
+        for i in [0, 1, 3] {
+            assert!(slots.contains(&i));
+        }
+        assert!(!slots.contains(&2));
+        assert!(!slots.contains(&4));
+    }
+
+    #[test]
+    fn test_node_fields() {
+        let mut nodes = nodes_with(&[(9, "Cavaliere")]);
+        nodes[9].set_field(NodeField::Description, "  Giurato alla corona ");
+        nodes[9].set_field(NodeField::Tags, "onore, spada");
+
+        assert_eq!(nodes[9].description, "Giurato alla corona");
+        assert_eq!(nodes[9].tags, vec!["onore", "spada"]);
+        assert_eq!(nodes[9].field(NodeField::Tags), "onore, spada");
+        assert_eq!(nodes[9].field(NodeField::Title), "Cavaliere");
+    }
+
+    #[test]
+    fn test_honeycomb_data_round_trip() {
+        let mut nodes = nodes_with(&[(9, "Cavaliere")]);
+        nodes[9].description = "Giurato alla corona".to_string();
+        nodes[9].tags = vec!["onore".to_string()];
+
+        let serialized = toml::to_string_pretty(&HoneycombData::from_nodes(&nodes)).unwrap();
+        let data = toml::from_str::<HoneycombData>(&serialized).unwrap();
+        assert_eq!(data.nodes[9], "Cavaliere");
+        assert_eq!(data.descriptions[9], "Giurato alla corona");
+        assert_eq!(data.tags[9], vec!["onore"]);
+
+        let legacy = toml::from_str::<HoneycombData>("nodes = [\"a\"]").unwrap();
+        assert!(legacy.descriptions.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_abilities() {
+        let nodes = nodes_with(&[
+            (9, "Cavaliere"),
+            (4, "Coraggioso"),
+            (0, "Spada"),
+            (18, "Arco"),
+        ]);
+        assert_eq!(HoneycombNode::invalid_abilities(&nodes), vec![18]);
+    }
 }
@@ -0,0 +1,215 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::super::action::{Action, Confirm};
+use super::super::app_state::{App, MAX_TOKEN};
+use super::super::export::sheet_markdown;
+use super::super::types::PopupType;
+
+impl App {
+    /// Esegue un'azione, qualunque sia il tasto o il comando che l'ha chiesta
+    pub fn run_action(&mut self, action: Action) -> Result<(), String> {
+        match action {
+            Action::SwitchTab(tab) => {
+                if !self.mode.tabs().contains(&tab) {
+                    return Err("Scheda non disponibile in questo ruolo".into());
+                }
+                self.current_tab = tab;
+            }
+            Action::ToggleTrait(idx) => {
+                let node = self.honeycomb_nodes.get(idx).ok_or("Tratto inesistente")?;
+                if node.text.is_empty() {
+                    return Err("Il tratto è vuoto".into());
+                }
+                if !self.used_traits.contains(&idx) && self.white_balls >= MAX_TOKEN {
+                    return Err(format!("Al massimo {} token bianchi", MAX_TOKEN));
+                }
+                self.toggle_trait(idx);
+            }
+            Action::ToggleMisfortune(idx) => {
+                let name = self
+                    .list_data
+                    .misfortunes
+                    .get(idx)
+                    .ok_or("Sventura inesistente")?;
+                if name.is_empty() {
+                    return Err("La sventura è vuota".into());
+                }
+                self.toggle_misfortune(idx);
+            }
+            Action::SetWhiteBalls(n) | Action::SetRedBalls(n) if n > MAX_TOKEN => {
+                return Err(format!("Inserire un numero da 0 a {}", MAX_TOKEN));
+            }
+            Action::SetWhiteBalls(n) => {
+                self.white_balls = n;
+                self.used_traits.truncate(n);
+            }
+            Action::SetRedBalls(n) => {
+                // Le sventure in gioco restano nel conteggio
+                self.red_balls = n.max(self.additional_red_balls.iter().sum());
+            }
+            Action::StartDraw => {
+                if self.white_balls == 0 || self.red_balls == 0 {
+                    return Err("Servono almeno un token bianco e uno rosso".into());
+                }
+                if self.manual_mode && !self.authoritative_session() {
+                    self.open_manual_result();
+                } else {
+                    self.popup = PopupType::ConfirmDraw;
+                }
+            }
+            Action::Reset => self.reset(),
+            Action::ExportSheet(path) => {
+                if same_file(Path::new(&path), &self.sheet_file) {
+                    return Err("Non si può esportare sopra il file della scheda in uso".into());
+                }
+                if Path::new(&path).exists() {
+                    self.ask_confirm(Confirm::OverwriteExport(path));
+                } else {
+                    self.export_sheet(&path)?;
+                }
+            }
+            Action::ImportSheet(path) => self.open_sheet_import(&path)?,
+            Action::ConvertSheet(format) => self.convert_sheet(format)?,
+            Action::SwitchCharacter(name) => self.switch_character(&name)?,
+            Action::OpenTimeline => self.open_timeline(),
+            Action::OpenSessions => self.open_sessions(),
+            Action::OpenCampaigns => self.open_campaigns(),
+            Action::OpenTraitSearch => self.open_trait_search(),
+            Action::OpenTraitPicker => self.open_trait_picker(),
+            Action::OpenQuickEntry => self.open_quick_entry(),
+            Action::ToggleManual => {
+                if self.authoritative_session() {
+                    return Err("Nella sessione autorevole le prove le estrae l'host".into());
+                }
+                self.manual_mode = !self.manual_mode;
+            }
+            Action::StartWizard if !self.is_sheet_empty() => {
+                self.ask_confirm(Confirm::NewCharacter)
+            }
+            Action::StartWizard => self.start_wizard(),
+        }
+        Ok(())
+    }
+
+    /// Chiede conferma prima di un'operazione che sovrascrive dati
+    pub(crate) fn ask_confirm(&mut self, confirm: Confirm) {
+        self.confirm = Some(confirm);
+        self.popup = PopupType::Confirm;
+    }
+
+    /// Esegue l'operazione confermata nel popup
+    pub fn accept_confirm(&mut self) {
+        self.popup = PopupType::None;
+        match self.confirm.take() {
+            Some(Confirm::NewCharacter) => self.start_wizard(),
+            Some(Confirm::OverwriteExport(path)) => {
+                if let Err(e) = self.export_sheet(&path) {
+                    self.status_message = Some(e);
+                }
+            }
+            Some(Confirm::ClearTrait(idx)) => self.clear_trait(idx),
+            None => {}
+        }
+    }
+
+    /// Scrive la scheda in Markdown nel file indicato
+    fn export_sheet(&mut self, path: &str) -> Result<(), String> {
+        let markdown = sheet_markdown(
+            &self.character_base_info,
+            &self.honeycomb_nodes,
+            &self.list_data,
+        );
+        fs::write(path, markdown).map_err(|e| format!("Esportazione non riuscita: {}", e))?;
+        self.status_message = Some(format!("Scheda esportata in {}", path));
+        Ok(())
+    }
+}
+
+/// I due percorsi indicano lo stesso file, anche se non esiste ancora
+fn same_file(a: &Path, b: &Path) -> bool {
+    let resolve = |path: &Path| -> Option<PathBuf> {
+        let parent = path.parent().filter(|p| !p.as_os_str().is_empty());
+        let dir = fs::canonicalize(parent.unwrap_or(Path::new("."))).ok()?;
+        Some(dir.join(path.file_name()?))
+    };
+    resolve(a).is_some_and(|a| Some(a) == resolve(b))
+}
+
+#[cfg(test)]
+mod action_impl_tests {
+    use std::fs;
+
+    use crate::app::action::Action;
+    use crate::app::{App, MAX_TOKEN, PopupType, TabType};
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn test_counts_and_draw() {
+        let mut app = App::new();
+        assert!(app.run_action(Action::StartDraw).is_err());
+
+        app.honeycomb_nodes[9].text = "Cartografa".into();
+        app.run_action(Action::ToggleTrait(9)).unwrap();
+        app.run_action(Action::SetRedBalls(2)).unwrap();
+        assert_eq!((app.white_balls, app.red_balls), (1, 2));
+
+        app.run_action(Action::SetWhiteBalls(0)).unwrap();
+        assert!(app.used_traits.is_empty());
+        app.run_action(Action::SetWhiteBalls(3)).unwrap();
+        app.run_action(Action::StartDraw).unwrap();
+        assert_eq!(app.popup, PopupType::ConfirmDraw);
+    }
+
+    #[test]
+    fn test_invalid_targets_are_reported() {
+        let mut app = App::new();
+        app.honeycomb_nodes[0].text.clear();
+        assert!(app.run_action(Action::ToggleTrait(0)).is_err());
+        assert!(app.run_action(Action::ToggleTrait(99)).is_err());
+        app.honeycomb_nodes[1].text = "Agile".into();
+        app.white_balls = MAX_TOKEN;
+        assert!(app.run_action(Action::ToggleTrait(1)).is_err());
+        assert!(app.run_action(Action::SetRedBalls(MAX_TOKEN + 1)).is_err());
+        assert!(app.run_action(Action::SetWhiteBalls(usize::MAX)).is_err());
+        assert!(
+            app.run_action(Action::SwitchTab(TabType::NarratorCastTab))
+                .is_err()
+        );
+        app.run_action(Action::SwitchTab(TabType::LogTab)).unwrap();
+        assert_eq!(app.current_tab, TabType::LogTab);
+    }
+
+    #[test]
+    fn test_export_asks_before_overwriting() {
+        let dir = TempDir::new("export");
+        let target = dir.join("scheda.md");
+        let path = target.to_str().unwrap().to_string();
+
+        let mut app = App::new();
+        app.character_base_info.name = "Lia".into();
+        app.run_action(Action::ExportSheet(path.clone())).unwrap();
+        assert!(fs::read_to_string(&target).unwrap().contains("Lia"));
+
+        app.character_base_info.name = "Bruno".into();
+        app.run_action(Action::ExportSheet(path.clone())).unwrap();
+        assert_eq!(app.popup, PopupType::Confirm);
+        assert!(fs::read_to_string(&target).unwrap().contains("Lia"));
+        app.accept_confirm();
+        assert!(fs::read_to_string(&target).unwrap().contains("Bruno"));
+
+        // Il file della scheda in uso non diventa Markdown
+        let sheet = format!("./{}", app.sheet_file.display());
+        assert!(app.run_action(Action::ExportSheet(sheet)).is_err());
+    }
+
+    #[test]
+    fn test_misfortunes_stay_in_red_count() {
+        let mut app = App::new();
+        app.list_data.misfortunes[0] = "Ferito".into();
+        app.list_data.misfortunes_red_balls[0] = "2".into();
+        app.run_action(Action::ToggleMisfortune(0)).unwrap();
+        app.run_action(Action::SetRedBalls(0)).unwrap();
+        assert_eq!(app.red_balls, 2);
+    }
+}
@@ -0,0 +1,182 @@
+use chrono::Local;
+
+use super::super::app_state::App;
+use super::super::honeycomb::{HoneycombNode, TraitKind};
+use super::super::types::PopupType;
+
+impl App {
+    /// Registra una modifica alla scheda nel registro dell'avanzamento
+    pub(crate) fn record_sheet_change(&mut self, field: String, before: &str, after: &str) {
+        let changes = self.advancement.changes.len();
+        self.advancement
+            .record_change(Local::now(), field, before, after);
+        if self.advancement.changes.len() > changes
+            && let Some(session) = self.journal.active_mut()
+        {
+            session
+                .changes
+                .extend(self.advancement.changes.last().cloned());
+        }
+    }
+
+    /// Registra una lezione appresa collegandola all'ultima prova effettuata
+    pub(crate) fn learn_lesson(&mut self, slot: usize) {
+        let text = self.list_data.lessons[slot].clone();
+        if text.is_empty() {
+            return;
+        }
+        let draw = self
+            .history
+            .last()
+            .map(|entry| (self.history.len(), entry.time));
+        self.advancement
+            .learn_lesson(Local::now(), slot, &text, draw);
+        if let Some(session) = self.journal.active_mut() {
+            session
+                .lessons
+                .extend(self.advancement.lessons.last().cloned());
+        }
+    }
+
+    /// Un nuovo tratto va in uno slot sbloccato da una lezione, una volta che
+    /// il personaggio ha imparato la prima; prima la griglia è in creazione
+    pub(crate) fn check_new_trait(&self, node: usize, text: &str) -> Result<(), String> {
+        let new_trait = self.honeycomb_nodes[node].text.is_empty() && !text.trim().is_empty();
+        if !new_trait
+            || self.advancement.lessons.is_empty()
+            || self.advancement.unlocked_nodes().contains(&node)
+        {
+            return Ok(());
+        }
+        Err("Lo slot va prima sbloccato con una lezione".to_string())
+    }
+
+    /// Usa l'ultima lezione non ancora spesa per sbloccare il nodo selezionato.
+    /// Il nodo deve essere uno slot libero adiacente ai tratti già compilati.
+    pub fn unlock_slot_with_lesson(&mut self) -> Result<usize, String> {
+        let node = self.selected_node.ok_or("Nessun nodo selezionato")?;
+        if !HoneycombNode::legal_slots(&self.honeycomb_nodes).contains(&node) {
+            return Err("Il nodo non è uno slot disponibile".to_string());
+        }
+        if self.advancement.unlocked_nodes().contains(&node) {
+            return Err("Il nodo è già stato sbloccato".to_string());
+        }
+        let lesson = self
+            .advancement
+            .pending_lesson()
+            .ok_or("Nessuna lezione disponibile per sbloccare uno slot")?;
+
+        self.advancement.lessons[lesson].unlocked_node = Some(node);
+        let text = self.advancement.lessons[lesson].text.clone();
+        self.record_sheet_change(format!("Slot {} {}", TraitKind::of(node), node), "", &text);
+        if let Some(change) = self.advancement.changes.last_mut() {
+            change.lesson = Some(lesson);
+        }
+        self.save_data();
+        Ok(node)
+    }
+
+    /// Apre la linea temporale dell'evoluzione del personaggio
+    pub fn open_timeline(&mut self) {
+        self.timeline_scroll = 0;
+        self.popup = PopupType::Timeline;
+    }
+}
+
+#[cfg(test)]
+mod advancement_impl_tests {
+    use crate::app::{App, Campaign, HoneycombNode, ListSection, NodeField};
+    use crate::test_support::{ActiveScope, TempDir};
+
+    #[test]
+    fn test_learn_lesson_links_last_draw() {
+        let mut app = App::new();
+        app.white_balls = 2;
+        app.red_balls = 1;
+        app.perform_first_draw();
+        app.cancel_draw();
+        app.list_data.lessons[1] = "Non fidarti dei nobili".to_string();
+
+        app.learn_lesson(1);
+
+        let lesson = app.advancement.lessons.last().unwrap();
+        assert_eq!(lesson.slot, 1);
+        assert_eq!(lesson.draw, Some(1));
+        assert_eq!(lesson.draw_time, Some(app.history[0].time));
+    }
+
+    #[test]
+    fn test_learn_empty_lesson_is_ignored() {
+        let mut app = App::new();
+        let before = app.advancement.lessons.len();
+        app.list_data.lessons[2].clear();
+        app.learn_lesson(2);
+        assert_eq!(app.advancement.lessons.len(), before);
+    }
+
+    #[test]
+    fn test_unlock_requires_legal_slot() {
+        let mut app = App::new();
+        for node in app.honeycomb_nodes.iter_mut() {
+            node.text.clear();
+        }
+        app.selected_node = Some(0);
+        assert!(app.unlock_slot_with_lesson().is_err());
+    }
+
+    #[test]
+    fn test_lesson_is_learned_once_per_text() {
+        let root = TempDir::new("lessons");
+        let campaign = Campaign::create(root.path(), "Prova").unwrap();
+        let _scope = ActiveScope::new(&campaign);
+        let mut app = App::new();
+        app.selected_list_item = Some((ListSection::Lessons, 0));
+        let save = |app: &mut App, text: &str| {
+            app.start_list_editing();
+            app.list_edit_buffer = text.to_string();
+            app.finish_list_editing();
+            app.advancement.lessons.len()
+        };
+
+        assert_eq!(save(&mut app, "Ascoltare"), 1);
+        // Salvare di nuovo lo stesso testo non è una nuova lezione
+        assert_eq!(save(&mut app, "Ascoltare "), 1);
+        assert_eq!(save(&mut app, "Ascoltare prima di parlare"), 2);
+        assert_eq!(save(&mut app, ""), 2);
+    }
+
+    #[test]
+    fn test_new_traits_need_an_unlocked_slot() {
+        let root = TempDir::new("slots");
+        let campaign = Campaign::create(root.path(), "Prova").unwrap();
+        let _scope = ActiveScope::new(&campaign);
+        let mut app = App::new();
+        for node in app.honeycomb_nodes.iter_mut() {
+            node.text.clear();
+        }
+        // In creazione la griglia si compila liberamente
+        let archetype = HoneycombNode::legal_slots(&app.honeycomb_nodes)[0];
+        app.set_node_field(archetype, NodeField::Title, "Cartografa")
+            .unwrap();
+
+        app.list_data.lessons[0] = "Ascoltare".into();
+        app.learn_lesson(0);
+        let slot = HoneycombNode::legal_slots(&app.honeycomb_nodes)[0];
+        assert!(
+            app.set_node_field(slot, NodeField::Title, "Paziente")
+                .is_err()
+        );
+        assert!(app.honeycomb_nodes[slot].text.is_empty());
+        // Descrizioni e modifiche ai tratti esistenti restano libere
+        app.set_node_field(slot, NodeField::Description, "Sa aspettare")
+            .unwrap();
+        app.set_node_field(archetype, NodeField::Title, "Esploratrice")
+            .unwrap();
+
+        app.selected_node = Some(slot);
+        app.unlock_slot_with_lesson().unwrap();
+        app.set_node_field(slot, NodeField::Title, "Paziente")
+            .unwrap();
+        assert_eq!(app.honeycomb_nodes[slot].text, "Paziente");
+    }
+}
@@ -0,0 +1,183 @@
+use std::fs;
+use std::path::Path;
+
+use super::super::advancement::Advancement;
+use super::super::app_state::App;
+use super::super::campaign::{
+    CAMPAIGNS_DIR, Campaign, CampaignFocus, CampaignInput, character_file,
+};
+use super::super::character::CharacterBaseInformation;
+use super::super::honeycomb::HoneycombNode;
+use super::super::journal::Journal;
+use super::super::list::ListData;
+use super::super::narrator::{AppMode, Narrator};
+use super::super::types::PopupType;
+
+impl App {
+    /// Apre la finestra delle campagne
+    pub fn open_campaigns(&mut self) {
+        self.load_campaign_list();
+        self.load_campaign_characters();
+        self.campaign_selected = self
+            .campaign
+            .as_ref()
+            .and_then(|c| self.campaign_list.iter().position(|(dir, _)| *dir == c.dir))
+            .unwrap_or(0);
+        self.character_selected = self.active_character_idx();
+        self.campaign_focus = CampaignFocus::Campaigns;
+        self.campaign_input = None;
+        self.popup = PopupType::Campaigns;
+    }
+
+    /// Rilegge le campagne presenti e i loro nomi
+    fn load_campaign_list(&mut self) {
+        self.campaign_list = Campaign::list(Path::new(CAMPAIGNS_DIR))
+            .into_iter()
+            .map(|dir| {
+                let name = Campaign::open(&dir)
+                    .map(|c| c.name)
+                    .unwrap_or_else(|_| dir.display().to_string());
+                (dir, name)
+            })
+            .collect();
+    }
+
+    /// Rilegge i personaggi della campagna aperta
+    pub fn load_campaign_characters(&mut self) {
+        self.campaign_characters = self
+            .campaign
+            .as_ref()
+            .map(|c| c.characters())
+            .unwrap_or_default();
+    }
+
+    /// Posizione del personaggio in uso tra quelli della campagna
+    fn active_character_idx(&self) -> usize {
+        self.campaign
+            .as_ref()
+            .and_then(|c| {
+                self.campaign_characters
+                    .iter()
+                    .position(|p| *p == c.character)
+            })
+            .unwrap_or(0)
+    }
+
+    /// Sposta la selezione nell'elenco attivo
+    pub fn select_campaign_entry(&mut self, next: bool) {
+        let (selected, len) = match self.campaign_focus {
+            CampaignFocus::Campaigns => (&mut self.campaign_selected, self.campaign_list.len()),
+            CampaignFocus::Characters => {
+                (&mut self.character_selected, self.campaign_characters.len())
+            }
+        };
+        if next {
+            if *selected + 1 < len {
+                *selected += 1;
+            }
+        } else {
+            *selected = selected.saturating_sub(1);
+        }
+    }
+
+    /// Apre la campagna o il personaggio selezionato
+    pub fn confirm_campaign_entry(&mut self) -> Result<(), String> {
+        match self.campaign_focus {
+            CampaignFocus::Campaigns => {
+                let (dir, _) = self
+                    .campaign_list
+                    .get(self.campaign_selected)
+                    .cloned()
+                    .ok_or("Nessuna campagna selezionata")?;
+                self.switch_campaign(&dir)
+            }
+            CampaignFocus::Characters => {
+                self.campaign.as_ref().ok_or("Nessuna campagna aperta")?;
+                let character = self
+                    .campaign_characters
+                    .get(self.character_selected)
+                    .cloned()
+                    .ok_or("Nessun personaggio selezionato")?;
+                self.switch_character(&character)
+            }
+        }
+    }
+
+    /// Mette in uso un personaggio della campagna aperta e ne carica la scheda
+    pub fn switch_character(&mut self, name: &str) -> Result<(), String> {
+        let campaign = self.campaign.as_mut().ok_or("Nessuna campagna aperta")?;
+        campaign.select_character(name).map_err(|e| e.to_string())?;
+        campaign.activate();
+        self.reload_data();
+        self.status_message = Some(format!("Personaggio {} in uso", name));
+        Ok(())
+    }
+
+    /// Rende attiva la campagna nella cartella indicata e ne carica i dati
+    pub fn switch_campaign(&mut self, dir: &Path) -> Result<(), String> {
+        let campaign = Campaign::open(dir).map_err(|e| format!("Campagna non valida: {}", e))?;
+        campaign.activate();
+        self.status_message = Some(format!("Campagna {} aperta", campaign.name));
+        self.campaign = Some(campaign);
+        self.load_campaign_characters();
+        self.reload_data();
+        self.character_selected = self.active_character_idx();
+        Ok(())
+    }
+
+    /// Conferma il testo digitato nella finestra delle campagne
+    pub fn confirm_campaign_input(&mut self) -> Result<(), String> {
+        let Some((input, text)) = self.campaign_input.take() else {
+            return Ok(());
+        };
+        match input {
+            CampaignInput::NewCampaign => {
+                let campaign =
+                    Campaign::create(Path::new(CAMPAIGNS_DIR), &text).map_err(|e| e.to_string())?;
+                let dir = campaign.dir.clone();
+                self.load_campaign_list();
+                self.campaign_selected = self
+                    .campaign_list
+                    .iter()
+                    .position(|(d, _)| *d == dir)
+                    .unwrap_or(0);
+                self.switch_campaign(&dir)
+            }
+            CampaignInput::NewCharacter => {
+                let campaign = self.campaign.as_mut().ok_or("Nessuna campagna aperta")?;
+                campaign.add_character(&text).map_err(|e| e.to_string())?;
+                campaign.activate();
+                self.load_campaign_characters();
+                self.reload_data();
+                self.character_selected = self.active_character_idx();
+                self.campaign_focus = CampaignFocus::Characters;
+                Ok(())
+            }
+            CampaignInput::Chronicle => {
+                let campaign = self.campaign.as_mut().ok_or("Nessuna campagna aperta")?;
+                campaign.add_chronicle(&text).map_err(|e| e.to_string())
+            }
+        }
+    }
+
+    /// Ricarica scheda, registri e note dai file della campagna attiva
+    pub(crate) fn reload_data(&mut self) {
+        let popup = self.popup;
+        self.reset();
+        self.popup = popup;
+        self.history.clear();
+        self.log_filter = Default::default();
+        self.log_selected = 0;
+        self.update_vertical_scroll_state();
+        self.character_base_info = CharacterBaseInformation::load_character_base_info();
+        self.honeycomb_nodes = HoneycombNode::load_honeycomb_data();
+        self.list_data = ListData::load_list_data();
+        self.advancement = Advancement::load_advancement();
+        self.sheet_file = character_file();
+        self.sheet_on_disk = fs::read_to_string(&self.sheet_file).unwrap_or_default();
+        self.journal = Journal::load_journal();
+        if self.mode == AppMode::Narrator {
+            self.narrator = Narrator::load_narrator();
+        }
+    }
+}
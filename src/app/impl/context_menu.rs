@@ -0,0 +1,164 @@
+use super::super::action::Confirm;
+use super::super::app_state::App;
+use super::super::context_menu::{ContextMenu, MenuAction};
+use super::super::hit::HitId;
+use super::super::honeycomb::NodeField;
+use super::super::list::ListSection;
+use super::super::types::PopupType;
+
+impl App {
+    /// Apre nel punto cliccato il menu contestuale dell'elemento, se ne ha uno
+    pub fn open_context_menu(&mut self, hit: HitId, x: u16, y: u16) {
+        let menu = match hit {
+            HitId::Node(idx) => {
+                self.selected_node = Some(idx);
+                ContextMenu::for_trait(idx, x, y)
+            }
+            HitId::Misfortune(idx) | HitId::MisfortuneDifficulty(idx) => {
+                self.selected_list_item = Some((ListSection::Misfortunes, idx));
+                ContextMenu::for_misfortune(idx, x, y)
+            }
+            _ => return,
+        };
+        self.context_menu = Some(menu);
+        self.popup = PopupType::ContextMenu;
+    }
+
+    /// Sposta la selezione nel menu contestuale
+    pub fn select_context_action(&mut self, next: bool) {
+        if let Some(menu) = &mut self.context_menu {
+            if next {
+                menu.selected = (menu.selected + 1).min(menu.actions.len().saturating_sub(1));
+            } else {
+                menu.selected = menu.selected.saturating_sub(1);
+            }
+        }
+    }
+
+    /// Chiude il menu contestuale
+    pub fn close_context_menu(&mut self) {
+        self.context_menu = None;
+        self.popup = PopupType::None;
+    }
+
+    /// Esegue la voce selezionata del menu contestuale e lo chiude
+    pub fn run_context_action(&mut self) {
+        let Some(action) = self
+            .context_menu
+            .as_ref()
+            .and_then(|menu| menu.actions.get(menu.selected).copied())
+        else {
+            return;
+        };
+        self.close_context_menu();
+
+        match action {
+            MenuAction::EditTrait(idx) => {
+                self.selected_node = Some(idx);
+                self.start_node_editing();
+            }
+            MenuAction::ToggleTrait(idx) => self.toggle_trait(idx),
+            MenuAction::ClearTrait(idx) => self.ask_confirm(Confirm::ClearTrait(idx)),
+            MenuAction::ToggleMisfortune(idx) => self.toggle_misfortune(idx),
+            MenuAction::EditDifficulty(idx) => {
+                self.selected_list_item = Some((ListSection::MisfortunesDifficult, idx));
+                self.start_list_editing();
+            }
+        }
+    }
+
+    /// Svuota un tratto della griglia, togliendolo prima dalla prova
+    pub(crate) fn clear_trait(&mut self, idx: usize) {
+        if self.used_traits.contains(&idx) {
+            self.toggle_trait(idx);
+        }
+        self.selected_node = Some(idx);
+        self.node_field = NodeField::Title;
+        self.node_edit_buffer.clear();
+        self.finish_node_editing();
+    }
+}
+
+#[cfg(test)]
+mod context_menu_impl_tests {
+    use crate::app::context_menu::ContextMenu;
+    use crate::app::{App, Campaign, MAX_TOKEN, PopupType};
+    use crate::test_support::{ActiveScope, TempDir};
+
+    fn open(app: &mut App, menu: ContextMenu, selected: usize) {
+        app.context_menu = Some(ContextMenu { selected, ..menu });
+        app.popup = PopupType::ContextMenu;
+    }
+
+    #[test]
+    fn test_toggle_trait_from_menu() {
+        let mut app = App::new();
+        app.honeycomb_nodes[4].text = "Agile".into();
+        open(&mut app, ContextMenu::for_trait(4, 0, 0), 1);
+
+        app.run_context_action();
+        assert_eq!(app.popup, PopupType::None);
+        assert!(app.used_traits.contains(&4));
+        assert_eq!(app.white_balls, 1);
+    }
+
+    #[test]
+    fn test_clear_trait_asks_confirmation() {
+        let root = TempDir::new("clear-trait");
+        let campaign = Campaign::create(root.path(), "Prova").unwrap();
+        let _scope = ActiveScope::new(&campaign);
+        let mut app = App::new();
+        app.honeycomb_nodes[4].text = "Agile".into();
+        app.toggle_trait(4);
+
+        // Annullando, il tratto resta com'era
+        open(&mut app, ContextMenu::for_trait(4, 0, 0), 2);
+        app.run_context_action();
+        assert_eq!(app.popup, PopupType::Confirm);
+        app.confirm = None;
+        app.popup = PopupType::None;
+        assert_eq!(app.honeycomb_nodes[4].text, "Agile");
+
+        open(&mut app, ContextMenu::for_trait(4, 0, 0), 2);
+        app.run_context_action();
+        app.accept_confirm();
+        assert_eq!(app.popup, PopupType::None);
+        assert!(app.honeycomb_nodes[4].text.is_empty());
+        assert!(!app.used_traits.contains(&4));
+        assert_eq!(app.white_balls, 0);
+    }
+
+    #[test]
+    fn test_toggle_misfortune_from_menu() {
+        let mut app = App::new();
+        app.list_data.misfortunes[2] = "Ferito".into();
+        app.list_data.misfortunes_red_balls[2] = "2".into();
+        open(&mut app, ContextMenu::for_misfortune(2, 0, 0), 0);
+
+        app.run_context_action();
+        assert_eq!(app.red_balls, 2);
+        assert_eq!(app.additional_red_balls[2], 2);
+    }
+
+    #[test]
+    fn test_edit_difficulty_from_menu() {
+        let mut app = App::new();
+        open(&mut app, ContextMenu::for_misfortune(1, 0, 0), 1);
+        app.select_context_action(true);
+        app.run_context_action();
+        assert!(app.editing_list_item);
+    }
+
+    #[test]
+    fn test_misfortune_red_balls_capped() {
+        let mut app = App::new();
+        app.red_balls = MAX_TOKEN - 1;
+        app.list_data.misfortunes[0] = "Ferito".into();
+        app.list_data.misfortunes_red_balls[0] = "3".into();
+
+        app.toggle_misfortune(0);
+        assert_eq!(app.red_balls, MAX_TOKEN);
+        app.toggle_misfortune(0);
+        assert_eq!(app.red_balls, MAX_TOKEN - 1);
+    }
+}
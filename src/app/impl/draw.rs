@@ -1,9 +1,11 @@
-use chrono::prelude::*;
-use rand::prelude::IndexedRandom;
+use chrono::Local;
 
-use super::super::app_state::{App, MAX_DRAW, MIN_DRAW};
+use super::super::app_state::{App, MAX_DRAW, MAX_TOKEN, MIN_DRAW};
+use super::super::bag;
 use super::super::history::DrawHistory;
+use super::super::scroll::{max_scroll, scrollbar_state};
 use super::super::types::{BallType, PopupType};
+use crate::session::Message;
 
 impl App {
     /// Reset dello stato dell'applicazione
@@ -22,94 +24,150 @@ impl App {
         self.random_mode = false;
         self.focused_section = FocusedSection::WhiteBalls;
         self.used_traits.clear();
+        self.challenge_description.clear();
         self.selected_node = Some(9); // set selection over archetype
         self.additional_red_balls = [0, 0, 0, 0];
         self.selected_list_item = Some((ListSection::Misfortunes, 0));
     }
 
-    /// Crea il pool di palline per l'estrazione
-    pub fn create_pool(&mut self) {
-        use BallType::*;
-        self.pool.clear();
-        if self.random_mode {
-            // Random mode: replace white balls with random mix of red and white
-            for _ in 0..self.white_balls {
-                if rand::random() {
-                    self.pool.push(White);
-                } else {
-                    self.pool.push(Red);
-                }
-            }
+    /// Mette in gioco o ritira una sventura, aggiornando i token rossi
+    pub fn toggle_misfortune(&mut self, idx: usize) {
+        // Ignora sfortune vuote
+        if self.list_data.misfortunes[idx].is_empty() {
+            return;
+        }
+
+        let value = self.list_data.misfortunes_red_balls[idx]
+            .trim()
+            .parse::<usize>()
+            .unwrap_or(0);
+
+        if self.additional_red_balls[idx] != 0 {
+            self.red_balls -= self.additional_red_balls[idx];
+            self.additional_red_balls[idx] = 0;
         } else {
-            // Normal mode
-            for _ in 0..self.white_balls {
-                self.pool.push(White);
-            }
+            // Il sacchetto non supera mai MAX_TOKEN token rossi
+            let value = value.min(MAX_TOKEN.saturating_sub(self.red_balls));
+            self.additional_red_balls[idx] = value;
+            self.red_balls += value;
+        }
+    }
+
+    /// Aggiunge o rimuove un tratto dalla prova, aggiornando i token bianchi
+    pub fn toggle_trait(&mut self, idx: usize) {
+        // Ignora nodi vuoti
+        if self.honeycomb_nodes[idx].text.is_empty() {
+            return;
         }
-        for _ in 0..self.red_balls {
-            self.pool.push(Red);
+
+        if let Some(pos) = self.used_traits.iter().position(|n| *n == idx) {
+            self.used_traits.swap_remove(pos);
+            self.white_balls -= 1;
+        } else if self.white_balls < MAX_TOKEN {
+            self.used_traits.push(idx);
+            self.white_balls += 1;
+        } else {
+            self.status_message = Some(format!("Al massimo {} token bianchi", MAX_TOKEN));
+            return;
         }
+        self.send_traits();
+    }
+
+    /// Crea il pool di palline per l'estrazione
+    pub fn create_pool(&mut self) {
+        self.pool = bag::fill_pool(
+            self.white_balls,
+            self.red_balls,
+            self.random_mode,
+            &mut rand::rng(),
+        );
     }
 
     /// Estrae un certo numero di palline dal pool
     pub fn draw_from_pool(&mut self, count: usize) -> Vec<BallType> {
-        let mut rng = rand::rng();
-        let mut drawn = Vec::new();
-
-        for _ in 0..count.min(self.pool.len()) {
-            if let Some(&ball) = self.pool.choose(&mut rng) {
-                drawn.push(ball);
-                if let Some(pos) = self.pool.iter().position(|&x| x == ball) {
-                    self.pool.remove(pos);
-                }
-            }
-        }
-
-        drawn
+        bag::draw(&mut self.pool, count, &mut rand::rng())
     }
 
-    /// Aggiunge un'estrazione alla cronologia
-    fn add_to_log(&mut self, risk: bool, risk_ball: Vec<BallType>) {
-        let local: DateTime<Local> = Local::now();
-        self.history.push(DrawHistory {
-            time: local.format("%A %e %B %Y, %T").to_string(),
+    /// Prova con i dati della pescata corrente, senza esiti
+    pub(crate) fn draw_entry(&mut self) -> DrawHistory {
+        let trait_names = self
+            .used_traits
+            .iter()
+            .map(|idx| self.honeycomb_nodes[*idx].text.clone())
+            .collect();
+        let misfortune_names = self
+            .additional_red_balls
+            .iter()
+            .enumerate()
+            .filter(|(_, tokens)| **tokens != 0)
+            .map(|(i, _)| self.list_data.misfortunes[i].clone())
+            .collect();
+        DrawHistory {
+            time: Local::now(),
+            player: self.session_name(),
+            description: std::mem::take(&mut self.challenge_description),
             white_balls: self.white_balls,
             traits: self.used_traits.clone(),
+            trait_names,
             red_balls: self.red_balls,
             misfortunes: self.additional_red_balls,
+            misfortune_names,
+            confused: self.random_mode,
+            adrenalined: self.forced_four_mode,
+            ..Default::default()
+        }
+    }
+
+    /// Aggiunge un'estrazione alla cronologia
+    fn add_to_log(&mut self, risk: bool, risk_ball: Vec<BallType>) {
+        let entry = DrawHistory {
             first_draw: self.current_first_draw.clone(),
             risked: risk,
             risk_draw: risk_ball,
-            confused: self.random_mode,
-            adrenalined: self.forced_four_mode,
+            ..self.draw_entry()
+        };
+        self.send(Message::Draw {
+            entry: entry.clone(),
         });
+        self.push_history(entry);
+        self.finish_draw();
+    }
 
+    /// Chiude la pescata: modificatori e tratti tornano disponibili
+    pub(crate) fn finish_draw(&mut self) {
         self.random_mode = false;
         self.forced_four_mode = false;
         self.used_traits.clear();
         self.additional_red_balls = [0, 0, 0, 0];
+        self.update_vertical_scroll_state();
+        self.popup = PopupType::None;
     }
 
     /// Esegue la prima estrazione
     pub fn perform_first_draw(&mut self) {
-        use PopupType::*;
+        if self.authoritative_session() {
+            // L'estrazione spetta all'host
+            self.request_draw();
+            return;
+        }
         self.create_pool();
         let drawn = self.draw_from_pool(self.draw_count);
         self.drawn_balls = drawn.clone();
         self.current_first_draw = drawn;
 
         if self.drawn_balls.len() < 5 {
-            self.popup = ConfirmRisk;
+            self.popup = PopupType::ConfirmRisk;
         } else {
             self.add_to_log(false, Vec::new());
-            self.update_vertical_scroll_state();
-            self.popup = None;
         }
     }
 
     /// Esegue l'estrazione di rischio
     pub fn perform_risk_draw(&mut self) {
-        use PopupType::*;
+        if self.draw_request.is_some() {
+            self.request_risk(true);
+            return;
+        }
         let remaining = 5 - self.drawn_balls.len();
         let mut risk_balls = Vec::new();
         if remaining > 0 {
@@ -118,44 +176,103 @@ impl App {
             self.drawn_balls.extend(additional);
         }
         self.add_to_log(true, risk_balls);
-        self.update_vertical_scroll_state();
-        self.popup = None;
     }
 
     /// Annulla l'estrazione di rischio
     pub fn cancel_draw(&mut self) {
-        use PopupType::*;
+        if self.draw_request.is_some() {
+            self.request_risk(false);
+            return;
+        }
         self.add_to_log(false, Vec::new());
-        self.update_vertical_scroll_state();
-        self.popup = None;
+    }
+
+    /// Apre l'inserimento dell'esito pescato dal sacchetto fisico
+    pub fn open_manual_result(&mut self) {
+        self.manual_result = [0; 4];
+        self.manual_field = 0;
+        self.popup = PopupType::ManualResult;
+    }
+
+    /// Modifica il campo selezionato dell'esito manuale
+    pub fn adjust_manual_result(&mut self, up: bool) {
+        let value = &mut self.manual_result[self.manual_field];
+        if up {
+            *value = (*value + 1).min(MAX_TOKEN);
+        } else {
+            *value = value.saturating_sub(1);
+        }
+    }
+
+    /// Controlla l'esito manuale rispetto al sacchetto della prova
+    pub fn check_manual_result(&self) -> Result<(), String> {
+        let [first_white, first_red, risk_white, risk_red] = self.manual_result;
+        bag::validate_manual(
+            self.white_balls,
+            self.red_balls,
+            self.random_mode,
+            self.draw_count,
+            [first_white, first_red],
+            [risk_white, risk_red],
+        )
+    }
+
+    /// Registra nel log l'esito pescato dal sacchetto fisico
+    pub fn record_manual_draw(&mut self) -> Result<(), String> {
+        if self.authoritative_session() {
+            return Err("Nella sessione autorevole le prove le estrae l'host".into());
+        }
+        self.check_manual_result()?;
+        let [first_white, first_red, risk_white, risk_red] = self.manual_result;
+        let balls = |white: usize, red: usize| {
+            let mut balls = vec![BallType::White; white];
+            balls.extend(vec![BallType::Red; red]);
+            balls
+        };
+        let first_draw = balls(first_white, first_red);
+        let risk_draw = balls(risk_white, risk_red);
+
+        self.drawn_balls = first_draw.clone();
+        self.drawn_balls.extend(risk_draw.iter().copied());
+        self.current_first_draw = first_draw.clone();
+        let entry = DrawHistory {
+            first_draw,
+            risked: !risk_draw.is_empty(),
+            risk_draw,
+            manual: true,
+            ..self.draw_entry()
+        };
+        self.send(Message::Draw {
+            entry: entry.clone(),
+        });
+        self.push_history(entry);
+        self.finish_draw();
+        Ok(())
     }
 
     /// Aggiorna lo stato della scrollbar verticale per la cronologia
-    fn update_vertical_scroll_state(&mut self) {
-        let content_height = self.history.len() * 13;
-        self.vertical_scroll_state = self.vertical_scroll_state.content_length(content_height);
+    pub(crate) fn update_vertical_scroll_state(&mut self) {
+        let entries = self.log_entries().len();
+        self.log_selected = self.log_selected.min(entries.saturating_sub(1));
+        let max = max_scroll(self.log_height, self.log_area);
+        self.vertical_scroll = self.vertical_scroll.min(max);
+        self.vertical_scroll_state =
+            scrollbar_state(max, self.log_area.height as usize, self.vertical_scroll);
     }
 
     /// Incrementa il valore delle palline/estrazioni
     pub fn increment_balls(&mut self) {
-        use super::super::app_state::MAX_TOKEN;
         use super::super::types::FocusedSection;
 
         match self.focused_section {
-            FocusedSection::WhiteBalls => {
-                if self.white_balls < MAX_TOKEN {
-                    self.white_balls += 1;
-                }
+            FocusedSection::WhiteBalls if self.white_balls < MAX_TOKEN => {
+                self.white_balls += 1;
             }
-            FocusedSection::RedBalls => {
-                if self.red_balls < MAX_TOKEN {
-                    self.red_balls += 1;
-                }
+            FocusedSection::RedBalls if self.red_balls < MAX_TOKEN => {
+                self.red_balls += 1;
             }
-            FocusedSection::DrawInput => {
-                if self.draw_count < MAX_DRAW && !self.forced_four_mode {
-                    self.draw_count += 1;
-                }
+            FocusedSection::DrawInput if self.draw_count < MAX_DRAW && !self.forced_four_mode => {
+                self.draw_count += 1;
             }
             _ => {}
         }
@@ -166,33 +283,27 @@ impl App {
         use super::super::types::FocusedSection;
 
         match self.focused_section {
-            FocusedSection::WhiteBalls => {
-                if self.white_balls > 0 {
-                    self.white_balls -= 1;
-                    if !self.used_traits.is_empty() {
-                        let _ = self.used_traits.pop();
-                    }
+            FocusedSection::WhiteBalls if self.white_balls > 0 => {
+                self.white_balls -= 1;
+                if !self.used_traits.is_empty() {
+                    let _ = self.used_traits.pop();
                 }
             }
-            FocusedSection::RedBalls => {
-                if self.red_balls > 0 {
-                    if self.red_balls > self.additional_red_balls.iter().sum() {
-                        self.red_balls -= 1;
-                    } else {
-                        for (i, d) in self.additional_red_balls.clone().iter().enumerate() {
-                            if *d > 0 {
-                                self.red_balls -= *d;
-                                self.additional_red_balls[i] = 0;
-                                break;
-                            }
+            FocusedSection::RedBalls if self.red_balls > 0 => {
+                if self.red_balls > self.additional_red_balls.iter().sum() {
+                    self.red_balls -= 1;
+                } else {
+                    for (i, d) in self.additional_red_balls.clone().iter().enumerate() {
+                        if *d > 0 {
+                            self.red_balls -= *d;
+                            self.additional_red_balls[i] = 0;
+                            break;
                         }
                     }
                 }
             }
-            FocusedSection::DrawInput => {
-                if self.draw_count > MIN_DRAW && !self.forced_four_mode {
-                    self.draw_count -= 1;
-                }
+            FocusedSection::DrawInput if self.draw_count > MIN_DRAW && !self.forced_four_mode => {
+                self.draw_count -= 1;
             }
             _ => {}
         }
@@ -345,6 +456,19 @@ mod draw_tests {
         assert_eq!(app.white_balls, MAX_TOKEN);
     }
 
+    #[test]
+    fn test_toggle_trait_stops_at_max() {
+        let mut app = App::new();
+        app.honeycomb_nodes[3].text = "Agile".into();
+        app.white_balls = MAX_TOKEN;
+
+        app.toggle_trait(3);
+
+        assert_eq!(app.white_balls, MAX_TOKEN);
+        assert!(app.used_traits.is_empty());
+        assert!(app.status_message.is_some());
+    }
+
     #[test]
     fn test_increment_balls_red() {
         let mut app = App::new();
@@ -1,34 +1,62 @@
 use std::fs;
 
+use super::super::advancement::AdvancementData;
 use super::super::app_state::App;
 use super::super::character::CharacterSection;
-use super::super::honeycomb::HoneycombData;
+use super::super::format::SheetFormat;
+use super::super::hit::HitId;
+use super::super::honeycomb::{HoneycombData, NodeField};
 use super::super::list::ListSection;
-
-const DATA_FILE: &str = "character_sheet.toml";
+use super::super::scroll::{max_scroll, scroll_by, scrollbar_state, text_height};
+use super::super::sheet::SheetField;
 
 impl App {
-    /// Salva i dati su file TOML
-    pub(crate) fn save_data(&self) {
-        let data = HoneycombData {
-            nodes: self
-                .honeycomb_nodes
-                .iter()
-                .map(|n| n.text.clone())
-                .collect(),
-        };
-
-        let mut string = String::new();
-        if let Ok(toml_string) = toml::to_string_pretty(&self.character_base_info) {
-            string.push_str(&toml_string);
-        }
-        if let Ok(toml_string) = toml::to_string_pretty(&data) {
-            string.push_str(&toml_string);
+    /// Salva la scheda nel formato del suo file, senza perdere le modifiche fatte al file da altri
+    pub(crate) fn save_data(&mut self) {
+        // Un file assente non ha modifiche da unire
+        let on_disk = fs::read_to_string(&self.sheet_file).ok();
+        if let Some(on_disk) = on_disk.filter(|on_disk| *on_disk != self.sheet_on_disk) {
+            match self.read_disk_sheet(&on_disk) {
+                Some(disk) => {
+                    if !self.merge_disk_sheet(on_disk, &disk) {
+                        // Si salva dopo aver risolto i conflitti
+                        return;
+                    }
+                }
+                // Un file vuoto o incompleto non deve svuotare la scheda
+                None => {
+                    self.status_message = Some(
+                        "File della scheda incompleto: riscritto dalla scheda in memoria".into(),
+                    )
+                }
+            }
         }
-        if let Ok(toml_string) = toml::to_string_pretty(&self.list_data) {
-            string.push_str(&toml_string);
+        self.write_sheet();
+    }
+
+    /// Scrive la scheda in memoria sul file
+    pub(crate) fn write_sheet(&mut self) {
+        let string = self.sheet_contents(self.sheet_format());
+        let _ = fs::write(&self.sheet_file, &string);
+        self.sheet_on_disk = string;
+
+        // Le modifiche alla scheda fanno parte della sessione in corso
+        if self.journal.active().is_some() {
+            self.journal.save_journal();
         }
-        let _ = fs::write(DATA_FILE, string);
+    }
+
+    /// Contenuto del file della scheda nel formato indicato
+    pub(crate) fn sheet_contents(&self, format: SheetFormat) -> String {
+        let advancement = AdvancementData {
+            advancement: self.advancement.clone(),
+        };
+        format.sheet_document(
+            &self.character_base_info,
+            &HoneycombData::from_nodes(&self.honeycomb_nodes),
+            &self.list_data,
+            &advancement,
+        )
     }
 
     /// Inizia la modifica delle informazioni del personaggio
@@ -46,11 +74,17 @@ impl App {
     /// Termina la modifica delle informazioni del personaggio
     pub fn finish_character_editing(&mut self) {
         if self.selected_character_info != CharacterSection::None {
-            if self.selected_character_info == CharacterSection::CharacterName {
-                self.character_base_info.name = self.character_edit_buffer.clone();
+            let after = self.character_edit_buffer.clone();
+            let (field, before) = if self.selected_character_info == CharacterSection::CharacterName
+            {
+                let before = std::mem::replace(&mut self.character_base_info.name, after.clone());
+                ("Nome", before)
             } else {
-                self.character_base_info.objective = self.character_edit_buffer.clone();
-            }
+                let before =
+                    std::mem::replace(&mut self.character_base_info.objective, after.clone());
+                ("Obiettivo", before)
+            };
+            self.record_sheet_change(field.to_string(), &before, &after);
             self.save_data();
             self.editing_character_info = false;
             self.character_edit_buffer.clear();
@@ -59,34 +93,65 @@ impl App {
 
     /// Inizia la modifica di un nodo della griglia esagonale
     pub fn start_node_editing(&mut self) {
+        self.start_node_field_editing(NodeField::Title);
+    }
+
+    /// Inizia la modifica di un campo specifico del nodo selezionato
+    pub fn start_node_field_editing(&mut self, field: NodeField) {
         if let Some(idx) = self.selected_node {
+            self.node_field = field;
             self.editing_node = true;
-            self.node_edit_buffer = self.honeycomb_nodes[idx].text.clone();
+            self.node_edit_buffer = self.honeycomb_nodes[idx].field(field);
         }
     }
 
     /// Termina la modifica di un nodo della griglia esagonale
     pub fn finish_node_editing(&mut self) {
         if let Some(idx) = self.selected_node {
-            self.honeycomb_nodes[idx].text = self.node_edit_buffer.clone().trim().to_string();
-            self.save_data();
+            let value = self.node_edit_buffer.clone();
+            if let Err(e) = self.set_node_field(idx, self.node_field, &value) {
+                self.status_message = Some(e);
+            }
         }
         self.editing_node = false;
         self.node_edit_buffer.clear();
     }
 
+    /// Cambia un campo di un nodo, registrando la modifica e salvando la scheda
+    pub fn set_node_field(
+        &mut self,
+        idx: usize,
+        field: NodeField,
+        value: &str,
+    ) -> Result<(), String> {
+        if field == NodeField::Title {
+            self.check_new_trait(idx, value)?;
+        }
+        let before = self.honeycomb_nodes[idx].field(field);
+        self.honeycomb_nodes[idx].set_field(field, value);
+        let after = self.honeycomb_nodes[idx].field(field);
+        self.record_sheet_change(SheetField::Node(idx, field).label(), &before, &after);
+        self.save_data();
+        Ok(())
+    }
+
+    /// Valore corrente di un elemento della lista
+    fn list_value(&self, section: ListSection, idx: usize) -> String {
+        use ListSection::*;
+        match section {
+            Misfortunes => self.list_data.misfortunes[idx].clone(),
+            MisfortunesDifficult => self.list_data.misfortunes_red_balls[idx].clone(),
+            LxResources => self.list_data.left_resources[idx].clone(),
+            Notes => self.list_data.notes.clone(),
+            Lessons => self.list_data.lessons[idx].clone(),
+        }
+    }
+
     /// Inizia la modifica di un elemento della lista
     pub fn start_list_editing(&mut self) {
-        use ListSection::*;
         if let Some((section, idx)) = self.selected_list_item {
             self.editing_list_item = true;
-            self.list_edit_buffer = match section {
-                Misfortunes => self.list_data.misfortunes[idx].clone(),
-                MisfortunesDifficult => self.list_data.misfortunes_red_balls[idx].clone(),
-                LxResources => self.list_data.left_resources[idx].clone(),
-                Notes => self.list_data.notes.clone(),
-                Lessons => self.list_data.lessons[idx].clone(),
-            };
+            self.list_edit_buffer = self.list_value(section, idx);
         }
     }
 
@@ -94,6 +159,7 @@ impl App {
     pub fn finish_list_editing(&mut self) {
         use ListSection::*;
         if let Some((section, idx)) = self.selected_list_item {
+            let before = self.list_value(section, idx);
             match section {
                 Misfortunes => {
                     self.list_data.misfortunes[idx] =
@@ -116,6 +182,13 @@ impl App {
                     self.update_list_vertical_scroll_state(idx)
                 }
             }
+            let after = self.list_value(section, idx);
+            self.record_sheet_change(SheetField::List(section, idx).label(), &before, &after);
+            // Si impara una lezione quando lo spazio si riempie o il testo cambia,
+            // non a ogni salvataggio
+            if section == Lessons && !after.is_empty() && before != after {
+                self.learn_lesson(idx);
+            }
             self.save_data();
         }
         self.editing_list_item = false;
@@ -124,18 +197,46 @@ impl App {
 
     /// Aggiorna lo stato della scrollbar verticale per le lezioni
     pub fn update_list_vertical_scroll_state(&mut self, idx: usize) {
-        let width = self.lections_area[idx % 3].width;
-        let content_height = self.list_data.lessons[idx % 3].len() / width as usize;
-        self.list_vertical_scroll_state[idx % 3] =
-            self.list_vertical_scroll_state[idx % 3].content_length(content_height);
+        let idx = idx % 3;
+        // Finché la lezione non è disegnata non se ne conosce l'altezza
+        let Some(area) = self.hits.area(HitId::Lesson(idx)) else {
+            return;
+        };
+        let max = max_scroll(text_height(&self.list_data.lessons[idx], area), area);
+        self.list_vertical_scroll[idx] = self.list_vertical_scroll[idx].min(max);
+        self.list_vertical_scroll_state[idx] =
+            scrollbar_state(max, area.height as usize, self.list_vertical_scroll[idx]);
     }
 
     /// Aggiorna lo stato della scrollbar verticale per le note
     pub fn update_notes_vertical_scroll_state(&mut self) {
-        let width = self.resources_area[1].width;
-        let content_height = self.list_data.notes.len() / width as usize;
-        self.notes_vertical_scroll_state = self
-            .notes_vertical_scroll_state
-            .content_length(content_height);
+        let Some(area) = self.hits.area(HitId::Notes) else {
+            return;
+        };
+        let max = max_scroll(text_height(&self.list_data.notes, area), area);
+        self.notes_vertical_scroll = self.notes_vertical_scroll.min(max);
+        self.notes_vertical_scroll_state =
+            scrollbar_state(max, area.height as usize, self.notes_vertical_scroll);
+    }
+
+    /// Scorre una lezione di `delta` righe
+    pub fn scroll_lesson(&mut self, idx: usize, delta: isize) {
+        let idx = idx % 3;
+        let Some(area) = self.hits.area(HitId::Lesson(idx)) else {
+            return;
+        };
+        let max = max_scroll(text_height(&self.list_data.lessons[idx], area), area);
+        self.list_vertical_scroll[idx] = scroll_by(self.list_vertical_scroll[idx], delta, max);
+        self.update_list_vertical_scroll_state(idx);
+    }
+
+    /// Scorre le note di `delta` righe
+    pub fn scroll_notes(&mut self, delta: isize) {
+        let Some(area) = self.hits.area(HitId::Notes) else {
+            return;
+        };
+        let max = max_scroll(text_height(&self.list_data.notes, area), area);
+        self.notes_vertical_scroll = scroll_by(self.notes_vertical_scroll, delta, max);
+        self.update_notes_vertical_scroll_state();
     }
 }
@@ -0,0 +1,143 @@
+use std::fs;
+
+use super::super::app_state::App;
+use super::super::import::SheetImport;
+use super::super::types::PopupType;
+
+impl App {
+    /// Legge il CSV e apre l'anteprima, senza toccare la scheda
+    pub fn open_sheet_import(&mut self, path: &str) -> Result<(), String> {
+        let contents =
+            fs::read_to_string(path).map_err(|e| format!("Impossibile leggere {}: {}", path, e))?;
+        self.sheet_import = Some(SheetImport::parse(path, &contents)?);
+        self.sheet_import_selected = 0;
+        self.popup = PopupType::SheetImport;
+        Ok(())
+    }
+
+    /// Passa alla riga precedente o successiva dell'anteprima
+    pub fn select_import_row(&mut self, next: bool) {
+        let Some(import) = &self.sheet_import else {
+            return;
+        };
+        if next {
+            if self.sheet_import_selected + 1 < import.rows.len() {
+                self.sheet_import_selected += 1;
+            }
+        } else {
+            self.sheet_import_selected = self.sheet_import_selected.saturating_sub(1);
+        }
+    }
+
+    /// Cambia il campo della scheda in cui finisce la riga selezionata
+    pub fn cycle_import_target(&mut self, forward: bool) {
+        if let Some(import) = &mut self.sheet_import {
+            import.cycle_target(self.sheet_import_selected, forward);
+        }
+    }
+
+    /// Esclude la riga selezionata dall'importazione
+    pub fn ignore_import_row(&mut self) {
+        if let Some(row) = self
+            .sheet_import
+            .as_mut()
+            .and_then(|import| import.rows.get_mut(self.sheet_import_selected))
+        {
+            row.target = None;
+        }
+    }
+
+    /// Sostituisce la scheda con quella importata, avanzamento compreso, e la salva
+    pub fn confirm_sheet_import(&mut self) {
+        let Some(import) = self.sheet_import.take() else {
+            return;
+        };
+        self.apply_sheet(import.sheet());
+        // I tratti e le sventure della prova non corrispondono più
+        self.reset();
+        self.status_message = Some(format!(
+            "Scheda importata da {}: {} righe",
+            import.path,
+            import.mapped()
+        ));
+        // Le modifiche fatte al file da altri passano dal confronto
+        self.save_data();
+    }
+
+    pub fn cancel_sheet_import(&mut self) {
+        self.sheet_import = None;
+        self.popup = PopupType::None;
+    }
+}
+
+#[cfg(test)]
+mod import_impl_tests {
+    use chrono::Local;
+    use std::fs;
+
+    use crate::app::campaign::character_file;
+    use crate::app::sheet::SheetField;
+    use crate::app::{App, Campaign, PopupType};
+    use crate::test_support::{ActiveScope, TempDir};
+
+    #[test]
+    fn test_preview_and_mapping() {
+        let dir = TempDir::new("import");
+        let path = dir.join("pg.csv");
+        fs::write(&path, "Nome,Lia\nSoprannome,La Rossa\n").unwrap();
+
+        let mut app = App::new();
+        assert!(app.open_sheet_import("non-esiste.csv").is_err());
+        app.open_sheet_import(path.to_str().unwrap()).unwrap();
+        assert_eq!(app.popup, PopupType::SheetImport);
+        // L'anteprima non modifica la scheda
+        assert_ne!(app.character_base_info.name, "Lia");
+
+        app.select_import_row(true);
+        app.select_import_row(true);
+        assert_eq!(app.sheet_import_selected, 1);
+        app.cycle_import_target(true);
+        let import = app.sheet_import.as_ref().unwrap();
+        assert_eq!(import.rows[1].target, Some(SheetField::Name));
+        assert_eq!(import.sheet().info.name, "La Rossa");
+
+        app.ignore_import_row();
+        let import = app.sheet_import.as_ref().unwrap();
+        assert_eq!(import.sheet().info.name, "Lia");
+
+        app.cancel_sheet_import();
+        assert!(app.sheet_import.is_none());
+        assert_eq!(app.popup, PopupType::None);
+    }
+
+    #[test]
+    fn test_confirm_replaces_sheet_and_keeps_external_changes() {
+        let root = TempDir::new("import-confirm");
+        let campaign = Campaign::create(root.path(), "Prova").unwrap();
+        let _scope = ActiveScope::new(&campaign);
+
+        let mut app = App::new();
+        app.character_base_info.name = "Vecchio".into();
+        app.advancement
+            .learn_lesson(Local::now(), 0, "Vecchia lezione", None);
+        app.write_sheet();
+
+        // Un altro programma cambia l'obiettivo dopo l'ultimo salvataggio
+        let mut external = app.clone();
+        external.character_base_info.objective = "Scritto da fuori".into();
+        let contents = external.sheet_contents(app.sheet_format());
+        fs::write(character_file(), contents).unwrap();
+
+        let csv = root.join("pg.csv");
+        fs::write(&csv, "Nome,Lia\n").unwrap();
+        app.open_sheet_import(csv.to_str().unwrap()).unwrap();
+        app.confirm_sheet_import();
+
+        assert_eq!(app.popup, PopupType::None);
+        assert_eq!(app.character_base_info.name, "Lia");
+        assert_eq!(app.character_base_info.objective, "Scritto da fuori");
+        assert!(app.advancement.lessons.is_empty());
+        let saved = fs::read_to_string(character_file()).unwrap();
+        assert_eq!(saved, app.sheet_on_disk);
+    }
+}
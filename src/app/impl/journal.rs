@@ -0,0 +1,108 @@
+use chrono::Local;
+
+use super::super::app_state::App;
+use super::super::history::DrawHistory;
+use super::super::journal::format_duration;
+use super::super::types::PopupType;
+
+impl App {
+    /// Aggiunge una prova allo storico e alla sessione in corso
+    pub(crate) fn push_history(&mut self, entry: DrawHistory) {
+        if self.journal.add_draw(&entry) {
+            self.journal.save_journal();
+        }
+        self.history.push(entry);
+    }
+
+    /// Aggiunge un partecipante alla sessione in corso
+    pub(crate) fn add_session_participant(&mut self, name: &str) {
+        if let Some(session) = self.journal.active_mut() {
+            session.add_participant(name);
+        }
+    }
+
+    /// Apre il diario delle sessioni sulla più recente
+    pub fn open_sessions(&mut self) {
+        self.sessions_selected = self.journal.sessions.len().saturating_sub(1);
+        self.sessions_scroll = 0;
+        self.session_title = None;
+        self.popup = PopupType::Sessions;
+    }
+
+    /// Seleziona la sessione precedente o successiva nel diario
+    pub fn select_session(&mut self, next: bool) {
+        if next {
+            if self.sessions_selected + 1 < self.journal.sessions.len() {
+                self.sessions_selected += 1;
+            }
+        } else {
+            self.sessions_selected = self.sessions_selected.saturating_sub(1);
+        }
+        self.sessions_scroll = 0;
+    }
+
+    /// Avvia una sessione con il titolo digitato, partecipanti compresi
+    pub fn start_game_session(&mut self) {
+        let title = self.session_title.take().unwrap_or_default();
+        let mut participants = vec![self.session_name()];
+        participants.extend(self.narrator.players.iter().map(|p| p.name.clone()));
+
+        match self.journal.start(&title, Local::now(), &participants) {
+            Ok(()) => {
+                self.journal.save_journal();
+                self.sessions_selected = self.journal.sessions.len() - 1;
+                self.sessions_scroll = 0;
+                self.status_message = Some("Sessione iniziata".to_string());
+            }
+            Err(e) => self.status_message = Some(e),
+        }
+    }
+
+    /// Chiude la sessione in corso e ne mostra il riepilogo
+    pub fn end_game_session(&mut self) {
+        let Some(session) = self.journal.end(Local::now()) else {
+            self.status_message = Some("Nessuna sessione in corso".to_string());
+            return;
+        };
+        let message = format!(
+            "{} conclusa dopo {}",
+            session.title,
+            format_duration(session.duration(Local::now()))
+        );
+        self.journal.save_journal();
+        self.status_message = Some(message);
+        self.open_sessions();
+    }
+}
+
+#[cfg(test)]
+mod journal_impl_tests {
+    use chrono::Local;
+
+    use crate::app::App;
+
+    #[test]
+    fn test_draws_and_changes_join_active_session() {
+        let mut app = App::new();
+        app.journal = Default::default();
+        app.journal.start("Il porto", Local::now(), &[]).unwrap();
+        app.character_base_info.name = "Aria".into();
+        app.list_data.lessons[0] = "Mai fidarsi".into();
+
+        app.record_sheet_change("Nome".into(), "", "Aria");
+        app.learn_lesson(0);
+
+        let session = app.journal.active().unwrap();
+        assert_eq!(session.changes.len(), 1);
+        assert_eq!(session.lessons[0].text, "Mai fidarsi");
+        assert!(app.history.is_empty());
+    }
+
+    #[test]
+    fn test_no_session_no_grouping() {
+        let mut app = App::new();
+        app.journal = Default::default();
+        app.record_sheet_change("Nome".into(), "", "Aria");
+        assert!(app.journal.sessions.is_empty());
+    }
+}
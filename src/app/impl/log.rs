@@ -0,0 +1,265 @@
+use ratatui::layout::Rect;
+
+use super::super::app_state::App;
+use super::super::history::DrawHistory;
+use super::super::log_filter::{LogFilterField, cycle_flag};
+use super::super::scroll::{max_scroll, scroll_by};
+use super::super::types::PopupType;
+
+impl App {
+    /// Prove mostrate nel log, dalla più recente, con il loro numero progressivo.
+    ///
+    /// Con un filtro di sessione le prove vengono dal diario, altrimenti dallo storico.
+    pub fn log_entries(&self) -> Vec<(usize, &DrawHistory)> {
+        let source = match self.log_filter.session {
+            Some(i) => self
+                .journal
+                .sessions
+                .get(i)
+                .map(|s| self.journal.session_draws(s))
+                .unwrap_or_default(),
+            None => self.history.iter().collect(),
+        };
+        source
+            .into_iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, entry)| self.log_filter.matches(entry))
+            .map(|(i, entry)| (i + 1, entry))
+            .collect()
+    }
+
+    /// Registra l'area del log e la riga iniziale di ogni prova, a capo compresi
+    pub(crate) fn set_log_layout(&mut self, area: Rect, offsets: Vec<usize>, height: usize) {
+        self.log_area = area;
+        self.log_offsets = offsets;
+        self.log_height = height;
+        self.update_vertical_scroll_state();
+    }
+
+    /// Passa alla prova precedente o successiva del log
+    pub fn select_log_entry(&mut self, next: bool) {
+        if next {
+            self.log_selected = self.log_selected.saturating_add(1);
+        } else {
+            self.log_selected = self.log_selected.saturating_sub(1);
+        }
+        self.update_vertical_scroll_state();
+        self.vertical_scroll = self
+            .log_offsets
+            .get(self.log_selected)
+            .copied()
+            .unwrap_or(0);
+        self.update_vertical_scroll_state();
+    }
+
+    /// Scorre il log di `delta` righe; la prova selezionata segue la prima visibile
+    pub fn scroll_log(&mut self, delta: isize) {
+        let max = max_scroll(self.log_height, self.log_area);
+        self.vertical_scroll = scroll_by(self.vertical_scroll, delta, max);
+        self.log_selected = self
+            .log_offsets
+            .iter()
+            .rposition(|offset| *offset <= self.vertical_scroll)
+            .unwrap_or(0);
+        self.update_vertical_scroll_state();
+    }
+
+    /// Scorre il log di una pagina
+    pub fn scroll_log_page(&mut self, down: bool) {
+        let page = self.log_area.height.saturating_sub(3).max(1) as isize;
+        self.scroll_log(if down { page } else { -page });
+    }
+
+    /// Porta il log all'inizio o alla fine
+    pub fn scroll_log_to_end(&mut self, end: bool) {
+        if end {
+            self.vertical_scroll = max_scroll(self.log_height, self.log_area);
+            self.log_selected = self.log_offsets.len().saturating_sub(1);
+        } else {
+            self.vertical_scroll = 0;
+            self.log_selected = 0;
+        }
+        self.update_vertical_scroll_state();
+    }
+
+    /// Apre i filtri del log sul campo indicato
+    pub fn open_log_filter(&mut self, field: LogFilterField) {
+        self.log_filter_field = field;
+        self.popup = PopupType::LogFilter;
+    }
+
+    /// Seleziona il campo precedente o successivo nei filtri del log
+    pub fn select_log_filter_field(&mut self, next: bool) {
+        let fields = LogFilterField::ALL;
+        let current = fields
+            .iter()
+            .position(|f| *f == self.log_filter_field)
+            .unwrap_or(0);
+        let idx = if next {
+            (current + 1).min(fields.len() - 1)
+        } else {
+            current.saturating_sub(1)
+        };
+        self.log_filter_field = fields[idx];
+    }
+
+    /// Cambia il valore dei campi a scelta: sessione e filtri sì/no
+    pub fn adjust_log_filter(&mut self, next: bool) {
+        let field = self.log_filter_field;
+        if field == LogFilterField::Session {
+            let sessions = self.journal.sessions.len();
+            self.log_filter.session = match (self.log_filter.session, next) {
+                _ if sessions == 0 => None,
+                (None, true) => Some(0),
+                (None, false) => Some(sessions - 1),
+                (Some(i), true) if i + 1 < sessions => Some(i + 1),
+                (Some(i), false) if i > 0 => Some(i - 1),
+                _ => None,
+            };
+        } else if let Some(flag) = self.log_filter.flag_mut(field) {
+            *flag = cycle_flag(*flag);
+        }
+        self.filter_changed();
+    }
+
+    /// Modifica il testo del campo selezionato; `None` cancella l'ultimo carattere
+    pub fn edit_log_filter(&mut self, c: Option<char>) {
+        let Some(text) = self.log_filter.text_mut(self.log_filter_field) else {
+            return;
+        };
+        match c {
+            Some(c) => text.push(c),
+            None => {
+                text.pop();
+            }
+        }
+        self.filter_changed();
+    }
+
+    /// Rimuove tutti i filtri del log
+    pub fn clear_log_filter(&mut self) {
+        self.log_filter = Default::default();
+        self.filter_changed();
+    }
+
+    /// Torna alla prova più recente tra quelle filtrate
+    fn filter_changed(&mut self) {
+        self.log_selected = 0;
+        self.vertical_scroll = 0;
+        self.update_vertical_scroll_state();
+    }
+}
+
+#[cfg(test)]
+mod log_impl_tests {
+    use chrono::Local;
+    use ratatui::layout::Rect;
+
+    use crate::app::{App, DrawHistory, LogFilterField};
+
+    fn app_with_draws() -> App {
+        let mut app = App::new();
+        app.journal = Default::default();
+        for (description, risked) in [("Scalare", true), ("Nuotare", false), ("Correre", true)] {
+            app.history.push(DrawHistory {
+                time: Local::now(),
+                description: description.into(),
+                risked,
+                ..Default::default()
+            });
+        }
+        app
+    }
+
+    #[test]
+    fn test_entries_newest_first() {
+        let app = app_with_draws();
+        let numbers: Vec<usize> = app.log_entries().iter().map(|(n, _)| *n).collect();
+        assert_eq!(numbers, vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn test_filter_and_search() {
+        let mut app = app_with_draws();
+        app.open_log_filter(LogFilterField::Risked);
+        app.adjust_log_filter(true);
+        assert_eq!(app.log_entries().len(), 2);
+
+        app.log_filter_field = LogFilterField::Query;
+        for c in "scal".chars() {
+            app.edit_log_filter(Some(c));
+        }
+        let entries = app.log_entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].1.description, "Scalare");
+
+        app.clear_log_filter();
+        assert_eq!(app.log_entries().len(), 3);
+    }
+
+    #[test]
+    fn test_session_filter_uses_journal() {
+        let mut app = app_with_draws();
+        app.journal.start("Il porto", Local::now(), &[]).unwrap();
+        app.journal.add_draw(&DrawHistory {
+            description: "Contrattare".into(),
+            ..Default::default()
+        });
+
+        app.log_filter_field = LogFilterField::Session;
+        app.adjust_log_filter(true);
+        assert_eq!(app.log_filter.session, Some(0));
+        assert_eq!(app.log_entries()[0].1.description, "Contrattare");
+
+        app.adjust_log_filter(true);
+        assert_eq!(app.log_filter.session, None);
+    }
+
+    #[test]
+    fn test_jump_between_entries() {
+        let mut app = app_with_draws();
+        // Tre prove di 10 righe dopo la riga vuota iniziale, in 12 righe visibili
+        app.set_log_layout(Rect::new(0, 0, 40, 14), vec![1, 11, 21], 31);
+        app.select_log_entry(true);
+        assert_eq!(app.vertical_scroll, 11);
+        app.select_log_entry(true);
+        // L'ultima prova non può arrivare in cima: lo scorrimento si ferma alla fine
+        assert_eq!(app.vertical_scroll, 19);
+        app.select_log_entry(true);
+        app.select_log_entry(true);
+        app.select_log_entry(true);
+        assert_eq!(app.log_selected, 2);
+        app.select_log_entry(false);
+        assert_eq!(app.log_selected, 1);
+        assert_eq!(app.vertical_scroll, 11);
+    }
+
+    #[test]
+    fn test_scroll_by_lines_and_pages() {
+        let mut app = app_with_draws();
+        app.set_log_layout(Rect::new(0, 0, 40, 14), vec![1, 11, 21], 31);
+
+        app.scroll_log(3);
+        assert_eq!((app.vertical_scroll, app.log_selected), (3, 0));
+        app.scroll_log_page(true);
+        assert_eq!((app.vertical_scroll, app.log_selected), (14, 1));
+        app.scroll_log_page(true);
+        assert_eq!(app.vertical_scroll, 19);
+
+        app.scroll_log_to_end(false);
+        assert_eq!((app.vertical_scroll, app.log_selected), (0, 0));
+        app.scroll_log_to_end(true);
+        assert_eq!((app.vertical_scroll, app.log_selected), (19, 2));
+    }
+
+    #[test]
+    fn test_layout_change_keeps_scroll_in_range() {
+        let mut app = app_with_draws();
+        app.set_log_layout(Rect::new(0, 0, 40, 14), vec![1, 11, 21], 31);
+        app.scroll_log_to_end(true);
+        // Terminale più largo: meno righe a capo
+        app.set_log_layout(Rect::new(0, 0, 80, 14), vec![1, 8, 15], 22);
+        assert_eq!(app.vertical_scroll, 10);
+    }
+}
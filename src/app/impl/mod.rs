@@ -1,8 +1,23 @@
 // Sottomoduli di implementazione
+mod action;
+mod advancement;
+mod campaign;
+mod context_menu;
 mod draw;
 mod edit;
+mod import;
+mod journal;
+mod log;
 mod mouse;
+mod narrator;
 mod navigation;
+mod palette;
+mod play;
+mod quick_entry;
+mod search;
+mod session;
+mod sheet;
+mod wizard;
 
 // Tutti i metodi sono implementati direttamente su App
 // nei rispettivi moduli, quindi non serve re-export
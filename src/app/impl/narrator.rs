@@ -0,0 +1,328 @@
+use std::fs;
+
+use super::super::app_state::{App, MAX_DRAW, MAX_TOKEN, MIN_DRAW};
+use super::super::narrator::{
+    AppMode, CastSection, Narrator, NarratorField, NarratorFocus, Npc, PlayerStatus, Threat,
+};
+use super::super::types::{PopupType, TabType};
+
+impl App {
+    /// Avvia l'applicazione come narratore
+    pub fn start_narrator(&mut self) {
+        self.mode = AppMode::Narrator;
+        self.narrator = Narrator::load_narrator();
+        self.current_tab = TabType::NarratorChallengeTab;
+    }
+
+    /// Passa al tab successivo del ruolo corrente
+    pub fn next_tab(&mut self) {
+        self.current_tab = self.mode.next_tab(self.current_tab);
+    }
+
+    /// Incrementa o decrementa il valore focalizzato nel tab della sfida
+    pub fn adjust_challenge(&mut self, up: bool) {
+        match self.narrator_focus {
+            NarratorFocus::Difficulty => {
+                if up && self.narrator.difficulty < MAX_TOKEN {
+                    self.narrator.difficulty += 1;
+                } else if !up {
+                    self.narrator.difficulty = self.narrator.difficulty.saturating_sub(1);
+                }
+                self.narrator.save_narrator();
+                self.send_difficulty();
+            }
+            NarratorFocus::Npc if !self.narrator.npcs.is_empty() => {
+                let len = self.narrator.npcs.len();
+                self.selected_npc = if up {
+                    (self.selected_npc + len - 1) % len
+                } else {
+                    (self.selected_npc + 1) % len
+                };
+            }
+            NarratorFocus::DrawCount => {
+                if up && self.draw_count < MAX_DRAW {
+                    self.draw_count += 1;
+                } else if !up && self.draw_count > MIN_DRAW {
+                    self.draw_count -= 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Prepara la prova del PNG selezionato contro la difficoltà della scena,
+    /// riusando la stessa estrazione dei giocatori
+    pub fn prepare_npc_draw(&mut self) -> Result<(), String> {
+        let npc = self
+            .narrator
+            .npcs
+            .get(self.selected_npc)
+            .ok_or("Nessun PNG da far agire")?;
+        // Come per i giocatori, nessun colore supera il massimo di token
+        let white = npc.traits.min(MAX_TOKEN);
+        let red = self.narrator.challenge_difficulty();
+        if white + red == 0 {
+            return Err("Il sacchetto è vuoto".to_string());
+        }
+
+        self.white_balls = white;
+        self.red_balls = red;
+        self.used_traits.clear();
+        self.additional_red_balls = [0, 0, 0, 0];
+        self.drawn_balls.clear();
+        self.challenge_description = format!("PNG: {}", npc.name);
+        self.popup = PopupType::ConfirmDraw;
+        Ok(())
+    }
+
+    /// Numero di elementi nell'elenco selezionato del tab PNG e minacce
+    pub fn cast_len(&self) -> usize {
+        match self.cast_section {
+            CastSection::Npcs => self.narrator.npcs.len(),
+            CastSection::Threats => self.narrator.threats.len(),
+        }
+    }
+
+    /// Aggiunge un PNG o una minaccia e ne modifica subito il nome
+    pub fn add_cast_entry(&mut self) {
+        let field = match self.cast_section {
+            CastSection::Npcs => {
+                self.narrator.npcs.push(Npc::default());
+                NarratorField::NpcName(self.narrator.npcs.len() - 1)
+            }
+            CastSection::Threats => {
+                self.narrator.threats.push(Threat::default());
+                NarratorField::ThreatName(self.narrator.threats.len() - 1)
+            }
+        };
+        self.selected_cast = self.cast_len() - 1;
+        self.start_narrator_editing(field);
+    }
+
+    /// Rimuove il PNG o la minaccia selezionata
+    pub fn remove_cast_entry(&mut self) {
+        let idx = self.selected_cast;
+        match self.cast_section {
+            CastSection::Npcs if idx < self.narrator.npcs.len() => {
+                self.narrator.npcs.remove(idx);
+            }
+            CastSection::Threats if idx < self.narrator.threats.len() => {
+                self.narrator.threats.remove(idx);
+                self.send_difficulty();
+            }
+            _ => return,
+        }
+        self.selected_cast = self.selected_cast.min(self.cast_len().saturating_sub(1));
+        self.selected_npc = self
+            .selected_npc
+            .min(self.narrator.npcs.len().saturating_sub(1));
+        self.narrator.save_narrator();
+    }
+
+    /// Modifica i token del PNG o della minaccia selezionata
+    pub fn adjust_cast_tokens(&mut self, up: bool) {
+        let idx = self.selected_cast;
+        let tokens = match self.cast_section {
+            CastSection::Npcs => self.narrator.npcs.get_mut(idx).map(|n| &mut n.traits),
+            CastSection::Threats => self.narrator.threats.get_mut(idx).map(|t| &mut t.tokens),
+        };
+        if let Some(tokens) = tokens {
+            if up && *tokens < MAX_TOKEN {
+                *tokens += 1;
+            } else if !up {
+                *tokens = tokens.saturating_sub(1);
+            }
+            self.narrator.save_narrator();
+            self.send_difficulty();
+        }
+    }
+
+    /// Attiva o disattiva la minaccia selezionata
+    pub fn toggle_threat(&mut self) {
+        if self.cast_section == CastSection::Threats
+            && let Some(threat) = self.narrator.threats.get_mut(self.selected_cast)
+        {
+            threat.active = !threat.active;
+            self.narrator.save_narrator();
+            self.send_difficulty();
+        }
+    }
+
+    /// Aggiunge un giocatore leggendone la scheda
+    pub fn import_player(&mut self, path: &str) -> Result<(), String> {
+        let contents =
+            fs::read_to_string(path).map_err(|_| format!("Impossibile leggere {}", path))?;
+        let player = PlayerStatus::from_sheet(path, &contents)
+            .ok_or_else(|| format!("{} non è una scheda valida", path))?;
+        self.narrator.players.push(player);
+        self.selected_player = self.narrator.players.len() - 1;
+        self.narrator.save_narrator();
+        Ok(())
+    }
+
+    /// Rilegge le schede di tutti i giocatori
+    pub fn reload_players(&mut self) -> Result<(), String> {
+        let errors: Vec<String> = self
+            .narrator
+            .players
+            .iter_mut()
+            .filter(|p| !p.sheet.is_empty())
+            .filter_map(|p| p.reload().err())
+            .collect();
+        self.narrator.save_narrator();
+        match errors.first() {
+            Some(error) => Err(error.clone()),
+            None => Ok(()),
+        }
+    }
+
+    /// Segna o rimuove una condizione (Confusione o Adrenalina) sul giocatore selezionato
+    pub fn toggle_player_condition(&mut self, adrenaline: bool) {
+        if let Some(player) = self.narrator.players.get_mut(self.selected_player) {
+            if adrenaline {
+                player.adrenalined = !player.adrenalined;
+            } else {
+                player.confused = !player.confused;
+            }
+            self.narrator.save_narrator();
+        }
+    }
+
+    /// Rimuove il giocatore selezionato
+    pub fn remove_player(&mut self) {
+        if self.selected_player < self.narrator.players.len() {
+            self.narrator.players.remove(self.selected_player);
+            self.selected_player = self
+                .selected_player
+                .min(self.narrator.players.len().saturating_sub(1));
+            self.narrator.save_narrator();
+        }
+    }
+
+    /// Inizia la modifica di un campo del narratore
+    pub fn start_narrator_editing(&mut self, field: NarratorField) {
+        use NarratorField::*;
+        let value = match field {
+            NpcName(i) => self.narrator.npcs.get(i).map(|n| n.name.clone()),
+            NpcNotes(i) => self.narrator.npcs.get(i).map(|n| n.notes.clone()),
+            ThreatName(i) => self.narrator.threats.get(i).map(|t| t.name.clone()),
+            ThreatNotes(i) => self.narrator.threats.get(i).map(|t| t.notes.clone()),
+            SceneNotes => Some(self.narrator.scene_notes.clone()),
+            PlayerSheet => Some(String::new()),
+        };
+        if let Some(value) = value {
+            self.narrator_edit_buffer = value;
+            self.narrator_field = Some(field);
+        }
+    }
+
+    /// Termina la modifica di un campo del narratore
+    pub fn finish_narrator_editing(&mut self) {
+        use NarratorField::*;
+        let Some(field) = self.narrator_field.take() else {
+            return;
+        };
+        let value = std::mem::take(&mut self.narrator_edit_buffer);
+        match field {
+            NpcName(i) => self.narrator.npcs[i].name = value,
+            NpcNotes(i) => self.narrator.npcs[i].notes = value,
+            ThreatName(i) => self.narrator.threats[i].name = value,
+            ThreatNotes(i) => self.narrator.threats[i].notes = value,
+            SceneNotes => self.narrator.scene_notes = value,
+            PlayerSheet => {
+                let path = value.trim();
+                if !path.is_empty()
+                    && let Err(e) = self.import_player(path)
+                {
+                    self.status_message = Some(e);
+                }
+                return;
+            }
+        }
+        self.narrator.save_narrator();
+    }
+}
+
+#[cfg(test)]
+mod narrator_impl_tests {
+    use crate::app::narrator::{Npc, Threat};
+    use crate::app::{App, AppMode, MAX_TOKEN, NarratorFocus, PopupType, TabType};
+
+    fn narrator_app() -> App {
+        let mut app = App::new();
+        app.mode = AppMode::Narrator;
+        app.current_tab = TabType::NarratorChallengeTab;
+        app
+    }
+
+    #[test]
+    fn test_next_tab_follows_mode() {
+        let mut app = narrator_app();
+        app.next_tab();
+        assert_eq!(app.current_tab, TabType::NarratorCastTab);
+    }
+
+    #[test]
+    fn test_npc_draw_uses_scene_difficulty() {
+        let mut app = narrator_app();
+        app.narrator.difficulty = 2;
+        app.narrator.npcs.push(Npc {
+            name: "Capitano".into(),
+            traits: 3,
+            ..Default::default()
+        });
+        app.narrator.threats.push(Threat {
+            name: "Tempesta".into(),
+            tokens: 1,
+            active: true,
+            ..Default::default()
+        });
+
+        app.prepare_npc_draw().unwrap();
+        assert_eq!(app.popup, PopupType::ConfirmDraw);
+        assert_eq!((app.white_balls, app.red_balls), (3, 3));
+
+        app.perform_first_draw();
+        app.cancel_draw();
+        assert_eq!(app.history[0].description, "PNG: Capitano");
+        assert_eq!(app.history[0].red_balls, 3);
+    }
+
+    #[test]
+    fn test_npc_draw_stays_within_max_tokens() {
+        let mut app = narrator_app();
+        app.narrator.difficulty = MAX_TOKEN;
+        app.narrator.npcs.push(Npc {
+            name: "Drago".into(),
+            traits: MAX_TOKEN * 2,
+            ..Default::default()
+        });
+        app.narrator.threats.push(Threat {
+            name: "Tempesta".into(),
+            tokens: MAX_TOKEN,
+            active: true,
+            ..Default::default()
+        });
+
+        app.prepare_npc_draw().unwrap();
+        assert_eq!((app.white_balls, app.red_balls), (MAX_TOKEN, MAX_TOKEN));
+    }
+
+    #[test]
+    fn test_npc_draw_requires_npc() {
+        let mut app = narrator_app();
+        assert!(app.prepare_npc_draw().is_err());
+        assert_eq!(app.popup, PopupType::None);
+    }
+
+    #[test]
+    fn test_cycle_npc_selection() {
+        let mut app = narrator_app();
+        app.narrator.npcs = vec![Default::default(), Default::default()];
+        app.narrator_focus = NarratorFocus::Npc;
+        app.adjust_challenge(false);
+        assert_eq!(app.selected_npc, 1);
+        app.adjust_challenge(false);
+        assert_eq!(app.selected_npc, 0);
+    }
+}
@@ -79,18 +79,8 @@ impl App {
                         self.selected_list_item = Some((section, 9 - idx));
                     }
                 }
-                Notes => {
-                    self.notes_vertical_scroll = self.notes_vertical_scroll.saturating_sub(1);
-                    self.notes_vertical_scroll_state = self
-                        .notes_vertical_scroll_state
-                        .position(self.notes_vertical_scroll);
-                }
-                Lessons => {
-                    self.list_vertical_scroll[idx] =
-                        self.list_vertical_scroll[idx].saturating_sub(1);
-                    self.list_vertical_scroll_state[idx] = self.list_vertical_scroll_state[idx]
-                        .position(self.list_vertical_scroll[idx]);
-                }
+                Notes => self.scroll_notes(-1),
+                Lessons => self.scroll_lesson(idx, -1),
             }
         }
     }
@@ -106,26 +96,8 @@ impl App {
                 LxResources => {
                     self.selected_list_item = Some((section, (idx + 1) % 10));
                 }
-                Notes => {
-                    if self.notes_vertical_scroll
-                        < self.list_data.notes.len() / self.resources_area[1].width as usize
-                    {
-                        self.notes_vertical_scroll = self.notes_vertical_scroll.saturating_add(1);
-                        self.notes_vertical_scroll_state = self
-                            .notes_vertical_scroll_state
-                            .position(self.notes_vertical_scroll);
-                    }
-                }
-                Lessons => {
-                    if self.list_vertical_scroll[idx]
-                        < self.list_data.lessons[idx].len() / self.lections_area[idx].width as usize
-                    {
-                        self.list_vertical_scroll[idx] =
-                            self.list_vertical_scroll[idx].saturating_add(1);
-                        self.list_vertical_scroll_state[idx] = self.list_vertical_scroll_state[idx]
-                            .position(self.list_vertical_scroll[idx]);
-                    }
-                }
+                Notes => self.scroll_notes(1),
+                Lessons => self.scroll_lesson(idx, 1),
             }
         }
     }
@@ -0,0 +1,208 @@
+use super::super::action::{Action, Prompt};
+use super::super::app_state::App;
+use super::super::format::SheetFormat;
+use super::super::palette::{PaletteCommand, PaletteEntry, filter_entries};
+use super::super::types::PopupType;
+
+impl App {
+    /// Apre la palette dei comandi
+    pub fn open_palette(&mut self) {
+        self.palette_query.clear();
+        self.palette_selected = 0;
+        self.palette_prompt = None;
+        self.popup = PopupType::CommandPalette;
+    }
+
+    /// Tutte le azioni disponibili, con i tratti, le sventure e i personaggi attuali
+    pub fn palette_entries(&self) -> Vec<PaletteEntry> {
+        let mut entries: Vec<PaletteEntry> = self
+            .mode
+            .tabs()
+            .iter()
+            .map(|tab| {
+                PaletteEntry::run(format!("Vai a: {}", tab.title()), Action::SwitchTab(*tab))
+            })
+            .collect();
+
+        for (i, node) in self.honeycomb_nodes.iter().enumerate() {
+            if node.text.is_empty() {
+                continue;
+            }
+            let verb = if self.used_traits.contains(&i) {
+                "Togli tratto"
+            } else {
+                "Usa tratto"
+            };
+            entries.push(PaletteEntry::run(
+                format!("{}: {}", verb, node.text),
+                Action::ToggleTrait(i),
+            ));
+        }
+        for (i, name) in self.list_data.misfortunes.iter().enumerate() {
+            if name.is_empty() {
+                continue;
+            }
+            let verb = if self.additional_red_balls[i] != 0 {
+                "Ritira sventura"
+            } else {
+                "Metti in gioco sventura"
+            };
+            entries.push(PaletteEntry::run(
+                format!("{}: {}", verb, name),
+                Action::ToggleMisfortune(i),
+            ));
+        }
+
+        entries.extend([
+            PaletteEntry::ask("Imposta token bianchi…", Prompt::WhiteBalls),
+            PaletteEntry::ask("Imposta token rossi…", Prompt::RedBalls),
+            PaletteEntry::run("Effettua la prova", Action::StartDraw),
+            PaletteEntry::run("Prova rapida in una riga", Action::OpenQuickEntry),
+            PaletteEntry::run("Azzera la prova", Action::Reset),
+            PaletteEntry::run("Scegli i tratti per la prova", Action::OpenTraitPicker),
+            PaletteEntry::run("Cerca un tratto", Action::OpenTraitSearch),
+            PaletteEntry::run("Sacchetto fisico sì/no", Action::ToggleManual),
+            PaletteEntry::ask("Esporta la scheda in Markdown…", Prompt::ExportFile),
+            PaletteEntry::ask("Importa la scheda da CSV…", Prompt::ImportFile),
+            PaletteEntry::run("Creazione guidata del personaggio", Action::StartWizard),
+            PaletteEntry::run("Evoluzione del personaggio", Action::OpenTimeline),
+            PaletteEntry::run("Diario delle sessioni", Action::OpenSessions),
+            PaletteEntry::run("Campagne e personaggi", Action::OpenCampaigns),
+        ]);
+
+        let current = self.sheet_format();
+        for format in SheetFormat::ALL.into_iter().filter(|f| *f != current) {
+            entries.push(PaletteEntry::run(
+                format!("Salva la scheda in formato {}", format),
+                Action::ConvertSheet(format),
+            ));
+        }
+
+        if let Some(campaign) = &self.campaign {
+            for character in &self.campaign_characters {
+                if *character != campaign.character {
+                    entries.push(PaletteEntry::run(
+                        format!("Passa al personaggio: {}", character),
+                        Action::SwitchCharacter(character.clone()),
+                    ));
+                }
+            }
+        }
+        entries
+    }
+
+    /// Voci che corrispondono al testo digitato
+    pub fn palette_matches(&self) -> Vec<PaletteEntry> {
+        filter_entries(self.palette_entries(), &self.palette_query)
+    }
+
+    /// Passa alla voce precedente o successiva
+    pub fn select_palette_entry(&mut self, next: bool) {
+        if next {
+            if self.palette_selected + 1 < self.palette_matches().len() {
+                self.palette_selected += 1;
+            }
+        } else {
+            self.palette_selected = self.palette_selected.saturating_sub(1);
+        }
+    }
+
+    /// Modifica il testo digitato; `None` cancella l'ultimo carattere
+    pub fn edit_palette_query(&mut self, c: Option<char>) {
+        match c {
+            Some(c) => self.palette_query.push(c),
+            None => {
+                self.palette_query.pop();
+            }
+        }
+        self.palette_selected = 0;
+    }
+
+    /// Esegue la voce scelta, o chiede il suo argomento
+    pub fn confirm_palette(&mut self) -> Result<(), String> {
+        if let Some(prompt) = self.palette_prompt {
+            let action = prompt.action(&self.palette_query)?;
+            self.popup = PopupType::None;
+            return self.run_action(action);
+        }
+
+        let entry = self
+            .palette_matches()
+            .into_iter()
+            .nth(self.palette_selected)
+            .ok_or("Nessun comando corrisponde")?;
+        match entry.command {
+            PaletteCommand::Run(action) => {
+                self.popup = PopupType::None;
+                self.run_action(action)
+            }
+            PaletteCommand::Ask(prompt) => {
+                self.palette_prompt = Some(prompt);
+                self.palette_query.clear();
+                Ok(())
+            }
+        }
+    }
+
+    /// Torna all'elenco dalla richiesta di un argomento, oppure chiude la palette
+    pub fn cancel_palette(&mut self) {
+        if self.palette_prompt.take().is_some() {
+            self.palette_query.clear();
+        } else {
+            self.popup = PopupType::None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod palette_impl_tests {
+    use crate::app::{App, PopupType, TabType};
+
+    fn type_text(app: &mut App, text: &str) {
+        for c in text.chars() {
+            app.edit_palette_query(Some(c));
+        }
+    }
+
+    #[test]
+    fn test_toggle_trait_by_name() {
+        let mut app = App::new();
+        app.honeycomb_nodes[4].text = "Occhio di falco".into();
+        app.open_palette();
+        type_text(&mut app, "falco");
+        app.confirm_palette().unwrap();
+        assert_eq!(app.popup, PopupType::None);
+        assert!(app.used_traits.contains(&4));
+    }
+
+    #[test]
+    fn test_argument_prompt() {
+        let mut app = App::new();
+        app.open_palette();
+        type_text(&mut app, "token rossi");
+        app.confirm_palette().unwrap();
+        assert!(app.palette_prompt.is_some());
+        assert_eq!(app.popup, PopupType::CommandPalette);
+
+        type_text(&mut app, "troppi");
+        assert!(app.confirm_palette().is_err());
+        app.palette_query.clear();
+        type_text(&mut app, "4");
+        app.confirm_palette().unwrap();
+        assert_eq!(app.red_balls, 4);
+        assert_eq!(app.popup, PopupType::None);
+    }
+
+    #[test]
+    fn test_switch_tab_and_cancel() {
+        let mut app = App::new();
+        app.open_palette();
+        type_text(&mut app, "vai log");
+        app.confirm_palette().unwrap();
+        assert_eq!(app.current_tab, TabType::LogTab);
+
+        app.open_palette();
+        app.cancel_palette();
+        assert_eq!(app.popup, PopupType::None);
+    }
+}
@@ -0,0 +1,95 @@
+use super::super::app_state::App;
+use super::super::hit::HitId;
+use super::super::honeycomb::{ARCHETYPE_NODE, HONEYCOMB_SIZE};
+use super::super::list::ListSection;
+use super::super::types::PlayPane;
+
+/// Numero di sventure della scheda
+const MISFORTUNES: usize = 4;
+
+/// Indice successivo o precedente, ricominciando dall'altro capo
+fn step(idx: usize, next: bool, len: usize) -> usize {
+    if next {
+        (idx + 1) % len
+    } else {
+        (idx + len - 1) % len
+    }
+}
+
+impl App {
+    /// Sposta la selezione nel riquadro attivo della schermata di gioco
+    pub fn play_select(&mut self, next: bool) {
+        match self.play_focus {
+            PlayPane::Traits => {
+                let idx = self.selected_node.unwrap_or(ARCHETYPE_NODE);
+                self.selected_node = Some(step(idx, next, HONEYCOMB_SIZE));
+            }
+            PlayPane::Misfortunes => {
+                let idx = match self.selected_list_item {
+                    Some((ListSection::Misfortunes | ListSection::MisfortunesDifficult, idx)) => {
+                        step(idx, next, MISFORTUNES)
+                    }
+                    _ => 0,
+                };
+                self.selected_list_item = Some((ListSection::Misfortunes, idx));
+            }
+            PlayPane::Draw => {
+                self.focused_section = if next {
+                    self.focused_section.next()
+                } else {
+                    self.focused_section.prev()
+                };
+            }
+        }
+    }
+
+    /// Porta il focus sul riquadro dell'elemento cliccato nella schermata di gioco
+    pub(crate) fn focus_play_pane(&mut self, hit: HitId) {
+        self.play_focus = match hit {
+            HitId::Node(_) => PlayPane::Traits,
+            HitId::Misfortune(_) | HitId::MisfortuneDifficulty(_) => PlayPane::Misfortunes,
+            HitId::WhiteBalls
+            | HitId::RedBalls
+            | HitId::DrawInput
+            | HitId::RandomMode
+            | HitId::ForcedFour => PlayPane::Draw,
+            _ => return,
+        };
+    }
+}
+
+#[cfg(test)]
+mod play_tests {
+    use crate::app::{App, FocusedSection, HitId, ListSection, PlayPane};
+
+    #[test]
+    fn test_select_wraps_inside_pane() {
+        let mut app = App::new();
+        app.selected_node = Some(18);
+        app.play_select(true);
+        assert_eq!(app.selected_node, Some(0));
+        app.play_select(false);
+        assert_eq!(app.selected_node, Some(18));
+
+        app.play_focus = PlayPane::Misfortunes;
+        app.selected_list_item = Some((ListSection::Misfortunes, 0));
+        app.play_select(false);
+        assert_eq!(app.selected_list_item, Some((ListSection::Misfortunes, 3)));
+
+        app.play_focus = PlayPane::Draw;
+        app.focused_section = FocusedSection::WhiteBalls;
+        app.play_select(true);
+        assert_eq!(app.focused_section, FocusedSection::RedBalls);
+    }
+
+    #[test]
+    fn test_click_moves_focus() {
+        let mut app = App::new();
+        app.focus_play_pane(HitId::DrawInput);
+        assert_eq!(app.play_focus, PlayPane::Draw);
+        app.focus_play_pane(HitId::Log);
+        assert_eq!(app.play_focus, PlayPane::Draw);
+        app.focus_play_pane(HitId::Misfortune(2));
+        assert_eq!(app.play_focus, PlayPane::Misfortunes);
+    }
+}
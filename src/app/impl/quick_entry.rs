@@ -0,0 +1,157 @@
+use super::super::app_state::{App, MAX_DRAW, MAX_TOKEN, MIN_DRAW};
+use super::super::quick_entry::{QuickChallenge, complete, parse_challenge, suggestions};
+use super::super::types::{FocusedSection, PopupType};
+
+impl App {
+    /// Apre l'inserimento rapido della prova
+    pub fn open_quick_entry(&mut self) {
+        self.quick_entry.clear();
+        self.popup = PopupType::QuickEntry;
+    }
+
+    /// Nomi dei tratti presenti nella scheda
+    fn trait_names(&self) -> Vec<&str> {
+        self.honeycomb_nodes
+            .iter()
+            .map(|n| n.text.as_str())
+            .filter(|t| !t.is_empty())
+            .collect()
+    }
+
+    /// Nodo con il nome indicato, anche solo in parte se non è ambiguo
+    pub(crate) fn resolve_trait(&self, name: &str) -> Result<usize, String> {
+        let lower = name.to_lowercase();
+        let filled = || {
+            self.honeycomb_nodes
+                .iter()
+                .enumerate()
+                .filter(|(_, n)| !n.text.is_empty())
+        };
+        if let Some((idx, _)) = filled().find(|(_, n)| n.text.to_lowercase() == lower) {
+            return Ok(idx);
+        }
+
+        let found: Vec<(usize, &str)> = filled()
+            .filter(|(_, n)| n.text.to_lowercase().starts_with(&lower))
+            .map(|(i, n)| (i, n.text.as_str()))
+            .collect();
+        match found.as_slice() {
+            [(idx, _)] => Ok(*idx),
+            [] => Err(format!("Nella scheda non c'è il tratto «{}»", name)),
+            _ => Err(format!(
+                "«{}» può essere {}",
+                name,
+                found.iter().map(|(_, t)| *t).collect::<Vec<_>>().join(", ")
+            )),
+        }
+    }
+
+    /// Prova descritta dalla riga attuale, con i tratti già trovati nella scheda
+    pub fn quick_entry_plan(&self) -> Result<(QuickChallenge, Vec<usize>), String> {
+        let challenge = parse_challenge(&self.quick_entry)?;
+        let mut traits = Vec::new();
+        for name in &challenge.traits {
+            let idx = self.resolve_trait(name)?;
+            if traits.contains(&idx) {
+                return Err(format!(
+                    "«{}» è indicato più volte",
+                    self.honeycomb_nodes[idx].text
+                ));
+            }
+            traits.push(idx);
+        }
+        if traits.len() > MAX_TOKEN {
+            return Err(format!("Al massimo {} tratti", MAX_TOKEN));
+        }
+        Ok((challenge, traits))
+    }
+
+    /// Prepara la prova scritta nella riga, pronta per l'estrazione
+    pub fn confirm_quick_entry(&mut self) -> Result<(), String> {
+        let (challenge, traits) = self.quick_entry_plan()?;
+
+        self.reset();
+        self.white_balls = traits.len();
+        self.used_traits = traits;
+        self.red_balls = challenge.difficulty;
+        self.random_mode = challenge.confusion;
+        self.forced_four_mode = challenge.adrenaline;
+        self.draw_count = if challenge.adrenaline {
+            MAX_DRAW
+        } else {
+            challenge.draw.unwrap_or(MIN_DRAW)
+        };
+        self.focused_section = FocusedSection::DrawInput;
+        self.send_traits();
+        Ok(())
+    }
+
+    /// Completa la parola che si sta scrivendo, con i comandi o i tratti della scheda
+    pub fn complete_quick_entry(&mut self) {
+        if let Some(line) = complete(&self.quick_entry, &self.trait_names()) {
+            self.quick_entry = line;
+        }
+    }
+
+    /// Candidati per la parola che si sta scrivendo
+    pub fn quick_entry_suggestions(&self) -> Vec<&str> {
+        suggestions(&self.quick_entry, &self.trait_names())
+    }
+}
+
+#[cfg(test)]
+mod quick_entry_impl_tests {
+    use crate::app::{App, FocusedSection, MAX_DRAW, PopupType};
+
+    fn app_with_traits() -> App {
+        let mut app = App::new();
+        app.honeycomb_nodes[9].text = "Coraggioso".into();
+        app.honeycomb_nodes[4].text = "Spada".into();
+        app.honeycomb_nodes[3].text = "Spavaldo".into();
+        app
+    }
+
+    #[test]
+    fn test_confirm_sets_up_the_challenge() {
+        let mut app = app_with_traits();
+        app.white_balls = 7;
+        app.open_quick_entry();
+        app.quick_entry = "traits: coraggioso, Spad; diff 3; draw 2; confusion".into();
+        app.confirm_quick_entry().unwrap();
+
+        assert_eq!(app.used_traits, vec![9, 4]);
+        assert_eq!((app.white_balls, app.red_balls, app.draw_count), (2, 3, 2));
+        assert!(app.random_mode && !app.forced_four_mode);
+        assert_eq!(app.focused_section, FocusedSection::DrawInput);
+        assert_eq!(app.popup, PopupType::None);
+
+        app.quick_entry = "diff 1; adrenalina".into();
+        app.confirm_quick_entry().unwrap();
+        assert!(app.used_traits.is_empty());
+        assert_eq!(app.draw_count, MAX_DRAW);
+    }
+
+    #[test]
+    fn test_unknown_and_ambiguous_traits() {
+        let mut app = app_with_traits();
+        app.quick_entry = "tratti: Spa".into();
+        let err = app.quick_entry_plan().unwrap_err();
+        assert!(err.contains("Spada") && err.contains("Spavaldo"), "{}", err);
+
+        app.quick_entry = "tratti: Arco".into();
+        assert!(app.quick_entry_plan().is_err());
+        app.quick_entry = "tratti: Spada, spada".into();
+        assert!(app.quick_entry_plan().is_err());
+        assert_eq!(app.white_balls, 0);
+    }
+
+    #[test]
+    fn test_completion_uses_sheet_traits() {
+        let mut app = app_with_traits();
+        app.quick_entry = "tratti: cor".into();
+        app.complete_quick_entry();
+        assert_eq!(app.quick_entry, "tratti: Coraggioso");
+        app.quick_entry.push_str(", sp");
+        assert_eq!(app.quick_entry_suggestions(), vec!["Spavaldo", "Spada"]);
+    }
+}
@@ -0,0 +1,188 @@
+use super::super::app_state::App;
+use super::super::character::CharacterSection;
+use super::super::search::{fuzzy_score, keyword_matches, keywords};
+use super::super::types::{PopupType, TabType};
+
+impl App {
+    /// Tratti della griglia ordinati per pertinenza rispetto alla ricerca.
+    ///
+    /// Il titolo pesa più dei tag, che a loro volta pesano più della descrizione.
+    pub fn search_traits(&self, query: &str) -> Vec<usize> {
+        let mut results: Vec<(usize, i64)> = self
+            .honeycomb_nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| !node.text.is_empty())
+            .filter_map(|(i, node)| {
+                let title = fuzzy_score(query, &node.text).map(|s| s * 3);
+                let tags = node
+                    .tags
+                    .iter()
+                    .filter_map(|t| fuzzy_score(query, t))
+                    .max()
+                    .map(|s| s * 2);
+                let description = fuzzy_score(query, &node.description);
+                [title, tags, description]
+                    .into_iter()
+                    .flatten()
+                    .max()
+                    .map(|score| (i, score))
+            })
+            .collect();
+
+        results.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        results.into_iter().map(|(i, _)| i).collect()
+    }
+
+    /// Tratti compilati ordinati per attinenza alla descrizione della prova.
+    ///
+    /// Ogni parola chiave trovata nei tag vale 3, nel titolo 2 e nella
+    /// descrizione 1. I tratti senza corrispondenze restano in coda, così da
+    /// poter essere comunque scelti dal selettore.
+    pub fn suggest_traits(&self) -> Vec<(usize, usize)> {
+        let keys = keywords(&self.challenge_description);
+        let mut results: Vec<(usize, usize)> = self
+            .honeycomb_nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| !node.text.is_empty())
+            .map(|(i, node)| {
+                let tags = keyword_matches(&keys, &node.tags.join(" "));
+                let title = keyword_matches(&keys, &node.text);
+                let description = keyword_matches(&keys, &node.description);
+                (i, tags * 3 + title * 2 + description)
+            })
+            .collect();
+
+        results.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        results
+    }
+
+    /// Apre il selettore dei tratti suggeriti per la prova
+    pub fn open_trait_picker(&mut self) {
+        self.picker_selected = 0;
+        self.popup = PopupType::TraitPicker;
+    }
+
+    /// Aggiunge o rimuove dalla prova il tratto evidenziato nel selettore
+    pub fn toggle_picked_trait(&mut self) {
+        if let Some(&(idx, _)) = self.suggest_traits().get(self.picker_selected) {
+            self.toggle_trait(idx);
+        }
+    }
+
+    /// Apre la ricerca dei tratti
+    pub fn open_trait_search(&mut self) {
+        self.search_query.clear();
+        self.search_selected = 0;
+        self.popup = PopupType::TraitSearch;
+    }
+
+    /// Seleziona il tratto evidenziato nei risultati e chiude la ricerca
+    pub fn confirm_trait_search(&mut self) {
+        if let Some(&idx) = self
+            .search_traits(&self.search_query)
+            .get(self.search_selected)
+        {
+            self.selected_node = Some(idx);
+            self.selected_character_info = CharacterSection::None;
+            self.current_tab = TabType::CharacterSheetTab;
+        }
+        self.popup = PopupType::None;
+    }
+}
+
+#[cfg(test)]
+mod search_impl_tests {
+    use crate::app::{App, PopupType, TabType};
+
+    fn app_with_traits() -> App {
+        let mut app = App::new();
+        for node in app.honeycomb_nodes.iter_mut() {
+            node.text.clear();
+            node.tags.clear();
+            node.description.clear();
+        }
+        app.honeycomb_nodes[9].text = "Cavaliere".to_string();
+        app.honeycomb_nodes[4].text = "Coraggioso".to_string();
+        app.honeycomb_nodes[0].text = "Spada".to_string();
+        app.honeycomb_nodes[0].tags = vec!["combattimento".to_string()];
+        app.honeycomb_nodes[1].text = "Nuotare".to_string();
+        app.honeycomb_nodes[1].description = "Attraversare fiumi e mari".to_string();
+        app
+    }
+
+    #[test]
+    fn test_search_by_title() {
+        let app = app_with_traits();
+        assert_eq!(app.search_traits("spada").first(), Some(&0));
+        assert_eq!(app.search_traits("cor").first(), Some(&4));
+    }
+
+    #[test]
+    fn test_search_by_tag_and_description() {
+        let app = app_with_traits();
+        assert_eq!(app.search_traits("combat"), vec![0]);
+        assert_eq!(app.search_traits("fiumi"), vec![1]);
+    }
+
+    #[test]
+    fn test_empty_query_lists_filled_traits() {
+        let app = app_with_traits();
+        assert_eq!(app.search_traits("").len(), 4);
+    }
+
+    #[test]
+    fn test_suggest_traits_ranks_tags_first() {
+        let mut app = app_with_traits();
+        app.honeycomb_nodes[4].description = "Non teme il combattimento".to_string();
+        app.challenge_description = "Combatto contro i briganti".to_string();
+
+        let ranked: Vec<usize> = app.suggest_traits().iter().map(|(i, _)| *i).collect();
+
+        assert_eq!(ranked, vec![0, 4, 1, 9]);
+        assert_eq!(app.suggest_traits()[0].1, 3);
+    }
+
+    #[test]
+    fn test_toggle_picked_trait() {
+        let mut app = app_with_traits();
+        app.challenge_description = "attraversare il fiume".to_string();
+        app.open_trait_picker();
+
+        app.toggle_picked_trait();
+        assert_eq!(app.used_traits, vec![1]);
+        assert_eq!(app.white_balls, 1);
+
+        app.toggle_picked_trait();
+        assert!(app.used_traits.is_empty());
+        assert_eq!(app.white_balls, 0);
+    }
+
+    #[test]
+    fn test_description_is_stored_on_draw() {
+        let mut app = app_with_traits();
+        app.challenge_description = "Scalare le mura".to_string();
+        app.white_balls = 1;
+        app.red_balls = 1;
+        app.perform_first_draw();
+        app.cancel_draw();
+
+        assert_eq!(app.history[0].description, "Scalare le mura");
+        assert!(app.challenge_description.is_empty());
+    }
+
+    #[test]
+    fn test_confirm_jumps_to_trait() {
+        let mut app = app_with_traits();
+        app.current_tab = TabType::DrawTab;
+        app.open_trait_search();
+        app.search_query = "nuot".to_string();
+
+        app.confirm_trait_search();
+
+        assert_eq!(app.selected_node, Some(1));
+        assert_eq!(app.current_tab, TabType::CharacterSheetTab);
+        assert_eq!(app.popup, PopupType::None);
+    }
+}
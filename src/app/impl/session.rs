@@ -0,0 +1,691 @@
+use super::super::app_state::{App, MAX_TOKEN};
+use super::super::history::{DrawHistory, valid_count};
+use super::super::narrator::{AppMode, PlayerStatus};
+use super::super::proof::{self, PendingDraw};
+use super::super::types::{BallType, PopupType};
+use crate::session::{Message, Role};
+
+impl App {
+    /// Nome con cui l'istanza compare nella sessione e nel log
+    pub fn session_name(&self) -> String {
+        match self.mode {
+            AppMode::Narrator => "Narratore".to_string(),
+            AppMode::Player if self.character_base_info.name.trim().is_empty() => {
+                "Giocatore".to_string()
+            }
+            AppMode::Player => self.character_base_info.name.trim().to_string(),
+        }
+    }
+
+    /// Segna l'istanza come host della sessione
+    pub fn host_session(&mut self, addr: &str) {
+        self.session_label = Some(format!("Host {}", addr));
+        self.session_host = true;
+    }
+
+    /// Segna l'istanza come collegata a un host e si presenta
+    pub fn join_session(&mut self, addr: &str) {
+        self.session_label = Some(format!("Collegato a {}", addr));
+        self.session_host = false;
+        let role = match self.mode {
+            AppMode::Player => Role::Player,
+            AppMode::Narrator => Role::Narrator,
+        };
+        self.send(Message::Hello {
+            name: self.session_name(),
+            role,
+        });
+    }
+
+    /// Accoda un messaggio per le altre istanze, se c'è una sessione attiva
+    pub(crate) fn send(&mut self, msg: Message) {
+        if self.session_label.is_some() {
+            self.outbox.push(msg);
+        }
+    }
+
+    /// Comunica alla sessione la difficoltà decisa dal narratore
+    pub(crate) fn send_difficulty(&mut self) {
+        if self.mode == AppMode::Narrator {
+            self.send(Message::Difficulty {
+                tokens: self.narrator.challenge_difficulty(),
+            });
+        }
+    }
+
+    /// Comunica alla sessione i tratti messi in gioco
+    pub(crate) fn send_traits(&mut self) {
+        let traits = self
+            .used_traits
+            .iter()
+            .map(|idx| self.honeycomb_nodes[*idx].text.clone())
+            .collect();
+        self.send(Message::Traits {
+            player: self.session_name(),
+            traits,
+        });
+    }
+
+    /// Applica un messaggio ricevuto da un'altra istanza
+    pub fn apply_message(&mut self, msg: Message) {
+        match msg {
+            Message::Hello { name, role } => {
+                self.status_message = Some(format!("{} si è unito alla sessione", name));
+                self.add_session_participant(&name);
+                if role == Role::Player && self.mode == AppMode::Narrator {
+                    self.narrator_player(&name);
+                }
+                if self.session_host {
+                    let difficulty = (self.mode == AppMode::Narrator)
+                        .then(|| self.narrator.challenge_difficulty());
+                    self.send(Message::Welcome {
+                        history: self.history.clone(),
+                        difficulty,
+                        authoritative: self.authoritative,
+                    });
+                }
+            }
+            Message::Welcome {
+                history,
+                difficulty,
+                authoritative,
+            } => {
+                // Le impronte delle prove passate sono garantite dall'host
+                self.authoritative = authoritative;
+                if authoritative {
+                    // Il sacchetto fisico sfuggirebbe all'host
+                    self.manual_mode = false;
+                }
+                // Le prove fuori dai limiti del gioco non entrano nello storico
+                let history: Vec<DrawHistory> = history
+                    .into_iter()
+                    .filter(DrawHistory::within_limits)
+                    .collect();
+                for proof in history.iter().filter_map(|entry| entry.proof.as_ref()) {
+                    self.commitments.push((proof.id, proof.commitment.clone()));
+                    self.record_nonce(proof.id, &proof.nonce);
+                }
+                // Lo storico dell'host è quello di riferimento; le prove locali
+                // non ancora note all'host restano in coda e gli vengono inviate
+                let local = std::mem::replace(&mut self.history, history);
+                for entry in local {
+                    if !self.history.contains(&entry) {
+                        self.history.push(entry.clone());
+                        self.send(Message::Draw { entry });
+                    }
+                }
+                if let Some(tokens) = difficulty {
+                    self.apply_difficulty(tokens);
+                }
+            }
+            Message::Difficulty { tokens } => self.apply_difficulty(tokens),
+            Message::Traits { player, traits } => {
+                if self.mode == AppMode::Narrator {
+                    self.narrator_player(&player).traits = traits;
+                }
+            }
+            Message::Draw { entry } => self.receive_draw(entry),
+            Message::DrawRequest { id, entry, count } => {
+                // Un identificativo già annunciato confonderebbe le verifiche
+                let known = self.commitments.iter().any(|(known, _)| *known == id);
+                if !entry.within_limits() || !valid_count(count) {
+                    self.status_message = Some(format!(
+                        "Richiesta di {} rifiutata: sacchetto fuori dai limiti",
+                        entry.player
+                    ));
+                } else if self.session_host && self.authoritative && !known {
+                    self.serve_draw(id, entry, count);
+                }
+            }
+            Message::Commit { id, commitment } => {
+                self.commitments.push((id, commitment));
+                self.receive_commit(id);
+            }
+            Message::Nonce { id, nonce } => {
+                // Il nonce della propria richiesta lo sceglie solo questa istanza
+                if self.draw_request != Some(id)
+                    && self.record_nonce(id, &nonce)
+                    && self.session_host
+                {
+                    self.serve_nonce(id, &nonce);
+                }
+            }
+            Message::FirstDraw { id, balls } => self.receive_first_draw(id, balls),
+            Message::RiskRequest { id, risk } => {
+                if self.session_host {
+                    self.serve_risk(id, risk);
+                }
+            }
+        }
+    }
+
+    /// Sessione in cui tutte le prove le estrae l'host
+    pub fn authoritative_session(&self) -> bool {
+        self.authoritative && self.session_label.is_some()
+    }
+
+    /// Esito della verifica di una prova: `None` se non è stata estratta dall'host
+    /// o se è stata pescata da un sacchetto fisico.
+    ///
+    /// Una prova è valida se il seme rivelato corrisponde all'impronta annunciata
+    /// dall'host, il nonce è quello annunciato da chi l'ha chiesta e da entrambi
+    /// discende esattamente l'esito registrato.
+    pub fn draw_verified(&self, entry: &DrawHistory) -> Option<bool> {
+        if entry.manual {
+            return None;
+        }
+        let Some(proof) = &entry.proof else {
+            return self.authoritative.then_some(false);
+        };
+        let announced = self
+            .commitments
+            .iter()
+            .any(|(id, commitment)| *id == proof.id && *commitment == proof.commitment);
+        let nonce = self
+            .nonces
+            .iter()
+            .find(|(id, _)| *id == proof.id)
+            .is_some_and(|(_, nonce)| *nonce == proof.nonce);
+        let own = self.draw_request != Some(proof.id) || proof.nonce == self.draw_nonce;
+        Some(announced && nonce && own && proof.verify(entry))
+    }
+
+    /// Registra il nonce annunciato per una richiesta; vale solo il primo, così
+    /// nessuno può sostituirlo dopo l'impronta. Vero se è stato registrato.
+    fn record_nonce(&mut self, id: u64, nonce: &str) -> bool {
+        if self.nonces.iter().any(|(known, _)| *known == id) {
+            return false;
+        }
+        self.nonces.push((id, nonce.to_string()));
+        true
+    }
+
+    /// Chiede all'host di estrarre la prova corrente
+    pub(crate) fn request_draw(&mut self) {
+        let id = self.next_draw_id;
+        self.next_draw_id = self.next_draw_id.wrapping_add(1);
+        let entry = self.draw_entry();
+        self.draw_request = Some(id);
+        self.draw_nonce = proof::new_nonce();
+        self.popup = PopupType::AwaitingHost;
+        if self.session_host {
+            self.serve_draw(id, entry, self.draw_count);
+        } else {
+            self.send(Message::DrawRequest {
+                id,
+                entry,
+                count: self.draw_count,
+            });
+        }
+    }
+
+    /// Comunica all'host se rischiare nella prova in corso
+    pub(crate) fn request_risk(&mut self, risk: bool) {
+        let Some(id) = self.draw_request else {
+            return;
+        };
+        self.popup = PopupType::AwaitingHost;
+        if self.session_host {
+            self.serve_risk(id, risk);
+        } else {
+            self.send(Message::RiskRequest { id, risk });
+        }
+    }
+
+    /// Host: sceglie il seme e ne annuncia l'impronta
+    fn serve_draw(&mut self, id: u64, entry: DrawHistory, count: usize) {
+        // Le richieste abbandonate da chi si è scollegato non restano in memoria
+        self.pending_draws.retain(|p| !p.expired());
+        let pending = PendingDraw::start(id, entry, count);
+        let commitment = pending.commitment.clone();
+        self.pending_draws.push(pending);
+        self.commitments.push((id, commitment.clone()));
+        self.send(Message::Commit { id, commitment });
+        self.receive_commit(id);
+    }
+
+    /// Impronta annunciata per la propria richiesta: risponde con il nonce
+    fn receive_commit(&mut self, id: u64) {
+        if self.draw_request != Some(id) {
+            return;
+        }
+        let nonce = self.draw_nonce.clone();
+        // Un nonce arrivato prima dell'impronta non può prendere il posto del proprio
+        self.nonces.retain(|(known, _)| *known != id);
+        self.nonces.push((id, nonce.clone()));
+        self.send(Message::Nonce {
+            id,
+            nonce: nonce.clone(),
+        });
+        if self.session_host {
+            self.serve_nonce(id, &nonce);
+        }
+    }
+
+    /// Host: estrae il primo tentativo con il nonce ricevuto
+    fn serve_nonce(&mut self, id: u64, nonce: &str) {
+        let Some(pending) = self.pending_draws.iter_mut().find(|p| p.id == id) else {
+            return;
+        };
+        let Some(balls) = pending.first_draw(nonce) else {
+            self.status_message = Some("Nonce non valido per la prova richiesta".to_string());
+            return;
+        };
+        self.send(Message::FirstDraw {
+            id,
+            balls: balls.clone(),
+        });
+        self.receive_first_draw(id, balls);
+    }
+
+    /// Host: conclude la prova e ne rivela il seme a tutti
+    fn serve_risk(&mut self, id: u64, risk: bool) {
+        let Some(pos) = self
+            .pending_draws
+            .iter()
+            .position(|p| p.id == id && p.is_drawn())
+        else {
+            return;
+        };
+        let Some(entry) = self.pending_draws.remove(pos).finish(risk) else {
+            return;
+        };
+        self.send(Message::Draw {
+            entry: entry.clone(),
+        });
+        self.receive_draw(entry);
+    }
+
+    /// Primo tentativo estratto dall'host per la propria richiesta
+    fn receive_first_draw(&mut self, id: u64, balls: Vec<BallType>) {
+        if self.draw_request != Some(id) {
+            return;
+        }
+        self.drawn_balls = balls.clone();
+        self.current_first_draw = balls;
+        if self.drawn_balls.len() < 5 {
+            self.popup = PopupType::ConfirmRisk;
+        } else {
+            self.request_risk(false);
+        }
+    }
+
+    /// Prova conclusa da un'altra istanza o estratta dall'host
+    fn receive_draw(&mut self, entry: DrawHistory) {
+        if entry.manual && self.authoritative_session() {
+            self.status_message = Some(format!(
+                "Prova di {} dal sacchetto fisico rifiutata: le estrae l'host",
+                entry.player
+            ));
+            return;
+        }
+        if !entry.within_limits() {
+            self.status_message = Some(format!(
+                "Prova di {} rifiutata: sacchetto fuori dai limiti",
+                entry.player
+            ));
+            return;
+        }
+        let own = entry
+            .proof
+            .as_ref()
+            .is_some_and(|proof| self.draw_request == Some(proof.id));
+        if own {
+            self.draw_request = None;
+            self.drawn_balls = entry.first_draw.clone();
+            self.drawn_balls.extend(entry.risk_draw.iter().copied());
+            self.push_history(entry);
+            self.finish_draw();
+        } else {
+            self.status_message = Some(format!("Nuova prova di {}", entry.player));
+            self.push_history(entry);
+            self.update_vertical_scroll_state();
+        }
+    }
+
+    /// Difficoltà ricevuta dal narratore: diventa quella della prova del giocatore
+    fn apply_difficulty(&mut self, tokens: usize) {
+        if self.mode == AppMode::Player && tokens <= MAX_TOKEN {
+            // Le sventure messe in gioco restano in aggiunta
+            let misfortunes = self.additional_red_balls.iter().sum::<usize>();
+            self.red_balls = (tokens + misfortunes).min(MAX_TOKEN);
+            self.status_message = Some(format!("Il narratore fissa la difficoltà a {}", tokens));
+        }
+    }
+
+    /// Giocatore della plancia del narratore con il nome indicato, creato se manca
+    fn narrator_player(&mut self, name: &str) -> &mut PlayerStatus {
+        let pos = match self.narrator.players.iter().position(|p| p.name == name) {
+            Some(pos) => pos,
+            None => {
+                self.narrator.players.push(PlayerStatus {
+                    name: name.to_string(),
+                    ..Default::default()
+                });
+                self.narrator.players.len() - 1
+            }
+        };
+        &mut self.narrator.players[pos]
+    }
+}
+
+#[cfg(test)]
+mod session_impl_tests {
+    use crate::app::proof;
+    use crate::app::{Action, App, AppMode, MAX_DRAW, MAX_TOKEN, PopupType};
+    use crate::session::{Message, Role};
+
+    fn online(mut app: App) -> App {
+        app.session_label = Some("test".into());
+        app
+    }
+
+    #[test]
+    fn test_no_messages_without_session() {
+        let mut app = App::new();
+        app.white_balls = 1;
+        app.red_balls = 1;
+        app.perform_first_draw();
+        app.cancel_draw();
+        assert!(app.outbox.is_empty());
+    }
+
+    #[test]
+    fn test_draw_is_sent_with_player_name() {
+        let mut app = online(App::new());
+        app.character_base_info.name = "Aria".into();
+        app.white_balls = 1;
+        app.red_balls = 1;
+        app.perform_first_draw();
+        app.cancel_draw();
+
+        match app.outbox.last() {
+            Some(Message::Draw { entry }) => assert_eq!(entry.player, "Aria"),
+            other => panic!("messaggio inatteso {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_host_welcomes_new_player() {
+        let mut host = online(App::new());
+        host.session_host = true;
+        host.mode = AppMode::Narrator;
+        host.narrator.difficulty = 3;
+
+        host.apply_message(Message::Hello {
+            name: "Aria".into(),
+            role: Role::Player,
+        });
+
+        assert_eq!(host.narrator.players[0].name, "Aria");
+        assert!(matches!(
+            host.outbox.last(),
+            Some(Message::Welcome {
+                difficulty: Some(3),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_narrator_difficulty_reaches_player() {
+        let mut player = App::new();
+        player.apply_message(Message::Difficulty { tokens: 4 });
+        assert_eq!(player.red_balls, 4);
+        // Una difficoltà oltre il massimo viene ignorata
+        player.apply_message(Message::Difficulty { tokens: usize::MAX });
+        assert_eq!(player.red_balls, 4);
+    }
+
+    #[test]
+    fn test_narrator_sees_submitted_traits() {
+        let mut narrator = App::new();
+        narrator.mode = AppMode::Narrator;
+        narrator.apply_message(Message::Traits {
+            player: "Aria".into(),
+            traits: vec!["Spada".into(), "Coraggiosa".into()],
+        });
+        assert_eq!(narrator.narrator.players[0].traits.len(), 2);
+    }
+
+    #[test]
+    fn test_welcome_keeps_local_draws() {
+        let mut player = online(App::new());
+        player.white_balls = 1;
+        player.red_balls = 1;
+        player.perform_first_draw();
+        player.cancel_draw();
+        let local = player.history[0].clone();
+
+        player.apply_message(Message::Welcome {
+            history: Vec::new(),
+            difficulty: None,
+            authoritative: false,
+        });
+
+        assert_eq!(player.history, vec![local.clone()]);
+        // L'host riceve le prove che non conosceva
+        assert_eq!(player.outbox.last(), Some(&Message::Draw { entry: local }));
+    }
+
+    fn authoritative_host() -> App {
+        let mut host = online(App::new());
+        host.session_host = true;
+        host.authoritative = true;
+        host
+    }
+
+    /// Consegna a `to` i messaggi in uscita da `from`
+    fn deliver(from: &mut App, to: &mut App) {
+        for msg in std::mem::take(&mut from.outbox) {
+            to.apply_message(msg);
+        }
+    }
+
+    #[test]
+    fn test_host_commits_before_revealing() {
+        let mut host = authoritative_host();
+        host.white_balls = 2;
+        host.red_balls = 2;
+        host.perform_first_draw();
+
+        assert_eq!(host.popup, PopupType::ConfirmRisk);
+        assert!(matches!(host.outbox[0], Message::Commit { .. }));
+        assert!(matches!(host.outbox[1], Message::Nonce { .. }));
+        assert!(matches!(host.outbox[2], Message::FirstDraw { .. }));
+
+        host.perform_risk_draw();
+        let entry = host.history.last().unwrap();
+        assert_eq!(entry.first_draw.len() + entry.risk_draw.len(), 4);
+        assert_eq!(host.draw_verified(entry), Some(true));
+        assert!(matches!(host.outbox.last(), Some(Message::Draw { .. })));
+        assert!(host.pending_draws.is_empty());
+    }
+
+    #[test]
+    fn test_player_draw_is_performed_by_host() {
+        let mut host = authoritative_host();
+        let mut player = online(App::new());
+        player.authoritative = true;
+        player.white_balls = 3;
+        player.red_balls = 1;
+        player.perform_first_draw();
+        assert_eq!(player.popup, PopupType::AwaitingHost);
+        assert!(player.history.is_empty());
+
+        // Richiesta, impronta, nonce e primo tentativo
+        deliver(&mut player, &mut host);
+        deliver(&mut host, &mut player);
+        assert_eq!(player.popup, PopupType::AwaitingHost);
+        deliver(&mut player, &mut host);
+        deliver(&mut host, &mut player);
+        assert_eq!(player.popup, PopupType::ConfirmRisk);
+        assert_eq!(player.drawn_balls.len(), 1);
+
+        player.cancel_draw();
+        deliver(&mut player, &mut host);
+        deliver(&mut host, &mut player);
+
+        assert_eq!(player.popup, PopupType::None);
+        assert_eq!(player.draw_request, None);
+        assert_eq!(player.history, host.history);
+        assert_eq!(player.draw_verified(&player.history[0]), Some(true));
+        let proof = player.history[0].proof.as_ref().unwrap();
+        assert_eq!(proof.nonce, player.draw_nonce);
+    }
+
+    #[test]
+    fn test_request_ids_are_not_reused() {
+        let mut host = authoritative_host();
+        let mut player = online(App::new());
+        player.authoritative = true;
+        player.white_balls = 2;
+        player.perform_first_draw();
+        let request = player.outbox.clone();
+        let first = player.draw_request.unwrap();
+
+        deliver(&mut player, &mut host);
+        assert_eq!(host.pending_draws.len(), 1);
+        // La stessa richiesta ripetuta non apre una seconda estrazione
+        for msg in request {
+            host.apply_message(msg);
+        }
+        assert_eq!(host.pending_draws.len(), 1);
+
+        player.request_draw();
+        assert_ne!(player.draw_request, Some(first));
+    }
+
+    #[test]
+    fn test_host_cannot_replace_the_nonce() {
+        let mut host = authoritative_host();
+        let mut player = online(App::new());
+        let mut spectator = online(App::new());
+        player.authoritative = true;
+        spectator.authoritative = true;
+        player.white_balls = 2;
+        player.red_balls = 1;
+        player.perform_first_draw();
+        let id = player.draw_request.unwrap();
+        deliver(&mut player, &mut host);
+
+        // Impronta a tutti, poi il nonce del giocatore arriva solo allo spettatore
+        for msg in std::mem::take(&mut host.outbox) {
+            spectator.apply_message(msg.clone());
+            player.apply_message(msg);
+        }
+        deliver(&mut player, &mut spectator);
+
+        // L'host annuncia un nonce scelto da lui ed estrae con quello
+        let forged = Message::Nonce {
+            id,
+            nonce: proof::new_nonce(),
+        };
+        spectator.apply_message(forged.clone());
+        player.apply_message(forged.clone());
+        host.apply_message(forged);
+        host.serve_risk(id, false);
+        for msg in std::mem::take(&mut host.outbox) {
+            spectator.apply_message(msg.clone());
+            player.apply_message(msg);
+        }
+
+        let entry = player.history.last().unwrap();
+        assert!(entry.proof.as_ref().unwrap().verify(entry));
+        assert_eq!(player.draw_verified(entry), Some(false));
+        assert_eq!(spectator.draw_verified(entry), Some(false));
+    }
+
+    #[test]
+    fn test_unannounced_proof_is_rejected() {
+        let mut cheater = authoritative_host();
+        cheater.white_balls = 4;
+        cheater.perform_first_draw();
+        cheater.perform_risk_draw();
+        let forged = cheater.history[0].clone();
+
+        let mut player = online(App::new());
+        player.authoritative = true;
+        player.apply_message(Message::Draw { entry: forged });
+
+        let entry = &player.history[0];
+        assert!(entry.proof.as_ref().unwrap().verify(entry));
+        assert_eq!(player.draw_verified(entry), Some(false));
+    }
+
+    #[test]
+    fn test_manual_draws_are_refused_in_authoritative_session() {
+        let mut host = authoritative_host();
+        host.white_balls = 2;
+        host.red_balls = 1;
+        assert!(host.run_action(Action::ToggleManual).is_err());
+        assert!(!host.manual_mode);
+
+        // Un giocatore che era passato al sacchetto fisico prima di unirsi
+        let mut player = online(App::new());
+        player.manual_mode = true;
+        player.apply_message(Message::Welcome {
+            history: Vec::new(),
+            difficulty: None,
+            authoritative: true,
+        });
+        assert!(!player.manual_mode);
+
+        // Una prova manuale arrivata comunque non entra nello storico
+        let mut entry = host.draw_entry();
+        entry.manual = true;
+        host.apply_message(Message::Draw { entry });
+        assert!(host.history.is_empty());
+        assert!(host.status_message.is_some());
+    }
+
+    #[test]
+    fn test_oversized_peer_counts_are_refused() {
+        let mut host = authoritative_host();
+        let mut entry = host.draw_entry();
+        entry.white_balls = usize::MAX / 2;
+        entry.red_balls = usize::MAX / 2;
+
+        // Una richiesta con un sacchetto enorme non apre estrazioni
+        host.apply_message(Message::DrawRequest {
+            id: 7,
+            entry: entry.clone(),
+            count: 1,
+        });
+        host.apply_message(Message::Nonce {
+            id: 7,
+            nonce: proof::new_nonce(),
+        });
+        assert!(host.pending_draws.is_empty());
+        assert!(host.outbox.is_empty());
+        assert!(host.status_message.is_some());
+
+        // Né un numero di token estratti fuori dai limiti
+        let mut entry = host.draw_entry();
+        entry.white_balls = 2;
+        host.apply_message(Message::DrawRequest {
+            id: 8,
+            entry,
+            count: MAX_DRAW + 1,
+        });
+        assert!(host.pending_draws.is_empty());
+
+        // Una prova ricevuta con i conteggi gonfiati non entra nello storico
+        let mut cheater = authoritative_host();
+        cheater.white_balls = 4;
+        cheater.perform_first_draw();
+        cheater.perform_risk_draw();
+        let mut inflated = cheater.history[0].clone();
+        inflated.red_balls = MAX_TOKEN + 1;
+        let mut player = online(App::new());
+        player.apply_message(Message::Draw {
+            entry: inflated.clone(),
+        });
+        player.apply_message(Message::Welcome {
+            history: vec![inflated],
+            difficulty: None,
+            authoritative: true,
+        });
+        assert!(player.history.is_empty());
+    }
+}
@@ -0,0 +1,362 @@
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::super::app_state::App;
+use super::super::format::SheetFormat;
+use super::super::sheet::{Sheet, merge_sheets};
+use super::super::types::PopupType;
+
+impl App {
+    /// Formato del file della scheda in uso
+    pub fn sheet_format(&self) -> SheetFormat {
+        SheetFormat::from_path(&self.sheet_file)
+    }
+
+    /// Scheda attualmente in memoria
+    pub fn sheet(&self) -> Sheet {
+        Sheet {
+            info: self.character_base_info.clone(),
+            nodes: self.honeycomb_nodes.clone(),
+            list: self.list_data.clone(),
+            advancement: self.advancement.clone(),
+        }
+    }
+
+    pub(crate) fn apply_sheet(&mut self, sheet: Sheet) {
+        self.character_base_info = sheet.info;
+        self.honeycomb_nodes = sheet.nodes;
+        self.list_data = sheet.list;
+        self.advancement = sheet.advancement;
+        self.update_notes_vertical_scroll_state();
+    }
+
+    /// La scheda in memoria ha campi non ancora scritti sul file
+    fn sheet_unsaved(&self) -> bool {
+        !self
+            .sheet()
+            .same_as(&Sheet::parse(&self.sheet_on_disk, self.sheet_format()))
+    }
+
+    /// Scheda completa letta dal contenuto del file; `None` se è vuoto o scritto a metà
+    pub(crate) fn read_disk_sheet(&self, contents: &str) -> Option<Sheet> {
+        Sheet::read(contents, self.sheet_format())
+    }
+
+    /// Unisce alla scheda in memoria le modifiche fatte al file da altri.
+    ///
+    /// Ritorna `false` se alcuni campi sono cambiati da entrambe le parti:
+    /// in quel caso si apre il confronto e la scheda non va salvata.
+    pub(crate) fn merge_disk_sheet(&mut self, contents: String, disk: &Sheet) -> bool {
+        let base = Sheet::parse(&self.sheet_on_disk, self.sheet_format());
+        let merge = merge_sheets(&base, &self.sheet(), disk);
+        self.apply_sheet(merge.sheet);
+        if merge.reloaded > 0 {
+            self.status_message = Some(format!(
+                "Scheda aggiornata dal file: {} campi",
+                merge.reloaded
+            ));
+        }
+
+        if merge.conflicts.is_empty() {
+            self.sheet_on_disk = contents;
+            return true;
+        }
+        self.sheet_conflicts = merge.conflicts;
+        self.sheet_conflict_disk = contents;
+        self.sheet_conflict_scroll = 0;
+        self.popup = PopupType::SheetConflict;
+        false
+    }
+
+    /// Il file della scheda è cambiato fuori dall'applicazione
+    pub fn sheet_changed_on_disk(&mut self, contents: String) {
+        if contents == self.sheet_on_disk {
+            // È l'ultimo salvataggio dell'applicazione
+            return;
+        }
+        let Some(disk) = self.read_disk_sheet(&contents) else {
+            // Vuoto o ancora in scrittura: si aspetta la prossima modifica
+            return;
+        };
+        if self.merge_disk_sheet(contents, &disk) && self.sheet_unsaved() {
+            self.write_sheet();
+        }
+    }
+
+    /// Risolve i conflitti con la versione sul disco
+    pub fn keep_disk_sheet(&mut self) {
+        let mut sheet = self.sheet();
+        for conflict in std::mem::take(&mut self.sheet_conflicts) {
+            sheet.set(conflict.field, conflict.disk);
+        }
+        self.apply_sheet(sheet);
+        self.sheet_on_disk = std::mem::take(&mut self.sheet_conflict_disk);
+        self.popup = PopupType::None;
+        if self.sheet_unsaved() {
+            self.write_sheet();
+        }
+    }
+
+    /// Risolve i conflitti con la versione in memoria, sovrascrivendo il file
+    pub fn keep_my_sheet(&mut self) {
+        self.sheet_conflicts.clear();
+        self.sheet_on_disk = std::mem::take(&mut self.sheet_conflict_disk);
+        self.popup = PopupType::None;
+        self.write_sheet();
+    }
+
+    /// Si può uscire senza perdere modifiche: con dei conflitti aperti si torna al confronto
+    pub fn ready_to_quit(&mut self) -> bool {
+        if self.sheet_conflicts.is_empty() {
+            return true;
+        }
+        self.popup = PopupType::SheetConflict;
+        self.status_message = Some("Risolvi i conflitti della scheda prima di uscire".into());
+        false
+    }
+
+    /// Con dei conflitti aperti scrive la scheda in memoria accanto al file, con `.locale` in fondo
+    pub fn save_unresolved_sheet(&self) -> Option<PathBuf> {
+        if self.sheet_conflicts.is_empty() {
+            return None;
+        }
+        let mut path = OsString::from(self.sheet_file.as_os_str());
+        path.push(".locale");
+        let path = PathBuf::from(path);
+        fs::write(&path, self.sheet_contents(self.sheet_format())).ok()?;
+        Some(path)
+    }
+
+    /// Riscrive la scheda in un altro formato; il vecchio file resta con `.bak` in fondo
+    pub fn convert_sheet(&mut self, format: SheetFormat) -> Result<(), String> {
+        let old = self.sheet_file.clone();
+        if SheetFormat::from_path(&old) == format {
+            return Err(format!("La scheda è già in formato {}", format));
+        }
+        let new = old.with_extension(format.extension());
+        if new.exists() {
+            return Err(format!("Esiste già {}", new.display()));
+        }
+        // Le modifiche fatte al file da altri entrano nella scheda convertita
+        if self.sheet_conflicts.is_empty() {
+            self.save_data();
+        }
+        if !self.sheet_conflicts.is_empty() {
+            self.popup = PopupType::SheetConflict;
+            return Err("Risolvi i conflitti della scheda prima di convertirla".into());
+        }
+
+        let contents = self.sheet_contents(format);
+        fs::write(&new, &contents).map_err(|e| format!("Conversione non riuscita: {}", e))?;
+        if old.exists() {
+            let backup = backup_path(&old);
+            if let Err(e) = fs::rename(&old, &backup) {
+                // Con entrambi i file resterebbe in uso il vecchio
+                let _ = fs::remove_file(&new);
+                return Err(format!("Conversione non riuscita: {}", e));
+            }
+        }
+        self.sheet_file = new;
+        self.sheet_on_disk = contents;
+        self.status_message = Some(format!("Scheda salvata in {}", self.sheet_file.display()));
+        Ok(())
+    }
+}
+
+/// Copia di riserva libera per il file: `.bak` in fondo, numerata se esiste già
+fn backup_path(path: &Path) -> PathBuf {
+    let mut n = 0;
+    loop {
+        let mut backup = OsString::from(path.as_os_str());
+        if n > 0 {
+            backup.push(format!(".{}", n));
+        }
+        backup.push(".bak");
+        let backup = PathBuf::from(backup);
+        if !backup.exists() {
+            return backup;
+        }
+        n += 1;
+    }
+}
+
+#[cfg(test)]
+mod sheet_impl_tests {
+    use chrono::Local;
+    use std::fs;
+    use std::path::PathBuf;
+
+    use crate::app::advancement::AdvancementData;
+    use crate::app::campaign::character_file;
+    use crate::app::format::SheetFormat;
+    use crate::app::honeycomb::HoneycombData;
+    use crate::app::sheet::{Sheet, SheetField};
+    use crate::app::{App, Campaign, PopupType};
+    use crate::test_support::{ActiveScope, TempDir};
+
+    fn app_on_disk(contents: &str) -> App {
+        let mut app = App::new();
+        app.sheet_on_disk = contents.to_string();
+        app.character_base_info.name = "Lia".into();
+        app.honeycomb_nodes[9].text = "Cartografa".into();
+        app
+    }
+
+    const BASE: &str = r#"name = "Lia"
+objective = ""
+nodes = ["", "", "", "", "", "", "", "", "", "Cartografa", "", "", "", "", "", "", "", "", ""]
+misfortunes = ["", "", "", ""]
+misfortunes_red_balls = ["", "", "", ""]
+left_resources = ["", "", "", "", "", "", "", "", "", ""]
+notes = ""
+lessons = ["", "", ""]
+"#;
+
+    #[test]
+    fn test_external_edits_are_reloaded() {
+        let mut app = app_on_disk(BASE);
+        app.sheet_changed_on_disk(BASE.to_string());
+        assert!(app.status_message.is_none());
+
+        let edited = BASE.replace("objective = \"\"", "objective = \"Tornare a casa\"");
+        app.sheet_changed_on_disk(edited.clone());
+        assert_eq!(app.character_base_info.objective, "Tornare a casa");
+        assert_eq!(app.sheet_on_disk, edited);
+        assert_eq!(app.popup, PopupType::None);
+        assert!(app.status_message.is_some());
+
+        // Un file scritto a metà non svuota la scheda
+        app.sheet_changed_on_disk("name = \"Li".to_string());
+        assert_eq!(app.character_base_info.name, "Lia");
+        assert_eq!(app.sheet_on_disk, edited);
+    }
+
+    #[test]
+    fn test_empty_file_is_not_ready() {
+        let mut app = app_on_disk(BASE);
+        app.sheet_changed_on_disk(String::new());
+        assert_eq!(app.character_base_info.name, "Lia");
+        assert_eq!(app.honeycomb_nodes[9].text, "Cartografa");
+        assert_eq!(app.sheet_on_disk, BASE);
+        assert!(app.read_disk_sheet("").is_none());
+    }
+
+    #[test]
+    fn test_half_written_file_is_not_ready() {
+        let mut app = app_on_disk(BASE);
+        // Troncato alla fine di una riga: il TOML è valido ma mancano delle sezioni
+        let half: String = BASE
+            .lines()
+            .take(3)
+            .map(|line| format!("{}\n", line))
+            .collect();
+        let half = half.replace("Lia", "Aria");
+        assert!(app.read_disk_sheet(&half).is_none());
+
+        app.sheet_changed_on_disk(half);
+        assert_eq!(app.character_base_info.name, "Lia");
+        assert_eq!(app.sheet_on_disk, BASE);
+        assert!(app.status_message.is_none());
+    }
+
+    #[test]
+    fn test_external_lessons_are_reloaded() {
+        let mut app = app_on_disk(BASE);
+        let mut disk = Sheet::parse(BASE, SheetFormat::Toml);
+        disk.advancement
+            .learn_lesson(Local::now(), 0, "Ascoltare", None);
+        let contents = SheetFormat::Toml.sheet_document(
+            &disk.info,
+            &HoneycombData::from_nodes(&disk.nodes),
+            &disk.list,
+            &AdvancementData {
+                advancement: disk.advancement.clone(),
+            },
+        );
+
+        app.sheet_changed_on_disk(contents.clone());
+        assert_eq!(app.advancement, disk.advancement);
+        assert_eq!(app.sheet_on_disk, contents);
+    }
+
+    #[test]
+    fn test_conflicts_wait_for_a_choice() {
+        let mut app = app_on_disk(BASE);
+        app.honeycomb_nodes[9].text = "Esploratrice".into();
+        let edited = BASE.replace("Cartografa", "Navigatrice");
+
+        let disk = app.read_disk_sheet(&edited).unwrap();
+        assert!(!app.merge_disk_sheet(edited.clone(), &disk));
+        assert_eq!(app.popup, PopupType::SheetConflict);
+        assert_eq!(app.sheet_conflicts.len(), 1);
+        assert_eq!(
+            app.sheet_conflicts[0].field,
+            SheetField::Node(9, crate::app::NodeField::Title)
+        );
+        assert_eq!(app.honeycomb_nodes[9].text, "Esploratrice");
+        assert_eq!(app.sheet_on_disk, BASE);
+
+        app.keep_disk_sheet();
+        assert_eq!(app.honeycomb_nodes[9].text, "Navigatrice");
+        assert_eq!(app.sheet_on_disk, edited);
+        assert!(app.sheet_conflicts.is_empty());
+        assert_eq!(app.popup, PopupType::None);
+    }
+
+    #[test]
+    fn test_quit_waits_for_conflicts() {
+        let mut app = app_on_disk(BASE);
+        app.honeycomb_nodes[9].text = "Esploratrice".into();
+        let edited = BASE.replace("Cartografa", "Navigatrice");
+        let disk = app.read_disk_sheet(&edited).unwrap();
+        app.merge_disk_sheet(edited, &disk);
+
+        // Rimandata la scelta, non si esce finché non è fatta
+        app.popup = PopupType::None;
+        assert!(!app.ready_to_quit());
+        assert_eq!(app.popup, PopupType::SheetConflict);
+
+        app.sheet_conflicts.clear();
+        assert!(app.ready_to_quit());
+    }
+
+    #[test]
+    fn test_convert_keeps_external_edits_and_old_backups() {
+        let root = TempDir::new("convert");
+        let campaign = Campaign::create(root.path(), "Prova").unwrap();
+        let _scope = ActiveScope::new(&campaign);
+
+        let mut app = App::new();
+        app.character_base_info.name = "Lia".into();
+        app.write_sheet();
+        let toml = app.sheet_file.clone();
+        let old_backup = PathBuf::from(format!("{}.bak", toml.display()));
+        fs::write(&old_backup, "copia precedente").unwrap();
+
+        // Modifica esterna non ancora letta dall'applicazione
+        let mut external = app.clone();
+        external.character_base_info.objective = "Scritto da fuori".into();
+        fs::write(&toml, external.sheet_contents(SheetFormat::Toml)).unwrap();
+
+        app.convert_sheet(SheetFormat::Json).unwrap();
+        assert_eq!(app.character_base_info.objective, "Scritto da fuori");
+        assert_eq!(app.sheet_format(), SheetFormat::Json);
+        assert_eq!(app.sheet_file, character_file());
+        let json = fs::read_to_string(&app.sheet_file).unwrap();
+        assert_eq!(json, app.sheet_on_disk);
+        assert!(json.contains("Scritto da fuori"));
+
+        // La copia di riserva già presente resta intatta
+        assert_eq!(fs::read_to_string(&old_backup).unwrap(), "copia precedente");
+        let backup = PathBuf::from(format!("{}.1.bak", toml.display()));
+        assert!(
+            fs::read_to_string(backup)
+                .unwrap()
+                .contains("Scritto da fuori")
+        );
+        assert!(!toml.exists());
+
+        assert!(app.convert_sheet(SheetFormat::Json).is_err());
+    }
+}
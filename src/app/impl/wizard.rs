@@ -0,0 +1,146 @@
+use super::super::app_state::App;
+use super::super::honeycomb::HoneycombNode;
+use super::super::sheet::Sheet;
+use super::super::types::PopupType;
+use super::super::wizard::CharacterWizard;
+
+impl App {
+    /// Verifica se la scheda è ancora completamente vuota, avanzamento compreso
+    pub fn is_sheet_empty(&self) -> bool {
+        self.sheet().same_as(&Sheet::empty())
+    }
+
+    /// Avvia la creazione guidata, riprendendo la bozza se presente
+    pub fn start_wizard(&mut self) {
+        self.wizard = Some(CharacterWizard::load_draft().unwrap_or_default());
+        self.popup = PopupType::Wizard;
+    }
+
+    /// Chiude la creazione guidata salvando la bozza
+    pub fn close_wizard(&mut self) {
+        if let Some(wizard) = self.wizard.take() {
+            wizard.save_draft();
+        }
+        self.popup = PopupType::None;
+    }
+
+    /// Conferma il passo corrente e, alla fine, crea il personaggio
+    pub fn wizard_confirm(&mut self) {
+        let finished = match self.wizard.as_mut() {
+            Some(wizard) => wizard.advance(),
+            None => return,
+        };
+
+        if finished {
+            if let Some(wizard) = self.wizard.take() {
+                self.apply_wizard(wizard);
+            }
+            self.save_data();
+            CharacterWizard::discard_draft();
+            self.popup = PopupType::None;
+        }
+    }
+
+    /// Sostituisce la scheda del personaggio con i dati della bozza: ciò che
+    /// la creazione guidata non chiede riparte vuoto
+    pub(crate) fn apply_wizard(&mut self, wizard: CharacterWizard) {
+        let mut sheet = Sheet::empty();
+        sheet.info.name = wizard.base_info.name.trim().to_string();
+        sheet.info.objective = wizard.base_info.objective.trim().to_string();
+        sheet.nodes = HoneycombNode::create_honeycomb_layout_with_data(
+            wizard.nodes.iter().map(|t| t.trim().to_string()).collect(),
+        );
+        for (slot, resource) in sheet
+            .list
+            .left_resources
+            .iter_mut()
+            .zip(wizard.resources.iter())
+        {
+            *slot = resource.trim().to_string();
+        }
+        sheet.list.lessons[0] = wizard.lesson.trim().to_string();
+        self.apply_sheet(sheet);
+        // Tratti e sventure in gioco erano del personaggio precedente
+        self.reset();
+    }
+}
+
+#[cfg(test)]
+mod wizard_impl_tests {
+    use chrono::Local;
+
+    use crate::app::{Action, App, CharacterWizard, PopupType};
+
+    #[test]
+    fn test_apply_wizard() {
+        let mut app = App::new();
+        let mut wizard = CharacterWizard::default();
+        wizard.base_info.name = " Aria ".to_string();
+        wizard.base_info.objective = "Ritrovare il fratello".to_string();
+        wizard.nodes[9] = "Esploratrice".to_string();
+        wizard.nodes[4] = "Curiosa".to_string();
+        wizard.resources[0] = "Mappa".to_string();
+        wizard.lesson = "Mai fidarsi del mare".to_string();
+
+        app.apply_wizard(wizard);
+
+        assert_eq!(app.character_base_info.name, "Aria");
+        assert_eq!(app.honeycomb_nodes[9].text, "Esploratrice");
+        assert_eq!(app.honeycomb_nodes[4].text, "Curiosa");
+        assert_eq!(app.honeycomb_nodes.len(), 19);
+        assert_eq!(app.list_data.left_resources[0], "Mappa");
+        assert_eq!(app.list_data.lessons[0], "Mai fidarsi del mare");
+        assert!(!app.is_sheet_empty());
+    }
+
+    #[test]
+    fn test_apply_wizard_replaces_whole_sheet() {
+        let mut app = App::new();
+        app.list_data.notes = "Appunti del vecchio personaggio".to_string();
+        app.list_data.lessons[2] = "Vecchia lezione".to_string();
+        app.advancement
+            .learn_lesson(Local::now(), 2, "Vecchia lezione", None);
+        app.used_traits.push(9);
+
+        let mut wizard = CharacterWizard::default();
+        wizard.base_info.name = "Bruno".to_string();
+        app.apply_wizard(wizard);
+
+        assert_eq!(app.character_base_info.name, "Bruno");
+        assert!(app.list_data.notes.is_empty());
+        assert!(app.list_data.lessons[2].is_empty());
+        assert!(app.advancement.lessons.is_empty());
+        assert!(app.used_traits.is_empty());
+    }
+
+    #[test]
+    fn test_new_character_asks_before_overwriting() {
+        let mut app = App::new();
+        assert!(app.is_sheet_empty());
+        app.run_action(Action::StartWizard).unwrap();
+        assert_eq!(app.popup, PopupType::Wizard);
+
+        let mut app = App::new();
+        app.list_data.notes = "Appunti".to_string();
+        assert!(!app.is_sheet_empty());
+        app.run_action(Action::StartWizard).unwrap();
+        assert_eq!(app.popup, PopupType::Confirm);
+        assert!(app.wizard.is_none());
+
+        app.accept_confirm();
+        assert_eq!(app.popup, PopupType::Wizard);
+        assert!(app.confirm.is_none());
+    }
+
+    #[test]
+    fn test_wizard_confirm_blocks_invalid_step() {
+        let mut app = App::new();
+        app.wizard = Some(CharacterWizard::default());
+        app.popup = PopupType::Wizard;
+
+        app.wizard_confirm();
+
+        assert_eq!(app.popup, PopupType::Wizard);
+        assert!(app.wizard.as_ref().unwrap().error.is_some());
+    }
+}
@@ -0,0 +1,335 @@
+use super::honeycomb::{ARCHETYPE_NODE, HONEYCOMB_SIZE, NodeField, QUALITY_NODES};
+use super::list::ListSection;
+use super::sheet::{Sheet, SheetField};
+
+/// Riga del file importato, con il campo della scheda in cui finisce.
+///
+/// Le colonne sono `campo, valore` e, per i tratti, `descrizione, tag`;
+/// per le sventure la terza colonna è la difficoltà.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportRow {
+    /// Riga nel file, da 1
+    pub line: usize,
+    pub cells: Vec<String>,
+    /// `None` se la riga viene ignorata
+    pub target: Option<SheetField>,
+}
+
+impl ImportRow {
+    /// Nome del campo scritto nel file
+    pub fn key(&self) -> &str {
+        self.cells.first().map_or("", String::as_str)
+    }
+
+    fn cell(&self, idx: usize) -> String {
+        self.cells.get(idx).cloned().unwrap_or_default()
+    }
+
+    /// Valori da scrivere nella scheda, già accorciati alla lunghezza dei campi
+    pub fn values(&self) -> Vec<(SheetField, String)> {
+        let Some(target) = self.target else {
+            return Vec::new();
+        };
+        let mut values = vec![(target, self.cell(1))];
+        match target {
+            SheetField::Node(idx, _) => {
+                values.push((SheetField::Node(idx, NodeField::Description), self.cell(2)));
+                values.push((
+                    SheetField::Node(idx, NodeField::Tags),
+                    self.cell(3).replace(';', ","),
+                ));
+            }
+            SheetField::List(ListSection::Misfortunes, idx) => values.push((
+                SheetField::List(ListSection::MisfortunesDifficult, idx),
+                self.cell(2),
+            )),
+            _ => {}
+        }
+        values
+            .into_iter()
+            .map(|(field, value)| (field, value.chars().take(field.length()).collect()))
+            .collect()
+    }
+
+    /// Qualche valore supera la lunghezza del campo e verrà accorciato
+    pub fn truncated(&self) -> bool {
+        self.values()
+            .iter()
+            .zip(1..)
+            .any(|((_, value), idx)| value.chars().count() < self.cell(idx).chars().count())
+    }
+}
+
+/// Contenuto di un file CSV da importare come scheda
+#[derive(Debug, Clone)]
+pub struct SheetImport {
+    pub path: String,
+    pub rows: Vec<ImportRow>,
+}
+
+impl SheetImport {
+    /// Legge il CSV; il separatore (`,`, `;` o tabulazione) è quello più usato nella prima riga
+    pub fn parse(path: impl Into<String>, contents: &str) -> Result<Self, String> {
+        let first = contents
+            .lines()
+            .find(|l| !l.trim().is_empty())
+            .unwrap_or("");
+        // A parità vince la virgola, l'ultima
+        let delimiter = [b'\t', b';', b',']
+            .into_iter()
+            .max_by_key(|&d| first.bytes().filter(|&b| b == d).count())
+            .unwrap_or(b',');
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .delimiter(delimiter)
+            .from_reader(contents.as_bytes());
+
+        let mut rows = Vec::new();
+        for record in reader.records() {
+            let record = record.map_err(|e| format!("CSV non valido: {}", e))?;
+            let line = record.position().map_or(0, |p| p.line() as usize);
+            let cells: Vec<String> = record.iter().map(|c| c.trim().to_string()).collect();
+            if cells.iter().all(String::is_empty) {
+                continue;
+            }
+            // Intestazione facoltativa
+            if rows.is_empty() && matches!(normalize(&cells[0]).as_str(), "campo" | "field") {
+                continue;
+            }
+            let target = parse_key(&cells[0]);
+            rows.push(ImportRow {
+                line,
+                cells,
+                target,
+            });
+        }
+
+        if rows.is_empty() {
+            return Err("Il file non contiene righe da importare".into());
+        }
+        Ok(SheetImport {
+            path: path.into(),
+            rows,
+        })
+    }
+
+    /// Scheda risultante; i campi assenti dal file restano vuoti
+    pub fn sheet(&self) -> Sheet {
+        let mut sheet = Sheet::empty();
+        for row in &self.rows {
+            for (field, value) in row.values() {
+                sheet.set(field, value);
+            }
+        }
+        sheet
+    }
+
+    /// Righe che finiscono in un campo
+    pub fn mapped(&self) -> usize {
+        self.rows.iter().filter(|r| r.target.is_some()).count()
+    }
+
+    /// La riga verrà sovrascritta da una successiva con lo stesso campo
+    pub fn overridden(&self, row: usize) -> bool {
+        let target = self.rows[row].target;
+        target.is_some() && self.rows[row + 1..].iter().any(|r| r.target == target)
+    }
+
+    /// Passa al campo precedente o successivo per la riga, ignorarla compreso
+    pub fn cycle_target(&mut self, row: usize, forward: bool) {
+        let mut options: Vec<Option<SheetField>> = vec![None];
+        options.extend(import_targets().into_iter().map(Some));
+        let Some(row) = self.rows.get_mut(row) else {
+            return;
+        };
+        let pos = options.iter().position(|t| *t == row.target).unwrap_or(0);
+        let next = if forward {
+            (pos + 1) % options.len()
+        } else {
+            (pos + options.len() - 1) % options.len()
+        };
+        row.target = options[next];
+    }
+}
+
+/// Posizioni della griglia delle Abilità, nell'ordine della scheda
+fn ability_nodes() -> Vec<usize> {
+    (0..HONEYCOMB_SIZE)
+        .filter(|i| *i != ARCHETYPE_NODE && !QUALITY_NODES.contains(i))
+        .collect()
+}
+
+/// Campi a cui può corrispondere una riga, nell'ordine dello schema
+pub fn import_targets() -> Vec<SheetField> {
+    use ListSection::*;
+    let mut targets = vec![
+        SheetField::Name,
+        SheetField::Objective,
+        SheetField::Node(ARCHETYPE_NODE, NodeField::Title),
+    ];
+    let nodes = QUALITY_NODES.into_iter().chain(ability_nodes());
+    targets.extend(nodes.map(|idx| SheetField::Node(idx, NodeField::Title)));
+    targets.extend((0..4).map(|idx| SheetField::List(Misfortunes, idx)));
+    targets.extend((0..10).map(|idx| SheetField::List(LxResources, idx)));
+    targets.extend((0..3).map(|idx| SheetField::List(Lessons, idx)));
+    targets.push(SheetField::List(Notes, 0));
+    targets
+}
+
+/// Nome del campo nello schema del CSV, es. `Qualità 2`
+pub fn target_key(field: SheetField) -> String {
+    use ListSection::*;
+    let ordinal = |list: &[usize], idx: usize| list.iter().position(|&i| i == idx).unwrap_or(0) + 1;
+    match field {
+        SheetField::Node(ARCHETYPE_NODE, _) => "Archetipo".to_string(),
+        SheetField::Node(idx, _) if QUALITY_NODES.contains(&idx) => {
+            format!("Qualità {}", ordinal(&QUALITY_NODES, idx))
+        }
+        SheetField::Node(idx, _) => format!("Abilità {}", ordinal(&ability_nodes(), idx)),
+        SheetField::List(Misfortunes | MisfortunesDifficult, idx) => {
+            format!("Sventura {}", idx + 1)
+        }
+        field => field.label(),
+    }
+}
+
+/// Minuscolo, senza accenti né spazi doppi
+fn normalize(text: &str) -> String {
+    text.trim()
+        .trim_end_matches(':')
+        .to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'à' | 'á' => 'a',
+            'è' | 'é' => 'e',
+            'ì' | 'í' => 'i',
+            'ò' | 'ó' => 'o',
+            'ù' | 'ú' => 'u',
+            c => c,
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Campo indicato dalla prima colonna, in italiano o in inglese
+fn parse_key(key: &str) -> Option<SheetField> {
+    use ListSection::*;
+    let key = normalize(key);
+    let (name, number) = match key.rsplit_once(' ') {
+        Some((name, n)) if n.chars().all(|c| c.is_ascii_digit()) => (name, n.parse().ok()),
+        _ => (key.as_str(), None),
+    };
+    // Posizione da 1 in una lista lunga `len`
+    let nth = |len: usize| number.filter(|n| (1..=len).contains(n)).map(|n| n - 1);
+
+    match name {
+        "nome" | "name" => Some(SheetField::Name),
+        "obiettivo" | "objective" => Some(SheetField::Objective),
+        "archetipo" | "archetype" => Some(SheetField::Node(ARCHETYPE_NODE, NodeField::Title)),
+        "qualita" | "quality" => {
+            nth(QUALITY_NODES.len()).map(|i| SheetField::Node(QUALITY_NODES[i], NodeField::Title))
+        }
+        "abilita" | "ability" | "skill" => {
+            let nodes = ability_nodes();
+            nth(nodes.len()).map(|i| SheetField::Node(nodes[i], NodeField::Title))
+        }
+        "sventura" | "misfortune" => nth(4).map(|i| SheetField::List(Misfortunes, i)),
+        "risorsa" | "resource" => nth(10).map(|i| SheetField::List(LxResources, i)),
+        "lezione" | "lesson" => nth(3).map(|i| SheetField::List(Lessons, i)),
+        "note" | "notes" if number.is_none() => Some(SheetField::List(Notes, 0)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod import_tests {
+    use crate::app::import::*;
+
+    const CSV: &str = "\
+Campo,Valore,Descrizione,Tag
+Nome,Lia
+Obiettivo,Ritrovare il fratello
+Archetipo,Cartografa,Disegna mappe di ogni costa,\"mare, viaggio\"
+Qualità 1,Curiosa
+Skill 2,Nuotare
+Sventura 1,Ferita alla gamba,2
+
+Risorsa 1,Corda
+Lezione 1,Mai fidarsi dei pirati
+Note,\"Ha un debito, con il porto\"
+Colore preferito,Blu
+";
+
+    #[test]
+    fn test_schema_rows_are_mapped() {
+        let import = SheetImport::parse("pg.csv", CSV).unwrap();
+        assert_eq!(import.rows.len(), 10);
+        assert_eq!(import.mapped(), 9);
+        assert_eq!(import.rows[9].key(), "Colore preferito");
+        assert_eq!(import.rows[9].target, None);
+        assert_eq!(import.rows[3].line, 5);
+
+        let sheet = import.sheet();
+        assert_eq!(sheet.info.name, "Lia");
+        let archetype = &sheet.nodes[ARCHETYPE_NODE];
+        assert_eq!(archetype.text, "Cartografa");
+        assert_eq!(archetype.description, "Disegna mappe di ogni costa");
+        assert_eq!(archetype.tags, vec!["mare", "viaggio"]);
+        assert_eq!(sheet.nodes[QUALITY_NODES[0]].text, "Curiosa");
+        assert_eq!(sheet.nodes[ability_nodes()[1]].text, "Nuotare");
+        assert_eq!(sheet.list.misfortunes[0], "Ferita alla gamba");
+        assert_eq!(sheet.list.misfortunes_red_balls[0], "2");
+        assert_eq!(sheet.list.left_resources[0], "Corda");
+        assert_eq!(sheet.list.lessons[0], "Mai fidarsi dei pirati");
+        assert_eq!(sheet.list.notes, "Ha un debito, con il porto");
+    }
+
+    #[test]
+    fn test_spreadsheet_separators() {
+        let import = SheetImport::parse("pg.csv", "nome;Lia\nQUALITA  2:;Tenace\n").unwrap();
+        assert_eq!(import.mapped(), 2);
+        assert_eq!(import.sheet().nodes[QUALITY_NODES[1]].text, "Tenace");
+
+        let import = SheetImport::parse("pg.tsv", "Name\tLia\nLesson 3\tAscoltare\n").unwrap();
+        assert_eq!(import.sheet().list.lessons[2], "Ascoltare");
+
+        // Fuori dalla scheda
+        assert_eq!(parse_key("Sventura 5"), None);
+        assert_eq!(parse_key("Abilità 0"), None);
+        assert!(SheetImport::parse("vuoto.csv", "\n ,\n").is_err());
+    }
+
+    #[test]
+    fn test_keys_round_trip() {
+        for target in import_targets() {
+            assert_eq!(parse_key(&target_key(target)), Some(target));
+        }
+        assert_eq!(import_targets().len(), 3 + 6 + 12 + 4 + 10 + 3 + 1);
+    }
+
+    #[test]
+    fn test_mapping_can_be_changed() {
+        let long = "x".repeat(60);
+        let csv = format!("Nome,Lia\nNome,{}\nAltro,Mare\n", long);
+        let mut import = SheetImport::parse("pg.csv", &csv).unwrap();
+        assert!(import.overridden(0));
+        assert!(!import.overridden(1));
+        assert!(import.rows[1].truncated());
+        assert_eq!(import.sheet().info.name.chars().count(), 50);
+
+        import.cycle_target(1, true);
+        assert_eq!(import.rows[1].target, Some(SheetField::Objective));
+        import.cycle_target(2, false);
+        assert_eq!(
+            import.rows[2].target,
+            Some(SheetField::List(ListSection::Notes, 0))
+        );
+        import.cycle_target(2, true);
+        assert_eq!(import.rows[2].target, None);
+        assert!(!import.overridden(0));
+        assert_eq!(import.sheet().info.name, "Lia");
+    }
+}
@@ -0,0 +1,428 @@
+use chrono::{DateTime, Local, TimeDelta};
+use serde::de::IgnoredAny;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::borrow::Cow;
+use std::fs;
+
+use super::advancement::{LessonRecord, SheetChange};
+use super::campaign::data_file;
+use super::history::DrawHistory;
+use super::types::BallType;
+
+const DATA_FILE: &str = "sessions.toml";
+/// Archivio delle prove fatte durante le sessioni
+const DRAWS_FILE: &str = "draws.toml";
+
+/// Riepilogo di fine sessione
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct Recap {
+    /// Prove affrontate
+    pub challenges: usize,
+    /// Prove con almeno un successo
+    pub successes: usize,
+    /// Complicazioni, cioè token rossi pescati
+    pub complications: usize,
+    /// Sventure annotate sulla scheda durante la sessione
+    pub misfortunes: Vec<String>,
+    /// Lezioni apprese durante la sessione
+    pub lessons: Vec<String>,
+}
+
+/// Sessione di gioco, con le prove e le modifiche alla scheda avvenute durante
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GameSession {
+    pub title: String,
+    pub started: DateTime<Local>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ended: Option<DateTime<Local>>,
+    #[serde(default)]
+    pub participants: Vec<String>,
+    /// Identificativi delle prove della sessione, conservate nell'archivio del diario
+    #[serde(default, deserialize_with = "deserialize_draw_refs")]
+    pub draws: Vec<u64>,
+    #[serde(default)]
+    pub changes: Vec<SheetChange>,
+    #[serde(default)]
+    pub lessons: Vec<LessonRecord>,
+    /// Riepilogo generato alla chiusura
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recap: Option<Recap>,
+}
+
+impl GameSession {
+    /// Durata della sessione, fino a `now` se è ancora in corso
+    pub fn duration(&self, now: DateTime<Local>) -> TimeDelta {
+        self.ended.unwrap_or(now) - self.started
+    }
+
+    /// Aggiunge un partecipante, se non è già presente
+    pub fn add_participant(&mut self, name: &str) {
+        let name = name.trim();
+        if !name.is_empty() && !self.participants.iter().any(|p| p == name) {
+            self.participants.push(name.to_string());
+        }
+    }
+
+    /// Calcola il riepilogo delle prove indicate e delle modifiche registrate
+    fn recap(&self, draws: &[&DrawHistory]) -> Recap {
+        let drawn = |entry: &DrawHistory| {
+            entry
+                .first_draw
+                .iter()
+                .chain(entry.risk_draw.iter())
+                .copied()
+                .collect::<Vec<_>>()
+        };
+        Recap {
+            challenges: draws.len(),
+            successes: draws
+                .iter()
+                .filter(|entry| drawn(entry).contains(&BallType::White))
+                .count(),
+            complications: draws
+                .iter()
+                .map(|entry| drawn(entry).iter().filter(|b| **b == BallType::Red).count())
+                .sum(),
+            misfortunes: self
+                .changes
+                .iter()
+                .filter(|c| c.field.starts_with("Sventura ") && !c.after.is_empty())
+                .map(|c| c.after.clone())
+                .collect(),
+            lessons: self.lessons.iter().map(|l| l.text.clone()).collect(),
+        }
+    }
+}
+
+/// Durata leggibile, es. `2h 05m`
+pub fn format_duration(duration: TimeDelta) -> String {
+    let minutes = duration.num_minutes().max(0);
+    format!("{}h {:02}m", minutes / 60, minutes % 60)
+}
+
+/// Prova di una sessione come salvata nel diario
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DrawRef {
+    Id(u64),
+    /// Vecchi diari, che copiavano la prova intera: la recupera `load_journal`
+    Entry(IgnoredAny),
+}
+
+/// Legge gli identificativi delle prove di una sessione, saltando quelle
+/// copiate per intero dai vecchi diari
+fn deserialize_draw_refs<'de, D>(deserializer: D) -> Result<Vec<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let refs = Vec::<DrawRef>::deserialize(deserializer)?;
+    Ok(refs
+        .into_iter()
+        .filter_map(|draw| match draw {
+            DrawRef::Id(id) => Some(id),
+            DrawRef::Entry(_) => None,
+        })
+        .collect())
+}
+
+/// Prova dell'archivio, con l'identificativo usato dalle sessioni
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ArchivedDraw {
+    pub id: u64,
+    #[serde(flatten)]
+    pub entry: DrawHistory,
+}
+
+/// Struttura per serializzazione/deserializzazione dell'archivio delle prove
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct DrawsData<'a> {
+    #[serde(default)]
+    draws: Cow<'a, [ArchivedDraw]>,
+}
+
+/// Sessione di un vecchio diario, con le prove copiate per intero
+#[derive(Deserialize)]
+struct LegacySession {
+    #[serde(default)]
+    draws: Vec<DrawHistory>,
+}
+
+/// Vecchio diario con le prove copiate dentro le sessioni, da spostare nell'archivio
+#[derive(Deserialize)]
+struct LegacyJournal {
+    #[serde(default)]
+    sessions: Vec<LegacySession>,
+}
+
+/// Diario delle sessioni di gioco
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct Journal {
+    #[serde(default)]
+    pub sessions: Vec<GameSession>,
+    /// Prove fatte durante le sessioni, salvate a parte in `draws.toml`
+    #[serde(skip)]
+    pub draws: Vec<ArchivedDraw>,
+}
+
+impl Journal {
+    /// Sessione in corso, se presente
+    pub fn active(&self) -> Option<&GameSession> {
+        self.sessions.last().filter(|s| s.ended.is_none())
+    }
+
+    pub fn active_mut(&mut self) -> Option<&mut GameSession> {
+        self.sessions.last_mut().filter(|s| s.ended.is_none())
+    }
+
+    /// Apre una nuova sessione; ne può essere attiva una sola alla volta
+    pub fn start(
+        &mut self,
+        title: &str,
+        now: DateTime<Local>,
+        participants: &[String],
+    ) -> Result<(), String> {
+        if self.active().is_some() {
+            return Err("C'è già una sessione in corso".to_string());
+        }
+        let title = match title.trim() {
+            "" => format!("Sessione {}", self.sessions.len() + 1),
+            title => title.to_string(),
+        };
+        let mut session = GameSession {
+            title,
+            started: now,
+            ended: None,
+            participants: Vec::new(),
+            draws: Vec::new(),
+            changes: Vec::new(),
+            lessons: Vec::new(),
+            recap: None,
+        };
+        for name in participants {
+            session.add_participant(name);
+        }
+        self.sessions.push(session);
+        Ok(())
+    }
+
+    /// Chiude la sessione in corso e ne genera il riepilogo
+    pub fn end(&mut self, now: DateTime<Local>) -> Option<&GameSession> {
+        let recap = self.recap(self.active()?);
+        let session = self.active_mut()?;
+        session.ended = Some(now);
+        session.recap = Some(recap);
+        self.sessions.last()
+    }
+
+    /// Registra una prova nella sessione in corso, se presente
+    pub fn add_draw(&mut self, entry: &DrawHistory) -> bool {
+        let id = self.next_draw_id();
+        let Some(session) = self.active_mut() else {
+            return false;
+        };
+        session.add_participant(&entry.player);
+        session.draws.push(id);
+        self.draws.push(ArchivedDraw {
+            id,
+            entry: entry.clone(),
+        });
+        true
+    }
+
+    /// Identificativo libero per la prossima prova dell'archivio
+    fn next_draw_id(&self) -> u64 {
+        self.draws.iter().map(|d| d.id + 1).max().unwrap_or(1)
+    }
+
+    /// Prove di una sessione, recuperate dall'archivio
+    pub fn session_draws(&self, session: &GameSession) -> Vec<&DrawHistory> {
+        session
+            .draws
+            .iter()
+            .filter_map(|id| self.draws.iter().find(|d| d.id == *id))
+            .map(|d| &d.entry)
+            .collect()
+    }
+
+    /// Riepilogo di una sessione, parziale se è ancora in corso
+    pub fn recap(&self, session: &GameSession) -> Recap {
+        session.recap(&self.session_draws(session))
+    }
+
+    pub(crate) fn load_journal() -> Self {
+        let mut journal = Journal::default();
+        let contents = fs::read_to_string(data_file(DATA_FILE)).unwrap_or_default();
+        if let Ok(data) = toml::from_str::<Journal>(&contents) {
+            journal = data;
+        }
+        if let Ok(contents) = fs::read_to_string(data_file(DRAWS_FILE))
+            && let Ok(data) = toml::from_str::<DrawsData>(&contents)
+        {
+            journal.draws = data.draws.into_owned();
+        }
+
+        // I vecchi diari copiavano le prove nelle sessioni: passano nell'archivio,
+        // ognuna con il proprio identificativo anche se l'orario coincide
+        if let Ok(legacy) = toml::from_str::<LegacyJournal>(&contents) {
+            let before = journal.draws.len();
+            for (pos, old) in legacy.sessions.into_iter().enumerate() {
+                for entry in old.draws {
+                    let id = journal.next_draw_id();
+                    journal.draws.push(ArchivedDraw { id, entry });
+                    if let Some(session) = journal.sessions.get_mut(pos) {
+                        session.draws.push(id);
+                    }
+                }
+            }
+            if journal.draws.len() > before {
+                journal.save_journal();
+            }
+        }
+        journal
+    }
+
+    pub(crate) fn save_journal(&self) {
+        // Prima l'archivio, così le sessioni non puntano mai a prove mancanti
+        let draws = DrawsData {
+            draws: Cow::Borrowed(&self.draws),
+        };
+        if let Ok(string) = toml::to_string_pretty(&draws) {
+            let _ = fs::write(data_file(DRAWS_FILE), string);
+        }
+        if let Ok(string) = toml::to_string_pretty(self) {
+            let _ = fs::write(data_file(DATA_FILE), string);
+        }
+    }
+}
+
+#[cfg(test)]
+mod journal_tests {
+    use std::fs;
+
+    use crate::app::Campaign;
+    use crate::app::journal::*;
+    use crate::test_support::{ActiveScope, TempDir};
+
+    /// Prova fatta `seconds` secondi dopo adesso, per avere orari distinti
+    fn draw(seconds: i64, first: Vec<BallType>, risk: Vec<BallType>) -> DrawHistory {
+        DrawHistory {
+            time: Local::now() + TimeDelta::seconds(seconds),
+            first_draw: first,
+            risked: !risk.is_empty(),
+            risk_draw: risk,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_only_one_active_session() {
+        let mut journal = Journal::default();
+        let now = Local::now();
+        journal.start("", now, &["Aria".into()]).unwrap();
+        assert_eq!(journal.active().unwrap().title, "Sessione 1");
+        assert!(journal.start("Altra", now, &[]).is_err());
+
+        journal.end(now + TimeDelta::minutes(125)).unwrap();
+        assert!(journal.active().is_none());
+        assert_eq!(
+            format_duration(journal.sessions[0].duration(Local::now())),
+            "2h 05m"
+        );
+        assert!(journal.start("Altra", now, &[]).is_ok());
+    }
+
+    #[test]
+    fn test_recap() {
+        use BallType::*;
+        let mut journal = Journal::default();
+        assert!(!journal.add_draw(&draw(0, vec![White], vec![])));
+        journal.start("Il porto", Local::now(), &[]).unwrap();
+        journal.add_draw(&draw(1, vec![White, Red], vec![]));
+        journal.add_draw(&draw(2, vec![Red], vec![Red, Red, Red, White]));
+        journal.add_draw(&draw(3, vec![Red], vec![]));
+        journal.active_mut().unwrap().changes.push(SheetChange {
+            time: Local::now(),
+            field: "Sventura 1".into(),
+            before: String::new(),
+            after: "Ferito".into(),
+            lesson: None,
+        });
+
+        let recap = journal.end(Local::now()).unwrap().recap.clone().unwrap();
+        assert_eq!(recap.challenges, 3);
+        assert_eq!(recap.successes, 2);
+        assert_eq!(recap.complications, 6);
+        assert_eq!(recap.misfortunes, vec!["Ferito".to_string()]);
+    }
+
+    #[test]
+    fn test_sessions_store_draw_ids_only() {
+        let root = TempDir::new("journal");
+        let campaign = Campaign::create(root.path(), "Prova").unwrap();
+        let _scope = ActiveScope::new(&campaign);
+
+        let mut journal = Journal::default();
+        journal
+            .start("Il porto", Local::now(), &["Aria".into()])
+            .unwrap();
+        // Due prove nello stesso istante restano distinte
+        let entry = draw(0, vec![BallType::White], vec![]);
+        journal.add_draw(&entry);
+        journal.add_draw(&DrawHistory {
+            description: "Fuggire".into(),
+            ..entry
+        });
+        journal.end(Local::now());
+        journal.save_journal();
+
+        let sessions = fs::read_to_string(data_file(DATA_FILE)).unwrap();
+        assert!(!sessions.contains("first_draw"));
+        let back = Journal::load_journal();
+        assert_eq!(back, journal);
+        let draws = back.session_draws(&back.sessions[0]);
+        assert_eq!(draws.len(), 2);
+        assert_eq!(draws[1].description, "Fuggire");
+    }
+
+    #[test]
+    fn test_legacy_session_draws_move_to_archive() {
+        let root = TempDir::new("journal-legacy");
+        let campaign = Campaign::create(root.path(), "Prova").unwrap();
+        let _scope = ActiveScope::new(&campaign);
+
+        // Vecchio diario: prove intere dentro la sessione, orari testuali al
+        // secondo, per cui due prove ravvicinate hanno lo stesso orario
+        let legacy_draw = |description: &str| {
+            let entry = DrawHistory {
+                description: description.into(),
+                ..draw(0, vec![BallType::White], vec![])
+            };
+            toml::to_string(&entry)
+                .unwrap()
+                .lines()
+                .map(|line| match line.strip_prefix("time = ") {
+                    Some(_) => "time = \"Monday  3 March 2025, 18:04:05\"".to_string(),
+                    None => line.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        let legacy = format!(
+            "[[sessions]]\ntitle = \"Il porto\"\nstarted = \"2025-03-03T18:00:00+01:00\"\n\n[[sessions.draws]]\n{}\n\n[[sessions.draws]]\n{}\n",
+            legacy_draw("Scalare"),
+            legacy_draw("Fuggire")
+        );
+        fs::write(data_file(DATA_FILE), legacy).unwrap();
+
+        let journal = Journal::load_journal();
+        let draws = journal.session_draws(&journal.sessions[0]);
+        assert_eq!(draws.len(), 2);
+        assert_eq!(draws[0].description, "Scalare");
+        assert_eq!(draws[1].description, "Fuggire");
+
+        // Il diario viene riscritto con i soli riferimenti, senza perdere la prova
+        let sessions = fs::read_to_string(data_file(DATA_FILE)).unwrap();
+        assert!(!sessions.contains("Scalare"));
+        assert_eq!(Journal::load_journal(), journal);
+    }
+}
@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::fs;
 
-const DATA_FILE: &str = "character_sheet.toml";
+use super::campaign::character_file;
+use super::format::SheetFormat;
 
 /// Sezione della lista attualmente selezionata
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -45,18 +46,6 @@ impl ListSection {
         }
     }
 
-    #[allow(dead_code)]
-    pub fn idx(&self) -> usize {
-        use ListSection::*;
-        match *self {
-            Misfortunes => 0,
-            MisfortunesDifficult => 1,
-            LxResources => 2,
-            Notes => 3,
-            Lessons => 4,
-        }
-    }
-
     pub fn length(&self) -> usize {
         use ListSection::*;
         match *self {
@@ -81,12 +70,15 @@ pub struct ListData {
 
 impl ListData {
     pub(crate) fn load_list_data() -> Self {
-        if let Ok(contents) = fs::read_to_string(DATA_FILE)
-            && let Ok(data) = toml::from_str::<ListData>(&contents)
-        {
-            return data;
-        }
-        ListData::default()
+        let path = character_file();
+        fs::read_to_string(&path)
+            .map(|contents| Self::from_contents(&contents, SheetFormat::from_path(&path)))
+            .unwrap_or_default()
+    }
+
+    /// Liste lette dal contenuto del file della scheda
+    pub(crate) fn from_contents(contents: &str, format: SheetFormat) -> Self {
+        format.parse(contents).unwrap_or_default()
     }
 }
 
@@ -0,0 +1,234 @@
+use chrono::NaiveDate;
+
+use super::history::DrawHistory;
+
+/// Formati accettati per le date del filtro
+const DATE_FORMATS: [&str; 2] = ["%d/%m/%Y", "%Y-%m-%d"];
+
+/// Campo selezionato nella finestra dei filtri del log
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LogFilterField {
+    Query,
+    Session,
+    From,
+    To,
+    Trait,
+    Misfortune,
+    Risked,
+    Confused,
+    Adrenalined,
+}
+
+impl LogFilterField {
+    pub const ALL: [LogFilterField; 9] = [
+        LogFilterField::Query,
+        LogFilterField::Session,
+        LogFilterField::From,
+        LogFilterField::To,
+        LogFilterField::Trait,
+        LogFilterField::Misfortune,
+        LogFilterField::Risked,
+        LogFilterField::Confused,
+        LogFilterField::Adrenalined,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LogFilterField::Query => "Testo",
+            LogFilterField::Session => "Sessione",
+            LogFilterField::From => "Dal",
+            LogFilterField::To => "Al",
+            LogFilterField::Trait => "Tratto",
+            LogFilterField::Misfortune => "Sventura",
+            LogFilterField::Risked => "Rischiato",
+            LogFilterField::Confused => "Confusione",
+            LogFilterField::Adrenalined => "Adrenalina",
+        }
+    }
+
+    /// Campi in cui si digita del testo
+    pub fn is_text(&self) -> bool {
+        matches!(
+            self,
+            LogFilterField::Query
+                | LogFilterField::From
+                | LogFilterField::To
+                | LogFilterField::Trait
+                | LogFilterField::Misfortune
+        )
+    }
+}
+
+/// Filtri e ricerca applicati al tab Log
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LogFilter {
+    /// Testo cercato in descrizione, giocatore, tratti e sventure
+    pub query: String,
+    /// Sessione del diario da mostrare al posto dello storico
+    pub session: Option<usize>,
+    /// Primo giorno incluso, come digitato
+    pub from: String,
+    /// Ultimo giorno incluso, come digitato
+    pub to: String,
+    pub trait_name: String,
+    pub misfortune: String,
+    pub risked: Option<bool>,
+    pub confused: Option<bool>,
+    pub adrenalined: Option<bool>,
+}
+
+impl LogFilter {
+    /// Vero se almeno un filtro è impostato
+    pub fn is_active(&self) -> bool {
+        *self != LogFilter::default()
+    }
+
+    /// Testo del campo, per i campi testuali
+    pub fn text_mut(&mut self, field: LogFilterField) -> Option<&mut String> {
+        match field {
+            LogFilterField::Query => Some(&mut self.query),
+            LogFilterField::From => Some(&mut self.from),
+            LogFilterField::To => Some(&mut self.to),
+            LogFilterField::Trait => Some(&mut self.trait_name),
+            LogFilterField::Misfortune => Some(&mut self.misfortune),
+            _ => None,
+        }
+    }
+
+    /// Valore dei campi sì/no/indifferente
+    pub fn flag_mut(&mut self, field: LogFilterField) -> Option<&mut Option<bool>> {
+        match field {
+            LogFilterField::Risked => Some(&mut self.risked),
+            LogFilterField::Confused => Some(&mut self.confused),
+            LogFilterField::Adrenalined => Some(&mut self.adrenalined),
+            _ => None,
+        }
+    }
+
+    /// Vero se la prova soddisfa tutti i filtri impostati
+    pub fn matches(&self, entry: &DrawHistory) -> bool {
+        let day = entry.time.date_naive();
+        if parse_date(&self.from).is_some_and(|from| day < from)
+            || parse_date(&self.to).is_some_and(|to| day > to)
+        {
+            return false;
+        }
+        if !contains_any(&entry.trait_names, &self.trait_name)
+            || !contains_any(&entry.misfortune_names, &self.misfortune)
+        {
+            return false;
+        }
+        let flag = |filter: Option<bool>, value: bool| filter.is_none_or(|f| f == value);
+        if !flag(self.risked, entry.risked)
+            || !flag(self.confused, entry.confused)
+            || !flag(self.adrenalined, entry.adrenalined)
+        {
+            return false;
+        }
+
+        let query = self.query.trim().to_lowercase();
+        query.is_empty()
+            || [&entry.description, &entry.player]
+                .into_iter()
+                .chain(entry.trait_names.iter())
+                .chain(entry.misfortune_names.iter())
+                .any(|text| text.to_lowercase().contains(&query))
+    }
+}
+
+/// Data digitata nel filtro; `None` se vuota o non valida
+pub fn parse_date(text: &str) -> Option<NaiveDate> {
+    let text = text.trim();
+    DATE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(text, format).ok())
+}
+
+/// Vero se il filtro è vuoto o compare in uno dei nomi
+fn contains_any(names: &[String], filter: &str) -> bool {
+    let filter = filter.trim().to_lowercase();
+    filter.is_empty() || names.iter().any(|n| n.to_lowercase().contains(&filter))
+}
+
+/// Valore successivo dei campi sì/no/indifferente
+pub fn cycle_flag(flag: Option<bool>) -> Option<bool> {
+    match flag {
+        None => Some(true),
+        Some(true) => Some(false),
+        Some(false) => None,
+    }
+}
+
+#[cfg(test)]
+mod log_filter_tests {
+    use chrono::{Local, TimeZone};
+
+    use crate::app::log_filter::*;
+
+    fn entry(day: u32, traits: &[&str], risked: bool) -> DrawHistory {
+        DrawHistory {
+            time: Local.with_ymd_and_hms(2025, 3, day, 20, 0, 0).unwrap(),
+            description: "Scalare le mura".into(),
+            trait_names: traits.iter().map(|t| t.to_string()).collect(),
+            risked,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_empty_filter_matches_everything() {
+        let filter = LogFilter::default();
+        assert!(!filter.is_active());
+        assert!(filter.matches(&entry(1, &[], false)));
+    }
+
+    #[test]
+    fn test_date_range() {
+        let filter = LogFilter {
+            from: "02/03/2025".into(),
+            to: "2025-03-04".into(),
+            ..Default::default()
+        };
+        assert!(!filter.matches(&entry(1, &[], false)));
+        assert!(filter.matches(&entry(2, &[], false)));
+        assert!(filter.matches(&entry(4, &[], false)));
+        assert!(!filter.matches(&entry(5, &[], false)));
+
+        // Una data non valida non filtra
+        let filter = LogFilter {
+            from: "marzo".into(),
+            ..Default::default()
+        };
+        assert!(filter.matches(&entry(1, &[], false)));
+    }
+
+    #[test]
+    fn test_traits_flags_and_query() {
+        let filter = LogFilter {
+            trait_name: "agile".into(),
+            risked: Some(true),
+            ..Default::default()
+        };
+        assert!(filter.matches(&entry(1, &["Agile come un gatto"], true)));
+        assert!(!filter.matches(&entry(1, &["Agile come un gatto"], false)));
+        assert!(!filter.matches(&entry(1, &["Forte"], true)));
+
+        let filter = LogFilter {
+            query: "MURA".into(),
+            ..Default::default()
+        };
+        assert!(filter.matches(&entry(1, &[], false)));
+        let filter = LogFilter {
+            query: "porto".into(),
+            ..Default::default()
+        };
+        assert!(!filter.matches(&entry(1, &[], false)));
+    }
+
+    #[test]
+    fn test_cycle_flag() {
+        assert_eq!(cycle_flag(None), Some(true));
+        assert_eq!(cycle_flag(Some(true)), Some(false));
+        assert_eq!(cycle_flag(Some(false)), None);
+    }
+}
@@ -1,18 +1,52 @@
 // Moduli interni
+mod action;
+mod advancement;
 mod app_state;
+mod bag;
+mod campaign;
 mod character;
+mod context_menu;
+mod export;
+mod format;
 mod history;
+mod hit;
 mod honeycomb;
+mod import;
+mod journal;
 mod list;
+mod log_filter;
+mod narrator;
+mod palette;
+mod proof;
+mod quick_entry;
+mod scroll;
+mod search;
+mod sheet;
 mod types;
+mod wizard;
 
 // Modulo implementazioni (suddiviso in sottomoduli)
 #[path = "impl/mod.rs"]
 mod app_impl;
 
 // Re-export dei tipi pubblici
+pub use action::Action;
 #[allow(unused_imports)]
 pub use app_state::{App, MAX_DRAW, MAX_TOKEN, MIN_DRAW};
+pub(crate) use campaign::character_file;
+#[cfg(test)]
+pub(crate) use campaign::deactivate;
+pub use campaign::{CAMPAIGNS_DIR, Campaign, CampaignFocus, CampaignInput};
 pub use character::CharacterSection;
+pub use history::DrawHistory;
+pub(crate) use history::format_time;
+pub use hit::{HitId, Z_BASE, Z_MENU, Z_POPUP};
+pub use honeycomb::{HoneycombNode, NodeField, TraitKind};
+pub use import::target_key;
+pub use journal::{GameSession, format_duration};
 pub use list::{ListSection, get_section_type};
-pub use types::{BallType, FocusedSection, PopupType, TabType};
+pub use log_filter::{LogFilterField, parse_date};
+pub use narrator::{AppMode, CastSection, NarratorField, NarratorFocus};
+pub(crate) use scroll::lines_height;
+pub use types::{BallType, FocusedSection, PlayPane, PopupType, TabType};
+pub use wizard::{CharacterWizard, WIZARD_STEPS, WizardStep};
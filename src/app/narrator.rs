@@ -0,0 +1,414 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use super::app_state::MAX_TOKEN;
+use super::campaign::data_file;
+use super::character::CharacterBaseInformation;
+use super::format::SheetFormat;
+use super::list::ListData;
+use super::types::TabType;
+
+const DATA_FILE: &str = "narrator.toml";
+
+/// Ruolo con cui è stata avviata l'applicazione
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum AppMode {
+    #[default]
+    Player,
+    Narrator,
+}
+
+impl AppMode {
+    /// Tab disponibili nel ruolo, nell'ordine della barra
+    pub fn tabs(&self) -> &'static [TabType] {
+        use TabType::*;
+        match *self {
+            AppMode::Player => &[
+                DrawTab,
+                CharacterSheetTab,
+                AdditionalInfoTab,
+                LogTab,
+                PlayTab,
+            ],
+            AppMode::Narrator => &[
+                NarratorChallengeTab,
+                NarratorCastTab,
+                NarratorPlayersTab,
+                NarratorNotesTab,
+                LogTab,
+            ],
+        }
+    }
+
+    /// Posizione del tab nella barra
+    pub fn tab_idx(&self, tab: TabType) -> usize {
+        self.tabs().iter().position(|t| *t == tab).unwrap_or(0)
+    }
+
+    /// Tab successivo nella barra, ricominciando dal primo
+    pub fn next_tab(&self, tab: TabType) -> TabType {
+        let tabs = self.tabs();
+        tabs[(self.tab_idx(tab) + 1) % tabs.len()]
+    }
+}
+
+/// Sezione attualmente focalizzata nel tab della sfida
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NarratorFocus {
+    Difficulty,
+    Npc,
+    DrawCount,
+}
+
+impl NarratorFocus {
+    pub fn next(&self) -> Self {
+        use NarratorFocus::*;
+        match *self {
+            Difficulty => Npc,
+            Npc => DrawCount,
+            DrawCount => Difficulty,
+        }
+    }
+
+    pub fn prev(&self) -> Self {
+        use NarratorFocus::*;
+        match *self {
+            Difficulty => DrawCount,
+            Npc => Difficulty,
+            DrawCount => Npc,
+        }
+    }
+}
+
+/// Elenco selezionato nel tab di PNG e minacce
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CastSection {
+    Npcs,
+    Threats,
+}
+
+impl CastSection {
+    pub fn next(&self) -> Self {
+        match *self {
+            CastSection::Npcs => CastSection::Threats,
+            CastSection::Threats => CastSection::Npcs,
+        }
+    }
+}
+
+/// Campo del narratore in fase di modifica
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NarratorField {
+    NpcName(usize),
+    NpcNotes(usize),
+    ThreatName(usize),
+    ThreatNotes(usize),
+    SceneNotes,
+    PlayerSheet,
+}
+
+impl NarratorField {
+    pub fn length(&self) -> usize {
+        use NarratorField::*;
+        match *self {
+            NpcName(_) | ThreatName(_) => 50,
+            NpcNotes(_) | ThreatNotes(_) => 500,
+            SceneNotes => 2000,
+            PlayerSheet => 255,
+        }
+    }
+
+    /// Il campo accetta più righe
+    pub fn multiline(&self) -> bool {
+        use NarratorField::*;
+        matches!(*self, NpcNotes(_) | ThreatNotes(_) | SceneNotes)
+    }
+}
+
+impl fmt::Display for NarratorField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use NarratorField::*;
+        match *self {
+            NpcName(_) => write!(f, "Nome PNG"),
+            NpcNotes(_) => write!(f, "Note PNG"),
+            ThreatName(_) => write!(f, "Nome Minaccia"),
+            ThreatNotes(_) => write!(f, "Note Minaccia"),
+            SceneNotes => write!(f, "Note di Scena"),
+            PlayerSheet => write!(f, "Percorso Scheda Giocatore"),
+        }
+    }
+}
+
+/// Personaggio non giocante
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct Npc {
+    pub name: String,
+    #[serde(default)]
+    pub notes: String,
+    /// Token bianchi messi in gioco dal PNG nelle sue prove
+    #[serde(default)]
+    pub traits: usize,
+}
+
+/// Minaccia che rende più difficili le prove della scena
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct Threat {
+    pub name: String,
+    #[serde(default)]
+    pub notes: String,
+    /// Token rossi aggiunti alla difficoltà quando la minaccia è attiva
+    #[serde(default)]
+    pub tokens: usize,
+    #[serde(default)]
+    pub active: bool,
+}
+
+/// Sventura aperta di un giocatore
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct Misfortune {
+    pub text: String,
+    pub tokens: usize,
+}
+
+/// Situazione di un giocatore vista dal narratore
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct PlayerStatus {
+    pub name: String,
+    /// Scheda da cui rileggere il giocatore
+    #[serde(default)]
+    pub sheet: String,
+    #[serde(default)]
+    pub misfortunes: Vec<Misfortune>,
+    /// Tratti messi in gioco nella prova in corso
+    #[serde(default)]
+    pub traits: Vec<String>,
+    #[serde(default)]
+    pub confused: bool,
+    #[serde(default)]
+    pub adrenalined: bool,
+}
+
+impl PlayerStatus {
+    /// Legge nome e sventure aperte dal contenuto di una scheda personaggio,
+    /// nel formato indicato dall'estensione del file
+    pub fn from_sheet(sheet: &str, contents: &str) -> Option<Self> {
+        let format = SheetFormat::from_path(Path::new(sheet));
+        let info = format.parse::<CharacterBaseInformation>(contents).ok()?;
+        let list = ListData::from_contents(contents, format);
+
+        let misfortunes = list
+            .misfortunes
+            .iter()
+            .zip(list.misfortunes_red_balls.iter())
+            .filter(|(text, _)| !text.trim().is_empty())
+            .map(|(text, tokens)| Misfortune {
+                text: text.clone(),
+                tokens: tokens.trim().parse().unwrap_or(0),
+            })
+            .collect();
+
+        Some(PlayerStatus {
+            name: info.name,
+            sheet: sheet.to_string(),
+            misfortunes,
+            ..Default::default()
+        })
+    }
+
+    /// Rilegge la scheda mantenendo le condizioni segnate dal narratore
+    pub fn reload(&mut self) -> Result<(), String> {
+        let contents = fs::read_to_string(&self.sheet)
+            .map_err(|_| format!("Impossibile leggere {}", self.sheet))?;
+        let status = Self::from_sheet(&self.sheet, &contents)
+            .ok_or_else(|| format!("{} non è una scheda valida", self.sheet))?;
+        self.name = status.name;
+        self.misfortunes = status.misfortunes;
+        Ok(())
+    }
+}
+
+/// Dati della plancia del narratore
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct Narrator {
+    /// Difficoltà base della prova in corso
+    #[serde(default)]
+    pub difficulty: usize,
+    #[serde(default)]
+    pub scene_notes: String,
+    #[serde(default)]
+    pub npcs: Vec<Npc>,
+    #[serde(default)]
+    pub threats: Vec<Threat>,
+    #[serde(default)]
+    pub players: Vec<PlayerStatus>,
+}
+
+impl Narrator {
+    /// Difficoltà complessiva: base più le minacce attive, fino al massimo di
+    /// token rossi nel sacchetto
+    pub fn challenge_difficulty(&self) -> usize {
+        let difficulty = self.difficulty
+            + self
+                .threats
+                .iter()
+                .filter(|t| t.active)
+                .map(|t| t.tokens)
+                .sum::<usize>();
+        difficulty.min(MAX_TOKEN)
+    }
+
+    pub(crate) fn load_narrator() -> Self {
+        if let Ok(contents) = fs::read_to_string(data_file(DATA_FILE))
+            && let Ok(mut data) = toml::from_str::<Narrator>(&contents)
+        {
+            // Un file modificato a mano non può riempire il sacchetto oltre il massimo
+            data.difficulty = data.difficulty.min(MAX_TOKEN);
+            for npc in &mut data.npcs {
+                npc.traits = npc.traits.min(MAX_TOKEN);
+            }
+            for threat in &mut data.threats {
+                threat.tokens = threat.tokens.min(MAX_TOKEN);
+            }
+            return data;
+        }
+        Narrator::default()
+    }
+
+    pub(crate) fn save_narrator(&self) {
+        if let Ok(string) = toml::to_string_pretty(self) {
+            let _ = fs::write(data_file(DATA_FILE), string);
+        }
+    }
+}
+
+#[cfg(test)]
+mod narrator_tests {
+    use crate::app::Campaign;
+    use crate::app::narrator::*;
+    use crate::test_support::{ActiveScope, TempDir};
+
+    #[test]
+    fn test_narrator_tabs_cycle() {
+        let mode = AppMode::Narrator;
+        assert_eq!(mode.next_tab(TabType::NarratorNotesTab), TabType::LogTab);
+        assert_eq!(
+            mode.next_tab(TabType::LogTab),
+            TabType::NarratorChallengeTab
+        );
+        assert_eq!(AppMode::Player.next_tab(TabType::LogTab), TabType::PlayTab);
+        assert_eq!(AppMode::Player.next_tab(TabType::PlayTab), TabType::DrawTab);
+        assert_eq!(mode.tab_idx(TabType::LogTab), 4);
+    }
+
+    #[test]
+    fn test_challenge_difficulty_counts_active_threats() {
+        let mut narrator = Narrator {
+            difficulty: 2,
+            ..Default::default()
+        };
+        narrator.threats.push(Threat {
+            name: "Tempesta".into(),
+            tokens: 2,
+            active: true,
+            ..Default::default()
+        });
+        narrator.threats.push(Threat {
+            name: "Guardie".into(),
+            tokens: 3,
+            ..Default::default()
+        });
+
+        assert_eq!(narrator.challenge_difficulty(), 4);
+
+        narrator.threats[1].active = true;
+        narrator.threats[1].tokens = MAX_TOKEN;
+        assert_eq!(narrator.challenge_difficulty(), MAX_TOKEN);
+    }
+
+    #[test]
+    fn test_loaded_tokens_are_bounded() {
+        let root = TempDir::new("narrator");
+        let campaign = Campaign::create(root.path(), "Prova").unwrap();
+        let _scope = ActiveScope::new(&campaign);
+
+        let mut narrator = Narrator {
+            difficulty: 1000,
+            ..Default::default()
+        };
+        narrator.npcs.push(Npc {
+            name: "Capitano".into(),
+            traits: 1000,
+            ..Default::default()
+        });
+        narrator.threats.push(Threat {
+            name: "Tempesta".into(),
+            tokens: 1000,
+            active: true,
+            ..Default::default()
+        });
+        narrator.save_narrator();
+
+        let loaded = Narrator::load_narrator();
+        assert_eq!(loaded.difficulty, MAX_TOKEN);
+        assert_eq!(loaded.npcs[0].traits, MAX_TOKEN);
+        assert_eq!(loaded.threats[0].tokens, MAX_TOKEN);
+    }
+
+    #[test]
+    fn test_player_from_sheet() {
+        let contents = r#"
+name = "Aria"
+objective = "La sua nave"
+misfortunes = ["Ferita", "", "Braccata", ""]
+misfortunes_red_balls = ["2", "", "x", ""]
+left_resources = ["", "", "", "", "", "", "", "", "", ""]
+notes = ""
+lessons = ["", "", ""]
+"#;
+        let player = PlayerStatus::from_sheet("aria.toml", contents).unwrap();
+
+        assert_eq!(player.name, "Aria");
+        assert_eq!(
+            player.misfortunes,
+            vec![
+                Misfortune {
+                    text: "Ferita".into(),
+                    tokens: 2
+                },
+                Misfortune {
+                    text: "Braccata".into(),
+                    tokens: 0
+                },
+            ]
+        );
+        assert!(PlayerStatus::from_sheet("x", "non è toml").is_none());
+
+        // Il formato segue l'estensione della scheda
+        let json = r#"{ "name": "Aria", "objective": "" }"#;
+        assert_eq!(
+            PlayerStatus::from_sheet("aria.json", json).unwrap().name,
+            "Aria"
+        );
+        assert!(PlayerStatus::from_sheet("aria.toml", json).is_none());
+    }
+
+    #[test]
+    fn test_narrator_round_trip() {
+        let mut narrator = Narrator::default();
+        narrator.npcs.push(Npc {
+            name: "Capitano".into(),
+            traits: 3,
+            ..Default::default()
+        });
+        narrator.players.push(PlayerStatus {
+            name: "Aria".into(),
+            confused: true,
+            ..Default::default()
+        });
+
+        let serialized = toml::to_string_pretty(&narrator).unwrap();
+        assert_eq!(toml::from_str::<Narrator>(&serialized).unwrap(), narrator);
+    }
+}
@@ -0,0 +1,71 @@
+use super::action::{Action, Prompt};
+use super::search::fuzzy_score;
+
+/// Cosa succede scegliendo una voce della palette
+#[derive(Debug, Clone, PartialEq)]
+pub enum PaletteCommand {
+    Run(Action),
+    /// Chiede un argomento prima di eseguire
+    Ask(Prompt),
+}
+
+/// Voce della palette dei comandi
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaletteEntry {
+    pub label: String,
+    pub command: PaletteCommand,
+}
+
+impl PaletteEntry {
+    pub fn run(label: impl Into<String>, action: Action) -> Self {
+        PaletteEntry {
+            label: label.into(),
+            command: PaletteCommand::Run(action),
+        }
+    }
+
+    pub fn ask(label: impl Into<String>, prompt: Prompt) -> Self {
+        PaletteEntry {
+            label: label.into(),
+            command: PaletteCommand::Ask(prompt),
+        }
+    }
+}
+
+/// Voci che corrispondono alla ricerca, dalla più pertinente.
+///
+/// A parità di punteggio resta l'ordine originale.
+pub fn filter_entries(entries: Vec<PaletteEntry>, query: &str) -> Vec<PaletteEntry> {
+    let mut scored: Vec<(i64, PaletteEntry)> = entries
+        .into_iter()
+        .filter_map(|e| fuzzy_score(query, &e.label).map(|score| (score, e)))
+        .collect();
+    if !query.trim().is_empty() {
+        scored.sort_by_key(|(score, _)| -score);
+    }
+    scored.into_iter().map(|(_, e)| e).collect()
+}
+
+#[cfg(test)]
+mod palette_tests {
+    use crate::app::palette::*;
+
+    #[test]
+    fn test_fuzzy_filter() {
+        let entries = vec![
+            PaletteEntry::run("Azzera la prova", Action::Reset),
+            PaletteEntry::run("Effettua la prova", Action::StartDraw),
+            PaletteEntry::ask("Imposta token rossi…", Prompt::RedBalls),
+        ];
+
+        let all = filter_entries(entries.clone(), "");
+        assert_eq!(all, entries);
+
+        let found = filter_entries(entries.clone(), "effpr");
+        assert_eq!(found[0].command, PaletteCommand::Run(Action::StartDraw));
+
+        let found = filter_entries(entries, "rossi");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].command, PaletteCommand::Ask(Prompt::RedBalls));
+    }
+}
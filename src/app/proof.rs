@@ -0,0 +1,295 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::time::{Duration, Instant};
+
+use super::bag;
+use super::history::{DrawHistory, valid_count};
+use super::types::BallType;
+
+/// Token estratti in tutto tra prova e rischio
+const TOTAL_DRAW: usize = 5;
+
+/// Oltre questo tempo una prova non conclusa viene abbandonata dall'host
+const PENDING_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Dati con cui chiunque può ripetere un'estrazione fatta dall'host.
+///
+/// L'impronta del seme viene pubblicata prima dell'estrazione, il seme solo a
+/// prova conclusa: l'host non può cambiarlo e i giocatori non possono prevederlo.
+/// Al seme si aggiunge il nonce scelto da chi chiede la prova dopo l'impronta,
+/// così l'host non può cercare un seme che gli dia l'esito voluto.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DrawProof {
+    /// Identificativo della richiesta di estrazione
+    pub id: u64,
+    /// Impronta SHA-256 del seme, annunciata prima dell'estrazione
+    pub commitment: String,
+    /// Seme rivelato a prova conclusa
+    pub seed: String,
+    /// Nonce di chi ha chiesto la prova
+    pub nonce: String,
+    /// Token estratti al primo tentativo
+    pub count: usize,
+}
+
+impl DrawProof {
+    /// Verifica che l'esito registrato discenda dal seme e dal sacchetto annunciati
+    pub fn verify(&self, entry: &DrawHistory) -> bool {
+        // Un sacchetto fuori dai limiti non va nemmeno riempito
+        if !entry.within_limits() || !valid_count(self.count) {
+            return false;
+        }
+        if commitment(&self.seed) != self.commitment {
+            return false;
+        }
+        let Some(mut rng) = draw_rng(&self.seed, &self.nonce) else {
+            return false;
+        };
+        let mut pool = bag::fill_pool(entry.white_balls, entry.red_balls, entry.confused, &mut rng);
+        let first = bag::draw(&mut pool, self.count, &mut rng);
+        let risk = if entry.risked {
+            bag::draw(&mut pool, TOTAL_DRAW.saturating_sub(first.len()), &mut rng)
+        } else {
+            Vec::new()
+        };
+        first == entry.first_draw && risk == entry.risk_draw
+    }
+}
+
+/// Estrazione in corso sull'host: attende il nonce, poi la decisione di rischiare
+#[derive(Debug, Clone)]
+pub struct PendingDraw {
+    pub id: u64,
+    pub commitment: String,
+    /// Prova da completare
+    pub entry: DrawHistory,
+    seed: String,
+    nonce: String,
+    count: usize,
+    /// Generatore e sacchetto del primo tentativo, una volta ricevuto il nonce
+    drawn: Option<(ChaCha20Rng, Vec<BallType>)>,
+    started: Instant,
+}
+
+impl PendingDraw {
+    /// Sceglie un nuovo seme e ne prepara l'impronta
+    pub fn start(id: u64, entry: DrawHistory, count: usize) -> Self {
+        let seed = new_seed();
+        PendingDraw {
+            id,
+            commitment: commitment(&seed),
+            entry,
+            seed,
+            nonce: String::new(),
+            count,
+            drawn: None,
+            started: Instant::now(),
+        }
+    }
+
+    /// Richiesta rimasta senza risposta troppo a lungo
+    pub fn expired(&self) -> bool {
+        self.started.elapsed() > PENDING_TIMEOUT
+    }
+
+    /// Esegue il primo tentativo con il nonce ricevuto; `None` se il nonce non è
+    /// valido, se il sacchetto è fuori dai limiti o se il primo tentativo è già
+    /// stato estratto
+    pub fn first_draw(&mut self, nonce: &str) -> Option<Vec<BallType>> {
+        if self.drawn.is_some() || !self.entry.within_limits() || !valid_count(self.count) {
+            return None;
+        }
+        let mut rng = draw_rng(&self.seed, nonce)?;
+        let entry = &mut self.entry;
+        let mut pool = bag::fill_pool(entry.white_balls, entry.red_balls, entry.confused, &mut rng);
+        entry.first_draw = bag::draw(&mut pool, self.count, &mut rng);
+        self.nonce = nonce.to_string();
+        self.drawn = Some((rng, pool));
+        Some(entry.first_draw.clone())
+    }
+
+    /// Il primo tentativo è già stato estratto
+    pub fn is_drawn(&self) -> bool {
+        self.drawn.is_some()
+    }
+
+    /// Conclude la prova, rischiando o meno, e allega il seme rivelato;
+    /// `None` se il primo tentativo non è ancora stato estratto
+    pub fn finish(mut self, risk: bool) -> Option<DrawHistory> {
+        let (mut rng, mut pool) = self.drawn.take()?;
+        self.entry.risked = risk;
+        self.entry.risk_draw = if risk {
+            let remaining = TOTAL_DRAW.saturating_sub(self.entry.first_draw.len());
+            bag::draw(&mut pool, remaining, &mut rng)
+        } else {
+            Vec::new()
+        };
+        self.entry.proof = Some(DrawProof {
+            id: self.id,
+            commitment: self.commitment,
+            seed: self.seed,
+            nonce: self.nonce,
+            count: self.count,
+        });
+        Some(self.entry)
+    }
+}
+
+/// Nuovo seme casuale di 32 byte, in esadecimale
+fn new_seed() -> String {
+    to_hex(&rand::random::<[u8; 32]>())
+}
+
+/// Nuovo nonce casuale per una richiesta di estrazione
+pub fn new_nonce() -> String {
+    new_seed()
+}
+
+/// Impronta SHA-256 del seme, in esadecimale
+pub fn commitment(seed: &str) -> String {
+    to_hex(&Sha256::digest(seed.as_bytes()))
+}
+
+/// Generatore della prova, ricavato dall'impronta di seme e nonce insieme;
+/// `None` se uno dei due manca o non è valido
+fn draw_rng(seed: &str, nonce: &str) -> Option<ChaCha20Rng> {
+    let mut hasher = Sha256::new();
+    hasher.update(from_hex(seed)?);
+    hasher.update(from_hex(nonce)?);
+    Some(ChaCha20Rng::from_seed(hasher.finalize().into()))
+}
+
+/// Legge 32 byte in esadecimale
+fn from_hex(hex: &str) -> Option<[u8; 32]> {
+    let bytes = hex.as_bytes();
+    if bytes.len() != 64 {
+        return None;
+    }
+    let mut key = [0u8; 32];
+    for (i, pair) in bytes.chunks(2).enumerate() {
+        let pair = std::str::from_utf8(pair).ok()?;
+        key[i] = u8::from_str_radix(pair, 16).ok()?;
+    }
+    Some(key)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod proof_tests {
+    use crate::app::proof::*;
+    use crate::app::{MAX_DRAW, MAX_TOKEN};
+
+    fn request(white: usize, red: usize) -> DrawHistory {
+        DrawHistory {
+            white_balls: white,
+            red_balls: red,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_same_seed_same_draw() {
+        let (seed, nonce) = (new_seed(), new_nonce());
+        let mut a = draw_rng(&seed, &nonce).unwrap();
+        let mut b = draw_rng(&seed, &nonce).unwrap();
+        let mut pool_a = bag::fill_pool(5, 5, true, &mut a);
+        let mut pool_b = bag::fill_pool(5, 5, true, &mut b);
+        assert_eq!(pool_a, pool_b);
+        assert_eq!(
+            bag::draw(&mut pool_a, 4, &mut a),
+            bag::draw(&mut pool_b, 4, &mut b)
+        );
+    }
+
+    #[test]
+    fn test_invalid_seed() {
+        let nonce = new_nonce();
+        assert!(draw_rng("abc", &nonce).is_none());
+        assert!(draw_rng(&"zz".repeat(32), &nonce).is_none());
+    }
+
+    fn drawn(white: usize, red: usize, count: usize, risk: bool) -> DrawHistory {
+        let mut pending = PendingDraw::start(1, request(white, red), count);
+        pending.first_draw(&new_nonce()).unwrap();
+        pending.finish(risk).unwrap()
+    }
+
+    #[test]
+    fn test_finished_draw_verifies() {
+        let mut pending = PendingDraw::start(1, request(3, 4), 3);
+        assert_eq!(pending.first_draw(&new_nonce()).unwrap().len(), 3);
+
+        let entry = pending.finish(true).unwrap();
+        assert_eq!(entry.risk_draw.len(), 2);
+        assert!(entry.proof.as_ref().unwrap().verify(&entry));
+    }
+
+    #[test]
+    fn test_draw_needs_valid_nonce_once() {
+        let mut pending = PendingDraw::start(1, request(3, 4), 3);
+        assert!(pending.clone().finish(false).is_none());
+        assert!(pending.first_draw("").is_none());
+        assert!(pending.first_draw("xyz").is_none());
+        assert!(pending.first_draw(&new_nonce()).is_some());
+        assert!(pending.first_draw(&new_nonce()).is_none());
+    }
+
+    #[test]
+    fn test_pending_draw_expires() {
+        let mut pending = PendingDraw::start(1, request(1, 1), 1);
+        assert!(!pending.expired());
+        pending.started -= PENDING_TIMEOUT + Duration::from_secs(1);
+        assert!(pending.expired());
+    }
+
+    #[test]
+    fn test_nonce_changes_the_draw() {
+        let seed = new_seed();
+        let a = draw_rng(&seed, &new_nonce()).unwrap();
+        let b = draw_rng(&seed, &new_nonce()).unwrap();
+        assert_ne!(a.get_seed(), b.get_seed());
+        // Senza nonce non c'è generatore: l'host non può usare il solo seme
+        assert!(draw_rng(&seed, "").is_none());
+    }
+
+    #[test]
+    fn test_tampered_draw_fails() {
+        let entry = drawn(2, 2, 2, false);
+        let proof = entry.proof.clone().unwrap();
+        assert!(proof.verify(&entry));
+
+        let mut tampered = entry.clone();
+        tampered.first_draw.push(BallType::White);
+        assert!(!proof.verify(&tampered));
+
+        let mut forged = proof.clone();
+        forged.seed = new_seed();
+        assert!(!forged.verify(&entry));
+
+        let mut unbound = proof.clone();
+        unbound.nonce.clear();
+        assert!(!unbound.verify(&entry));
+    }
+
+    #[test]
+    fn test_out_of_limits_draw_fails() {
+        // Un sacchetto oltre il massimo non viene nemmeno estratto
+        let mut pending = PendingDraw::start(1, request(MAX_TOKEN + 1, 0), 1);
+        assert!(pending.first_draw(&new_nonce()).is_none());
+        let mut pending = PendingDraw::start(1, request(2, 2), MAX_DRAW + 1);
+        assert!(pending.first_draw(&new_nonce()).is_none());
+
+        let entry = drawn(2, 2, 2, false);
+        let mut inflated = entry.clone();
+        inflated.white_balls = usize::MAX;
+        assert!(!inflated.proof.as_ref().unwrap().verify(&inflated));
+        let mut proof = entry.proof.clone().unwrap();
+        proof.count = 0;
+        assert!(!proof.verify(&entry));
+    }
+}
@@ -0,0 +1,253 @@
+use super::app_state::{MAX_DRAW, MAX_TOKEN, MIN_DRAW};
+
+/// Prova descritta in una sola riga, es. `tratti: Coraggioso, Spada; diff 3; estrai 2; confusione`
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct QuickChallenge {
+    /// Nomi dei tratti, da cercare nella scheda
+    pub traits: Vec<String>,
+    pub difficulty: usize,
+    pub draw: Option<usize>,
+    pub confusion: bool,
+    pub adrenaline: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Keyword {
+    Traits,
+    Difficulty,
+    Draw,
+    Confusion,
+    Adrenaline,
+}
+
+/// Parole chiave accettate, in italiano e in inglese
+const KEYWORDS: [(&str, Keyword); 10] = [
+    ("tratti", Keyword::Traits),
+    ("traits", Keyword::Traits),
+    ("diff", Keyword::Difficulty),
+    ("difficoltà", Keyword::Difficulty),
+    ("estrai", Keyword::Draw),
+    ("draw", Keyword::Draw),
+    ("confusione", Keyword::Confusion),
+    ("confusion", Keyword::Confusion),
+    ("adrenalina", Keyword::Adrenaline),
+    ("adrenaline", Keyword::Adrenaline),
+];
+
+/// Divide una parte della riga nella parola chiave e nel suo argomento
+fn split_keyword(part: &str) -> (&str, &str) {
+    let part = part.trim_start();
+    let end = part
+        .find(|c: char| !c.is_alphabetic())
+        .unwrap_or(part.len());
+    let arg = part[end..].trim_start();
+    let arg = arg.strip_prefix(':').unwrap_or(arg);
+    (&part[..end], arg.trim())
+}
+
+fn keyword(word: &str) -> Option<Keyword> {
+    KEYWORDS
+        .iter()
+        .find(|(name, _)| *name == word.to_lowercase())
+        .map(|(_, k)| *k)
+}
+
+/// Interpreta una riga di inserimento rapido.
+///
+/// Le parti sono separate da `;` e l'ordine è libero. Gli errori indicano
+/// la parte che non è stato possibile leggere.
+pub fn parse_challenge(input: &str) -> Result<QuickChallenge, String> {
+    let mut challenge = QuickChallenge::default();
+    let mut seen: Vec<Keyword> = Vec::new();
+
+    for (n, part) in input.split(';').enumerate() {
+        if part.trim().is_empty() {
+            continue;
+        }
+        let fail = |msg: String| format!("Parte {} «{}»: {}", n + 1, part.trim(), msg);
+        let (word, arg) = split_keyword(part);
+        let kw = keyword(word).ok_or_else(|| {
+            fail(format!(
+                "«{}» non è un comando (tratti, diff, estrai, confusione, adrenalina)",
+                word
+            ))
+        })?;
+        if seen.contains(&kw) {
+            return Err(fail(format!("«{}» è già stato indicato", word)));
+        }
+        seen.push(kw);
+
+        match kw {
+            Keyword::Traits => {
+                challenge.traits = arg
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|t| !t.is_empty())
+                    .map(str::to_string)
+                    .collect();
+                if challenge.traits.is_empty() {
+                    return Err(fail("indicare almeno un tratto".into()));
+                }
+            }
+            Keyword::Difficulty => {
+                challenge.difficulty = match arg.parse::<usize>() {
+                    Ok(n) if n <= MAX_TOKEN => n,
+                    _ => {
+                        return Err(fail(format!(
+                            "la difficoltà è un numero da 0 a {}",
+                            MAX_TOKEN
+                        )));
+                    }
+                };
+            }
+            Keyword::Draw => {
+                challenge.draw = match arg.parse::<usize>() {
+                    Ok(n) if (MIN_DRAW..=MAX_DRAW).contains(&n) => Some(n),
+                    _ => {
+                        return Err(fail(format!(
+                            "si estraggono da {} a {} token",
+                            MIN_DRAW, MAX_DRAW
+                        )));
+                    }
+                };
+            }
+            Keyword::Confusion | Keyword::Adrenaline if !arg.is_empty() => {
+                return Err(fail(format!("«{}» non vuole argomenti", word)));
+            }
+            Keyword::Confusion => challenge.confusion = true,
+            Keyword::Adrenaline => challenge.adrenaline = true,
+        }
+    }
+
+    if challenge.adrenaline && challenge.draw.is_some_and(|d| d != MAX_DRAW) {
+        return Err(format!(
+            "Con l'adrenalina si estraggono sempre {} token",
+            MAX_DRAW
+        ));
+    }
+    Ok(challenge)
+}
+
+/// Parte della riga da completare e i candidati che le corrispondono
+fn completion_target<'a>(input: &str, names: &[&'a str]) -> (usize, Vec<&'a str>) {
+    let start = input.rfind(';').map_or(0, |i| i + 1);
+    let part = &input[start..];
+    let (word, _) = split_keyword(part);
+    let word_end = start + part.len() - part.trim_start().len() + word.len();
+
+    let (fragment_start, candidates): (usize, Vec<&'a str>) = if word_end == input.len() {
+        // Si sta ancora scrivendo la parola chiave
+        (
+            word_end - word.len(),
+            KEYWORDS.iter().map(|(k, _)| *k).collect(),
+        )
+    } else if keyword(word) == Some(Keyword::Traits) {
+        let after = input.rfind([',', ':']).filter(|&i| i >= word_end);
+        let from = after.map_or(word_end, |i| i + 1);
+        let skipped = input[from..].len() - input[from..].trim_start().len();
+        (from + skipped, names.to_vec())
+    } else {
+        return (input.len(), Vec::new());
+    };
+
+    let fragment = input[fragment_start..].to_lowercase();
+    if fragment.is_empty() && candidates.len() == KEYWORDS.len() {
+        return (fragment_start, Vec::new());
+    }
+    let matches = candidates
+        .into_iter()
+        .filter(|c| c.to_lowercase().starts_with(&fragment))
+        .collect();
+    (fragment_start, matches)
+}
+
+/// Candidati per la parola che si sta scrivendo: comandi o nomi dei tratti
+pub fn suggestions<'a>(input: &str, names: &[&'a str]) -> Vec<&'a str> {
+    completion_target(input, names).1
+}
+
+/// Completa la parola che si sta scrivendo.
+///
+/// Con un solo candidato la parola viene completata per intero, con più
+/// candidati fino al loro prefisso comune. Ritorna `None` se non c'è nulla da aggiungere.
+pub fn complete(input: &str, names: &[&str]) -> Option<String> {
+    let (start, matches) = completion_target(input, names);
+    let first = matches.first()?;
+    let common: String = if matches.len() == 1 {
+        first.to_string()
+    } else {
+        let mut common: Vec<char> = first.chars().collect();
+        for other in &matches[1..] {
+            let len = common
+                .iter()
+                .zip(other.chars())
+                .take_while(|(a, b)| a.to_lowercase().eq(b.to_lowercase()))
+                .count();
+            common.truncate(len);
+        }
+        common.into_iter().collect()
+    };
+
+    if common.chars().count() <= input[start..].chars().count() {
+        return None;
+    }
+    Some(format!("{}{}", &input[..start], common))
+}
+
+#[cfg(test)]
+mod quick_entry_tests {
+    use crate::app::quick_entry::*;
+
+    #[test]
+    fn test_parse_full_line() {
+        let c = parse_challenge("traits: Coraggioso, Spada; diff 3; draw 2; confusion").unwrap();
+        assert_eq!(c.traits, vec!["Coraggioso", "Spada"]);
+        assert_eq!(c.difficulty, 3);
+        assert_eq!(c.draw, Some(2));
+        assert!(c.confusion);
+        assert!(!c.adrenaline);
+
+        let c = parse_challenge("Difficoltà: 2;  Adrenalina ;").unwrap();
+        assert_eq!((c.difficulty, c.adrenaline), (2, true));
+        assert!(c.traits.is_empty());
+    }
+
+    #[test]
+    fn test_parse_errors_name_the_part() {
+        let err = parse_challenge("tratti: Spada; dif 3").unwrap_err();
+        assert!(err.starts_with("Parte 2 «dif 3»"), "{}", err);
+        assert!(parse_challenge("diff tre").is_err());
+        assert!(parse_challenge("diff 30").is_err());
+        assert!(parse_challenge("estrai 5").is_err());
+        assert!(parse_challenge("tratti:").is_err());
+        assert!(parse_challenge("diff 1; diff 2").is_err());
+        assert!(parse_challenge("confusione 2").is_err());
+        assert!(parse_challenge("adrenalina; estrai 2").is_err());
+        assert!(parse_challenge("adrenalina; estrai 4").is_ok());
+    }
+
+    #[test]
+    fn test_complete_keywords_and_traits() {
+        let names = ["Coraggioso", "Spada", "Spavaldo"];
+        assert_eq!(complete("diff 2; tr", &names), Some("diff 2; tra".into()));
+        assert_eq!(
+            complete("diff 2; trat", &names),
+            Some("diff 2; tratti".into())
+        );
+        assert_eq!(complete("con", &names), Some("confusion".into()));
+        assert_eq!(
+            complete("tratti: cor", &names),
+            Some("tratti: Coraggioso".into())
+        );
+        assert_eq!(
+            complete("tratti: Coraggioso, sp", &names),
+            Some("tratti: Coraggioso, Spa".into())
+        );
+        assert_eq!(complete("tratti: Coraggioso, Spa", &names), None);
+        assert_eq!(complete("diff 2", &names), None);
+        assert_eq!(
+            suggestions("tratti Coraggioso,Spa", &names),
+            vec!["Spada", "Spavaldo"]
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use std::collections::VecDeque;
+
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::text::{Line, Span, StyledGrapheme, Text};
+use ratatui::widgets::ScrollbarState;
+
+/// Righe occupate da un testo mandato a capo dentro un riquadro con bordi
+pub(crate) fn text_height(text: &str, area: Rect) -> usize {
+    lines_height(&Text::raw(text).lines, area.width.saturating_sub(2))
+}
+
+/// Righe occupate da delle righe di testo mandate a capo su `width` colonne
+/// come fa `Paragraph` con `Wrap { trim: true }`
+pub(crate) fn lines_height(lines: &[Line], width: u16) -> usize {
+    if width == 0 {
+        return 0;
+    }
+    lines
+        .iter()
+        .map(|line| {
+            let graphemes = line
+                .spans
+                .iter()
+                .flat_map(|span| span.styled_graphemes(Style::default()));
+            wrapped_rows(graphemes, width)
+        })
+        .sum()
+}
+
+/// Righe di una riga mandata a capo tra le parole.
+///
+/// Ripete il `WordWrapper` di ratatui tenendo solo le larghezze, così l'altezza
+/// coincide con quella disegnata: gli spazi a inizio riga spariscono, le parole
+/// troppo lunghe vengono spezzate e un rientro che non sta con la prima parola
+/// lascia una riga vuota.
+fn wrapped_rows<'a>(graphemes: impl Iterator<Item = StyledGrapheme<'a>>, width: u16) -> usize {
+    let mut rows = 0;
+    let mut line_width = 0;
+    let mut line_empty = true;
+    let mut word_width = 0;
+    let mut word_empty = true;
+    let mut spaces: VecDeque<u16> = VecDeque::new();
+    let mut spaces_width = 0;
+    let mut after_word = false;
+
+    for grapheme in graphemes {
+        let is_space = grapheme.is_whitespace();
+        let symbol_width = u16::try_from(Span::raw(grapheme.symbol).width()).unwrap_or(u16::MAX);
+        if symbol_width > width {
+            continue;
+        }
+
+        // Parola finita, o troppo lunga per stare da sola su una riga
+        let word_found = after_word && is_space;
+        let word_overflow = line_empty && word_width + symbol_width > width;
+        let spaces_overflow = line_empty && spaces_width + symbol_width > width;
+        if word_found || word_overflow || spaces_overflow {
+            if !line_empty {
+                line_width += spaces_width;
+            }
+            line_empty &= word_empty;
+            line_width += word_width;
+            spaces.clear();
+            spaces_width = 0;
+            word_width = 0;
+            word_empty = true;
+        }
+
+        // Riga piena: si va a capo togliendo gli spazi che restavano in fondo
+        let line_full = line_width >= width;
+        let pending_overflow = symbol_width > 0 && line_width + spaces_width + word_width >= width;
+        if line_full || pending_overflow {
+            let mut remaining = width.saturating_sub(line_width);
+            rows += 1;
+            line_width = 0;
+            line_empty = true;
+            while let Some(&space) = spaces.front() {
+                if space > remaining {
+                    break;
+                }
+                spaces_width -= space;
+                remaining -= space;
+                spaces.pop_front();
+            }
+            if is_space && spaces.is_empty() {
+                continue;
+            }
+        }
+
+        if is_space {
+            spaces_width += symbol_width;
+            spaces.push_back(symbol_width);
+        } else {
+            word_width += symbol_width;
+            word_empty = false;
+        }
+        after_word = !is_space;
+    }
+
+    if line_empty && word_empty && !spaces.is_empty() {
+        rows += 1;
+    }
+    if !line_empty || !word_empty {
+        rows += 1;
+    }
+    rows.max(1)
+}
+
+/// Scorrimento massimo di un contenuto alto `height` righe in un riquadro con bordi
+pub(crate) fn max_scroll(height: usize, area: Rect) -> usize {
+    height.saturating_sub(area.height.saturating_sub(2) as usize)
+}
+
+/// Sposta uno scorrimento di `delta` righe senza superare `max`
+pub(crate) fn scroll_by(scroll: usize, delta: isize, max: usize) -> usize {
+    scroll.saturating_add_signed(delta).min(max)
+}
+
+/// Stato della scrollbar: nascosta se il contenuto sta tutto nel riquadro
+pub(crate) fn scrollbar_state(max: usize, view: usize, position: usize) -> ScrollbarState {
+    if max == 0 {
+        return ScrollbarState::default();
+    }
+    ScrollbarState::new(max + 1)
+        .viewport_content_length(view)
+        .position(position)
+}
+
+#[cfg(test)]
+mod scroll_tests {
+    use crate::app::scroll::*;
+
+    #[test]
+    fn test_text_height_counts_wrapped_lines() {
+        let area = Rect::new(0, 0, 12, 5);
+        assert_eq!(text_height("", area), 1);
+        assert_eq!(text_height("una riga", area), 1);
+        // 10 colonne utili: le parole vanno a capo senza spezzarsi
+        assert_eq!(text_height("uno due tre quattro cinque", area), 4);
+        // Conta i caratteri, non i byte
+        assert_eq!(text_height("àèìòùàèìòù", area), 1);
+        assert_eq!(text_height("a\nb\nc", area), 3);
+        // Le parole più larghe del riquadro vengono spezzate
+        assert_eq!(text_height("abcdefghijklmnopqrstuvwxy", area), 3);
+        assert_eq!(text_height("testo", Rect::new(0, 0, 2, 5)), 0);
+    }
+
+    #[test]
+    fn test_height_matches_rendered_paragraph() {
+        use ratatui::buffer::Buffer;
+        use ratatui::widgets::{Paragraph, Widget, Wrap};
+
+        let lines = vec![
+            Line::from(vec![
+                Span::raw("Prova: "),
+                Span::raw("scalare la torre del faro"),
+            ]),
+            Line::from(""),
+            Line::from("   rientro iniziale e parolalunghissimaspezzata in due"),
+            Line::from("àèìòù àèìòù àèìòù"),
+            Line::from("spazi    in mezzo   e in fondo   "),
+            Line::from("      "),
+            // Ultima riga non vuota, per contare anche quelle vuote prima
+            Line::from("fine"),
+        ];
+        for width in 4..=40 {
+            let height = lines_height(&lines, width);
+            let area = Rect::new(0, 0, width, 100);
+            let mut buffer = Buffer::empty(area);
+            Paragraph::new(lines.clone())
+                .wrap(Wrap { trim: true })
+                .render(area, &mut buffer);
+            // Le righe disegnate sono quelle fino all'ultima non vuota
+            let rendered = (0..100)
+                .rposition(|y| (0..width).any(|x| buffer[(x, y)].symbol() != " "))
+                .map_or(0, |y| y + 1);
+            assert_eq!(height, rendered, "larghezza {}", width);
+        }
+    }
+
+    #[test]
+    fn test_scroll_limits() {
+        let area = Rect::new(0, 0, 12, 5);
+        assert_eq!(max_scroll(2, area), 0);
+        assert_eq!(max_scroll(10, area), 7);
+        assert_eq!(scroll_by(5, 10, 7), 7);
+        assert_eq!(scroll_by(2, -5, 7), 0);
+    }
+}
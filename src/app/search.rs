@@ -0,0 +1,156 @@
+/// Punteggio di corrispondenza fuzzy tra una ricerca e un testo.
+///
+/// I caratteri della ricerca devono comparire nel testo nello stesso ordine,
+/// anche non consecutivi. Lettere consecutive e inizi di parola valgono di più.
+/// Ritorna `None` se il testo non corrisponde.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    let query: Vec<char> = query
+        .trim()
+        .to_lowercase()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut q = 0;
+    let mut prev_match: Option<usize> = None;
+
+    for (i, &c) in candidate.iter().enumerate() {
+        if q == query.len() {
+            break;
+        }
+        if c != query[q] {
+            continue;
+        }
+
+        score += 1;
+        if prev_match.is_some_and(|p| p + 1 == i) {
+            score += 5;
+        }
+        if i == 0 || !candidate[i - 1].is_alphanumeric() {
+            score += 10;
+        }
+        prev_match = Some(i);
+        q += 1;
+    }
+
+    if q < query.len() {
+        return None;
+    }
+    // A parità di lettere trovate, i testi più corti sono più pertinenti
+    Some(score * 100 - candidate.len() as i64)
+}
+
+/// Divide una lista di tag separati da virgola
+pub fn parse_tags(text: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in text.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+        if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+/// Lunghezza minima di una parola chiave (scarta articoli e preposizioni)
+const MIN_KEYWORD_LEN: usize = 3;
+
+/// Prefisso comune oltre il quale due parole si considerano la stessa radice
+const MIN_SHARED_ROOT: usize = 5;
+
+/// Parole chiave di una descrizione libera, in minuscolo e senza duplicati
+pub fn keywords(text: &str) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    for word in text
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.chars().count() >= MIN_KEYWORD_LEN)
+    {
+        if !words.iter().any(|w| w == word) {
+            words.push(word.to_string());
+        }
+    }
+    words
+}
+
+/// Numero di parole chiave che compaiono nel testo.
+///
+/// Una parola corrisponde se è prefisso dell'altra, se differisce solo per la
+/// desinenza ("drago" e "draghi") o se condividono una radice lunga, così
+/// "combattere" trova anche "combattimento".
+pub fn keyword_matches(keywords: &[String], text: &str) -> usize {
+    let words = self::keywords(text);
+    keywords
+        .iter()
+        .filter(|k| words.iter().any(|w| same_root(k, w)))
+        .count()
+}
+
+fn same_root(a: &str, b: &str) -> bool {
+    if a.starts_with(b) || b.starts_with(a) {
+        return true;
+    }
+    let shared = a.chars().zip(b.chars()).take_while(|(x, y)| x == y).count();
+    let shortest = a.chars().count().min(b.chars().count());
+    shared >= MIN_SHARED_ROOT || (shared >= MIN_KEYWORD_LEN && shared + 1 >= shortest)
+}
+
+#[cfg(test)]
+mod search_tests {
+    use crate::app::search::*;
+
+    #[test]
+    fn test_fuzzy_score_subsequence() {
+        assert!(fuzzy_score("crg", "Coraggioso").is_some());
+        assert!(fuzzy_score("xyz", "Coraggioso").is_none());
+        assert!(fuzzy_score("", "Coraggioso").is_some());
+    }
+
+    #[test]
+    fn test_fuzzy_score_case_insensitive() {
+        assert_eq!(
+            fuzzy_score("SPADA", "spada lunga"),
+            fuzzy_score("spada", "Spada Lunga")
+        );
+    }
+
+    #[test]
+    fn test_fuzzy_score_prefers_consecutive_and_word_start() {
+        let exact = fuzzy_score("spada", "Spada").unwrap();
+        let scattered = fuzzy_score("spada", "Sopravvivenza da strada").unwrap();
+        assert!(exact > scattered);
+
+        let word_start = fuzzy_score("lu", "Spada lunga").unwrap();
+        let inner = fuzzy_score("lu", "Salute").unwrap();
+        assert!(word_start > inner);
+    }
+
+    #[test]
+    fn test_parse_tags() {
+        assert_eq!(
+            parse_tags(" combattimento, Mare ,, mare,furtività "),
+            vec!["combattimento", "Mare", "furtività"]
+        );
+        assert!(parse_tags("  ").is_empty());
+    }
+
+    #[test]
+    fn test_keywords_skip_short_words() {
+        assert_eq!(
+            keywords("Scalo le mura di un castello, le MURA!"),
+            vec!["scalo", "mura", "castello"]
+        );
+    }
+
+    #[test]
+    fn test_keyword_matches_shared_root() {
+        let keys = keywords("combattere contro il drago");
+        assert_eq!(keyword_matches(&keys, "Combattimento"), 1);
+        assert_eq!(keyword_matches(&keys, "Draghi e combattenti"), 2);
+        assert_eq!(keyword_matches(&keys, "Nuotare"), 0);
+    }
+}
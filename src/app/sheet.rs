@@ -0,0 +1,359 @@
+use super::advancement::{Advancement, AdvancementData};
+use super::character::CharacterBaseInformation;
+use super::format::SheetFormat;
+use super::honeycomb::{HONEYCOMB_SIZE, HoneycombData, HoneycombNode, NodeField, TraitKind};
+use super::list::{ListData, ListSection};
+
+/// Campo modificabile della scheda del personaggio
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SheetField {
+    Name,
+    Objective,
+    Node(usize, NodeField),
+    List(ListSection, usize),
+}
+
+impl SheetField {
+    /// Tutti i campi, nell'ordine della scheda
+    pub fn all() -> Vec<SheetField> {
+        use ListSection::*;
+        let mut fields = vec![SheetField::Name, SheetField::Objective];
+        for idx in 0..HONEYCOMB_SIZE {
+            for field in [NodeField::Title, NodeField::Description, NodeField::Tags] {
+                fields.push(SheetField::Node(idx, field));
+            }
+        }
+        for (section, count) in [
+            (Misfortunes, 4),
+            (MisfortunesDifficult, 4),
+            (LxResources, 10),
+            (Notes, 1),
+            (Lessons, 3),
+        ] {
+            fields.extend((0..count).map(|idx| SheetField::List(section, idx)));
+        }
+        fields
+    }
+
+    /// Lunghezza massima del valore, come nella modifica a mano
+    pub fn length(&self) -> usize {
+        match *self {
+            SheetField::Name | SheetField::Objective => {
+                CharacterBaseInformation::default().length()
+            }
+            SheetField::Node(_, field) => field.length(),
+            SheetField::List(section, _) => section.length(),
+        }
+    }
+
+    /// Nome del campo, come nell'evoluzione del personaggio
+    pub fn label(&self) -> String {
+        use ListSection::*;
+        match *self {
+            SheetField::Name => "Nome".to_string(),
+            SheetField::Objective => "Obiettivo".to_string(),
+            SheetField::Node(idx, NodeField::Title) => format!("{} {}", TraitKind::of(idx), idx),
+            SheetField::Node(idx, field) => format!("{} {} ({})", TraitKind::of(idx), idx, field),
+            SheetField::List(section, idx) => match section {
+                Misfortunes => format!("Sventura {}", idx + 1),
+                MisfortunesDifficult => format!("Difficoltà Sventura {}", idx + 1),
+                LxResources => format!("Risorsa {}", idx + 1),
+                Notes => "Note".to_string(),
+                Lessons => format!("Lezione {}", idx + 1),
+            },
+        }
+    }
+}
+
+/// Contenuto della scheda, in memoria o letto dal file
+#[derive(Debug, Clone)]
+pub struct Sheet {
+    pub info: CharacterBaseInformation,
+    pub nodes: Vec<HoneycombNode>,
+    pub list: ListData,
+    pub advancement: Advancement,
+}
+
+impl Sheet {
+    /// Scheda letta dal contenuto del file; le parti mancanti restano vuote
+    pub fn parse(contents: &str, format: SheetFormat) -> Self {
+        Sheet {
+            info: CharacterBaseInformation::from_contents(contents, format),
+            nodes: HoneycombNode::from_contents(contents, format),
+            list: ListData::from_contents(contents, format),
+            advancement: format
+                .parse::<AdvancementData>(contents)
+                .map(|data| data.advancement)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Scheda completa letta dal file; `None` se manca qualche sezione,
+    /// come in un file vuoto o scritto a metà
+    pub fn read(contents: &str, format: SheetFormat) -> Option<Self> {
+        Some(Sheet {
+            info: format.parse(contents).ok()?,
+            nodes: HoneycombNode::from_data(format.parse::<HoneycombData>(contents).ok()?),
+            list: format.parse(contents).ok()?,
+            advancement: format.parse::<AdvancementData>(contents).ok()?.advancement,
+        })
+    }
+
+    /// Scheda senza alcun campo compilato
+    pub fn empty() -> Self {
+        Self::parse("", SheetFormat::Toml)
+    }
+
+    pub fn get(&self, field: SheetField) -> String {
+        use ListSection::*;
+        match field {
+            SheetField::Name => self.info.name.clone(),
+            SheetField::Objective => self.info.objective.clone(),
+            SheetField::Node(idx, field) => self.nodes[idx].field(field),
+            SheetField::List(section, idx) => match section {
+                Misfortunes => self.list.misfortunes[idx].clone(),
+                MisfortunesDifficult => self.list.misfortunes_red_balls[idx].clone(),
+                LxResources => self.list.left_resources[idx].clone(),
+                Notes => self.list.notes.clone(),
+                Lessons => self.list.lessons[idx].clone(),
+            },
+        }
+    }
+
+    pub fn set(&mut self, field: SheetField, value: String) {
+        use ListSection::*;
+        match field {
+            SheetField::Name => self.info.name = value,
+            SheetField::Objective => self.info.objective = value,
+            SheetField::Node(idx, field) => self.nodes[idx].set_field(field, &value),
+            SheetField::List(section, idx) => match section {
+                Misfortunes => self.list.misfortunes[idx] = value,
+                MisfortunesDifficult => self.list.misfortunes_red_balls[idx] = value,
+                LxResources => self.list.left_resources[idx] = value,
+                Notes => self.list.notes = value,
+                Lessons => self.list.lessons[idx] = value,
+            },
+        }
+    }
+
+    /// Campi con un valore diverso nell'altra scheda
+    pub fn differences(&self, other: &Sheet) -> Vec<SheetField> {
+        SheetField::all()
+            .into_iter()
+            .filter(|&field| self.get(field) != other.get(field))
+            .collect()
+    }
+
+    /// Stesso contenuto, evoluzione del personaggio compresa
+    pub fn same_as(&self, other: &Sheet) -> bool {
+        self.differences(other).is_empty() && self.advancement == other.advancement
+    }
+}
+
+/// Campo cambiato sia in memoria sia sul disco, con i due valori
+#[derive(Debug, Clone, PartialEq)]
+pub struct SheetConflict {
+    pub field: SheetField,
+    pub mine: String,
+    pub disk: String,
+}
+
+/// Esito dell'unione delle modifiche esterne con la scheda in memoria
+#[derive(Debug, Clone)]
+pub struct SheetMerge {
+    pub sheet: Sheet,
+    /// Campi presi dal disco
+    pub reloaded: usize,
+    pub conflicts: Vec<SheetConflict>,
+}
+
+/// Unione a tre vie tra la versione letta per ultima, quella in memoria e quella sul disco.
+///
+/// Un campo cambiato da una sola parte prende quel valore; se è cambiato da
+/// entrambe in modo diverso resta quello in memoria e diventa un conflitto.
+/// L'evoluzione del personaggio è un registro: le voci nuove sul disco si aggiungono.
+pub fn merge_sheets(base: &Sheet, mine: &Sheet, disk: &Sheet) -> SheetMerge {
+    let mut sheet = mine.clone();
+    let mut reloaded = 0;
+    if disk.advancement != base.advancement && disk.advancement != mine.advancement {
+        sheet.advancement = if mine.advancement == base.advancement {
+            disk.advancement.clone()
+        } else {
+            merge_advancement(&base.advancement, &mine.advancement, &disk.advancement)
+        };
+        reloaded += 1;
+    }
+    let mut conflicts = Vec::new();
+    for field in SheetField::all() {
+        let (base, mine, disk) = (base.get(field), mine.get(field), disk.get(field));
+        if disk == base || disk == mine {
+            continue;
+        }
+        if mine == base {
+            sheet.set(field, disk);
+            reloaded += 1;
+        } else {
+            conflicts.push(SheetConflict { field, mine, disk });
+        }
+    }
+    SheetMerge {
+        sheet,
+        reloaded,
+        conflicts,
+    }
+}
+
+/// Aggiunge all'evoluzione in memoria le voci comparse solo sul disco.
+///
+/// Le voci già lette restano al loro posto; di queste cambia solo il nodo
+/// sbloccato dalla lezione, che si prende dal disco se in memoria è invariato.
+fn merge_advancement(base: &Advancement, mine: &Advancement, disk: &Advancement) -> Advancement {
+    let mut merged = mine.clone();
+    for (i, lesson) in disk.lessons.iter().enumerate() {
+        match base.lessons.get(i) {
+            Some(read) => {
+                if read != lesson && mine.lessons.get(i) == Some(read) {
+                    merged.lessons[i] = lesson.clone();
+                }
+            }
+            None => {
+                if !mine.lessons[base.lessons.len().min(mine.lessons.len())..].contains(lesson) {
+                    merged.lessons.push(lesson.clone());
+                }
+            }
+        }
+    }
+    for change in disk.changes.iter().skip(base.changes.len()) {
+        if mine.changes[base.changes.len().min(mine.changes.len())..].contains(change) {
+            continue;
+        }
+        let mut change = change.clone();
+        // L'indice di una lezione nuova si riferisce all'elenco sul disco
+        change.lesson = change.lesson.and_then(|i| {
+            if i < base.lessons.len() {
+                return Some(i);
+            }
+            let lesson = disk.lessons.get(i)?;
+            merged.lessons.iter().rposition(|l| l == lesson)
+        });
+        merged.changes.push(change);
+    }
+    merged
+}
+
+#[cfg(test)]
+mod sheet_tests {
+    use chrono::Local;
+
+    use crate::app::sheet::*;
+
+    const BASE: &str = r#"
+name = "Lia"
+objective = "Ritrovare il fratello"
+nodes = ["Nuotare", "", "", "", "", "", "", "", "", "Cartografa", "", "", "", "", "", "", "", "", ""]
+misfortunes = ["", "", "", ""]
+misfortunes_red_balls = ["", "", "", ""]
+left_resources = ["", "", "", "", "", "", "", "", "", ""]
+notes = ""
+lessons = ["", "", ""]
+"#;
+
+    #[test]
+    fn test_parse_and_fields() {
+        let sheet = Sheet::parse(BASE, SheetFormat::Toml);
+        assert_eq!(sheet.get(SheetField::Name), "Lia");
+        assert_eq!(
+            sheet.get(SheetField::Node(9, NodeField::Title)),
+            "Cartografa"
+        );
+        assert_eq!(SheetField::Node(9, NodeField::Title).label(), "Archetipo 9");
+
+        let empty = Sheet::empty();
+        assert_eq!(empty.nodes.len(), HONEYCOMB_SIZE);
+        assert_eq!(
+            empty.differences(&sheet),
+            vec![
+                SheetField::Name,
+                SheetField::Objective,
+                SheetField::Node(0, NodeField::Title),
+                SheetField::Node(9, NodeField::Title),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_keeps_both_sides() {
+        let base = Sheet::parse(BASE, SheetFormat::Toml);
+        let mut mine = base.clone();
+        mine.set(SheetField::List(ListSection::Notes, 0), "Appunti".into());
+        let disk = Sheet::parse(&BASE.replace("Lia", "Lia la Rossa"), SheetFormat::Toml);
+
+        let merge = merge_sheets(&base, &mine, &disk);
+        assert!(merge.conflicts.is_empty());
+        assert_eq!(merge.reloaded, 1);
+        assert_eq!(merge.sheet.get(SheetField::Name), "Lia la Rossa");
+        assert_eq!(merge.sheet.list.notes, "Appunti");
+    }
+
+    #[test]
+    fn test_merge_reports_conflicts() {
+        let base = Sheet::parse(BASE, SheetFormat::Toml);
+        let mut mine = base.clone();
+        mine.set(SheetField::Name, "Lia di Mare".into());
+        let disk = Sheet::parse(&BASE.replace("Lia", "Lia la Rossa"), SheetFormat::Toml);
+
+        let merge = merge_sheets(&base, &mine, &disk);
+        assert_eq!(
+            merge.conflicts,
+            vec![SheetConflict {
+                field: SheetField::Name,
+                mine: "Lia di Mare".into(),
+                disk: "Lia la Rossa".into(),
+            }]
+        );
+        assert_eq!(merge.sheet.get(SheetField::Name), "Lia di Mare");
+
+        // Stessa modifica da entrambe le parti: nessun conflitto
+        let merge = merge_sheets(&base, &disk, &disk);
+        assert!(merge.conflicts.is_empty());
+        assert_eq!(merge.reloaded, 0);
+    }
+
+    #[test]
+    fn test_read_needs_every_section() {
+        let sheet = Sheet::read(BASE, SheetFormat::Toml).unwrap();
+        assert!(sheet.same_as(&Sheet::parse(BASE, SheetFormat::Toml)));
+
+        assert!(Sheet::read("", SheetFormat::Toml).is_none());
+        assert!(Sheet::read("  \n", SheetFormat::Toml).is_none());
+        // Scritto a metà, tagliato sia a fine riga sia dentro una riga
+        let cut = BASE.find("misfortunes_red_balls").unwrap();
+        assert!(Sheet::read(&BASE[..cut], SheetFormat::Toml).is_none());
+        assert!(Sheet::read(&BASE[..cut + 10], SheetFormat::Toml).is_none());
+    }
+
+    #[test]
+    fn test_merge_appends_advancement_from_disk() {
+        let base = Sheet::parse(BASE, SheetFormat::Toml);
+        let mut mine = base.clone();
+        mine.advancement
+            .record_change(Local::now(), "Nome".into(), "Lia", "Lia di Mare");
+        let mut disk = base.clone();
+        disk.advancement
+            .record_change(Local::now(), "Lezione 1".into(), "", "Ascoltare");
+        disk.advancement
+            .learn_lesson(Local::now(), 0, "Ascoltare", None);
+
+        let merge = merge_sheets(&base, &mine, &disk);
+        assert_eq!(merge.reloaded, 1);
+        let advancement = &merge.sheet.advancement;
+        assert_eq!(advancement.lessons, disk.advancement.lessons);
+        assert_eq!(advancement.changes.len(), 2);
+        assert_eq!(advancement.changes[0], mine.advancement.changes[0]);
+        assert_eq!(advancement.changes[1].lesson, Some(0));
+
+        // Invariata in memoria: si prende quella del disco
+        let merge = merge_sheets(&base, &base, &disk);
+        assert_eq!(merge.sheet.advancement, disk.advancement);
+    }
+}
@@ -1,7 +1,8 @@
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Tipo di pallina estratta dal sacchetto
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum BallType {
     White,
     Red,
@@ -22,38 +23,64 @@ pub enum PopupType {
     None,
     ConfirmDraw,
     ConfirmRisk,
+    /// Prova in attesa dell'estrazione dell'host
+    AwaitingHost,
+    /// Esito di un sacchetto fisico da inserire a mano
+    ManualResult,
+    Wizard,
+    Timeline,
+    /// Diario delle sessioni di gioco
+    Sessions,
+    /// Campagne, personaggi e cronaca
+    Campaigns,
+    /// Filtri e ricerca del log
+    LogFilter,
+    /// Menu contestuale aperto con il tasto destro
+    ContextMenu,
+    TraitSearch,
+    TraitPicker,
+    /// Palette dei comandi con ricerca fuzzy
+    CommandPalette,
+    /// Prova scritta in una riga
+    QuickEntry,
+    /// Scheda cambiata sia in memoria sia sul disco
+    SheetConflict,
+    /// Anteprima della scheda importata da CSV
+    SheetImport,
+    /// Conferma di un'operazione che sovrascrive dati
+    Confirm,
 }
 
 /// Tab attivo nell'interfaccia
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(clippy::enum_variant_names)]
 pub enum TabType {
     DrawTab,
     CharacterSheetTab,
     AdditionalInfoTab,
     LogTab,
-    None, // default
+    /// Scheda e prova nella stessa schermata
+    PlayTab,
+    NarratorChallengeTab,
+    NarratorCastTab,
+    NarratorPlayersTab,
+    NarratorNotesTab,
 }
 
 impl TabType {
-    pub fn next(&self) -> Self {
+    /// Titolo mostrato nella barra dei tab e nella palette dei comandi
+    pub fn title(&self) -> &'static str {
         use TabType::*;
         match *self {
-            DrawTab => CharacterSheetTab,
-            CharacterSheetTab => AdditionalInfoTab,
-            AdditionalInfoTab => LogTab,
-            LogTab => DrawTab,
-            _ => DrawTab,
-        }
-    }
-
-    pub fn idx(&self) -> usize {
-        use TabType::*;
-        match *self {
-            DrawTab => 0,
-            CharacterSheetTab => 1,
-            AdditionalInfoTab => 2,
-            LogTab => 3,
-            None => 0, // if not valid return 0 as default
+            DrawTab => "Fai una Prova",
+            CharacterSheetTab => "Scheda pt.1",
+            AdditionalInfoTab => "Scheda pt.2",
+            LogTab => "Logs Prove",
+            PlayTab => "Gioca",
+            NarratorChallengeTab => "Sfida",
+            NarratorCastTab => "PNG e Minacce",
+            NarratorPlayersTab => "Giocatori",
+            NarratorNotesTab => "Note di Scena",
         }
     }
 }
@@ -92,15 +119,31 @@ impl FocusedSection {
     }
 }
 
-/// Funzioni di utilità per la conversione di indici
-pub fn get_tab_type(idx: usize) -> TabType {
-    use TabType::*;
-    match idx {
-        0 => DrawTab,
-        1 => CharacterSheetTab,
-        2 => AdditionalInfoTab,
-        3 => LogTab,
-        _ => None,
+/// Riquadro attivo nella schermata di gioco
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlayPane {
+    Traits,
+    Misfortunes,
+    Draw,
+}
+
+impl PlayPane {
+    pub fn next(&self) -> Self {
+        use PlayPane::*;
+        match *self {
+            Traits => Misfortunes,
+            Misfortunes => Draw,
+            Draw => Traits,
+        }
+    }
+
+    pub fn prev(&self) -> Self {
+        use PlayPane::*;
+        match *self {
+            Traits => Draw,
+            Misfortunes => Traits,
+            Draw => Misfortunes,
+        }
     }
 }
 
@@ -134,35 +177,6 @@ mod types_tests {
         assert_ne!(PopupType::None, PopupType::ConfirmDraw);
     }
 
-    #[test]
-    fn test_tab_type_next() {
-        assert_eq!(TabType::DrawTab.next(), TabType::CharacterSheetTab);
-        assert_eq!(
-            TabType::CharacterSheetTab.next(),
-            TabType::AdditionalInfoTab
-        );
-        assert_eq!(TabType::AdditionalInfoTab.next(), TabType::LogTab);
-        assert_eq!(TabType::LogTab.next(), TabType::DrawTab);
-    }
-
-    #[test]
-    fn test_tab_type_next_wraps() {
-        let mut tab = TabType::DrawTab;
-        for _ in 0..4 {
-            tab = tab.next();
-        }
-        assert_eq!(tab, TabType::DrawTab);
-    }
-
-    #[test]
-    fn test_tab_type_idx() {
-        assert_eq!(TabType::DrawTab.idx(), 0);
-        assert_eq!(TabType::CharacterSheetTab.idx(), 1);
-        assert_eq!(TabType::AdditionalInfoTab.idx(), 2);
-        assert_eq!(TabType::LogTab.idx(), 3);
-        assert_eq!(TabType::None.idx(), 0);
-    }
-
     #[test]
     fn test_focused_section_next() {
         assert_eq!(FocusedSection::WhiteBalls.next(), FocusedSection::RedBalls);
@@ -195,13 +209,4 @@ mod types_tests {
         }
         assert_eq!(section, FocusedSection::WhiteBalls);
     }
-
-    #[test]
-    fn test_get_tab_type() {
-        assert_eq!(get_tab_type(0), TabType::DrawTab);
-        assert_eq!(get_tab_type(1), TabType::CharacterSheetTab);
-        assert_eq!(get_tab_type(2), TabType::AdditionalInfoTab);
-        assert_eq!(get_tab_type(3), TabType::LogTab);
-        assert_eq!(get_tab_type(99), TabType::None);
-    }
 }
@@ -0,0 +1,421 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use super::campaign::data_file;
+use super::character::CharacterBaseInformation;
+use super::honeycomb::{ARCHETYPE_NODE, HONEYCOMB_SIZE, HoneycombNode, QUALITY_NODES, TraitKind};
+
+const DRAFT_FILE: &str = "character_draft.toml";
+
+/// Passo della procedura guidata di creazione del personaggio
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum WizardStep {
+    Identity,
+    Archetype,
+    Qualities,
+    Abilities,
+    Resources,
+    Lesson,
+    Summary,
+}
+
+impl WizardStep {
+    pub fn next(&self) -> Self {
+        use WizardStep::*;
+        match *self {
+            Identity => Archetype,
+            Archetype => Qualities,
+            Qualities => Abilities,
+            Abilities => Resources,
+            Resources => Lesson,
+            Lesson => Summary,
+            Summary => Summary,
+        }
+    }
+
+    pub fn prev(&self) -> Self {
+        use WizardStep::*;
+        match *self {
+            Identity => Identity,
+            Archetype => Identity,
+            Qualities => Archetype,
+            Abilities => Qualities,
+            Resources => Abilities,
+            Lesson => Resources,
+            Summary => Lesson,
+        }
+    }
+
+    pub fn idx(&self) -> usize {
+        use WizardStep::*;
+        match *self {
+            Identity => 0,
+            Archetype => 1,
+            Qualities => 2,
+            Abilities => 3,
+            Resources => 4,
+            Lesson => 5,
+            Summary => 6,
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        use WizardStep::*;
+        match *self {
+            Identity => "Chi sono?",
+            Archetype => "Archetipo",
+            Qualities => "Qualità",
+            Abilities => "Abilità iniziali",
+            Resources => "Risorse",
+            Lesson => "Prima lezione",
+            Summary => "Riepilogo",
+        }
+    }
+}
+
+/// Numero totale di passi della procedura guidata
+pub const WIZARD_STEPS: usize = 7;
+
+/// Bozza del personaggio in fase di creazione guidata
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CharacterWizard {
+    pub step: WizardStep,
+    pub field: usize,
+    pub base_info: CharacterBaseInformation,
+    pub nodes: Vec<String>,
+    pub resources: [String; 10],
+    pub lesson: String,
+    #[serde(skip)]
+    pub error: Option<String>,
+}
+
+impl Default for CharacterWizard {
+    fn default() -> Self {
+        CharacterWizard {
+            step: WizardStep::Identity,
+            field: 0,
+            base_info: CharacterBaseInformation::default(),
+            nodes: vec![String::new(); HONEYCOMB_SIZE],
+            resources: Default::default(),
+            lesson: String::new(),
+            error: None,
+        }
+    }
+}
+
+impl CharacterWizard {
+    /// Indici dei nodi delle Abilità, nell'ordine della griglia
+    pub fn ability_nodes() -> Vec<usize> {
+        (0..HONEYCOMB_SIZE)
+            .filter(|&i| TraitKind::of(i) == TraitKind::Ability)
+            .collect()
+    }
+
+    /// Numero di campi modificabili nel passo corrente
+    pub fn field_count(&self) -> usize {
+        use WizardStep::*;
+        match self.step {
+            Identity => 2,
+            Archetype | Lesson => 1,
+            Qualities => QUALITY_NODES.len(),
+            Abilities => Self::ability_nodes().len(),
+            Resources => self.resources.len(),
+            Summary => 0,
+        }
+    }
+
+    /// Etichetta del campo `idx` nel passo corrente
+    pub fn field_label(&self, idx: usize) -> String {
+        use WizardStep::*;
+        match self.step {
+            Identity if idx == 0 => "Come mi chiamo?".to_string(),
+            Identity => "Per cosa sono disposto a RISCHIARE?".to_string(),
+            Archetype => "Archetipo".to_string(),
+            Qualities => format!("Qualità {}", idx + 1),
+            Abilities => format!("Abilità {}", idx + 1),
+            Resources => format!("Risorsa {}", idx + 1),
+            Lesson => "Lezione".to_string(),
+            Summary => String::new(),
+        }
+    }
+
+    /// Lunghezza massima del campo corrente
+    pub fn field_length(&self) -> usize {
+        use WizardStep::*;
+        match self.step {
+            Identity => self.base_info.length(),
+            Archetype | Qualities | Abilities => 35,
+            Resources => 75,
+            Lesson => 500,
+            Summary => 0,
+        }
+    }
+
+    /// Nodo della griglia associato al campo `idx`, se il passo ne ha uno
+    fn node_of_field(&self, idx: usize) -> Option<usize> {
+        use WizardStep::*;
+        match self.step {
+            Archetype => Some(ARCHETYPE_NODE),
+            Qualities => QUALITY_NODES.get(idx).copied(),
+            Abilities => Self::ability_nodes().get(idx).copied(),
+            _ => None,
+        }
+    }
+
+    /// Valore del campo `idx` nel passo corrente
+    pub fn field_value(&self, idx: usize) -> &str {
+        use WizardStep::*;
+        if let Some(node) = self.node_of_field(idx) {
+            return &self.nodes[node];
+        }
+        match self.step {
+            Identity if idx == 0 => &self.base_info.name,
+            Identity => &self.base_info.objective,
+            Resources => &self.resources[idx],
+            Lesson => &self.lesson,
+            _ => "",
+        }
+    }
+
+    /// Riferimento modificabile al campo selezionato
+    fn current_field_mut(&mut self) -> Option<&mut String> {
+        use WizardStep::*;
+        let idx = self.field;
+        if let Some(node) = self.node_of_field(idx) {
+            return self.nodes.get_mut(node);
+        }
+        match self.step {
+            Identity if idx == 0 => Some(&mut self.base_info.name),
+            Identity => Some(&mut self.base_info.objective),
+            Resources => self.resources.get_mut(idx),
+            Lesson => Some(&mut self.lesson),
+            _ => None,
+        }
+    }
+
+    /// Aggiunge un carattere al campo selezionato
+    pub fn push_char(&mut self, c: char) {
+        let max = self.field_length();
+        if let Some(value) = self.current_field_mut()
+            && value.len() < max
+        {
+            value.push(c);
+        }
+    }
+
+    /// Rimuove l'ultimo carattere dal campo selezionato
+    pub fn pop_char(&mut self) {
+        if let Some(value) = self.current_field_mut() {
+            value.pop();
+        }
+    }
+
+    /// Seleziona il campo successivo del passo
+    pub fn next_field(&mut self) {
+        if self.field_count() > 0 {
+            self.field = (self.field + 1) % self.field_count();
+        }
+    }
+
+    /// Seleziona il campo precedente del passo
+    pub fn prev_field(&mut self) {
+        if self.field_count() > 0 {
+            self.field = (self.field + self.field_count() - 1) % self.field_count();
+        }
+    }
+
+    /// Nodi della bozza nel formato della griglia, per riusare le regole di adiacenza
+    fn honeycomb(&self) -> Vec<HoneycombNode> {
+        HoneycombNode::create_honeycomb_layout_with_data(
+            self.nodes.iter().map(|t| t.trim().to_string()).collect(),
+        )
+    }
+
+    /// Verifica che il passo corrente sia completo
+    pub fn validate_step(&self) -> Result<(), String> {
+        use WizardStep::*;
+        let filled = |s: &String| !s.trim().is_empty();
+        match self.step {
+            Identity if !filled(&self.base_info.name) => {
+                Err("Il personaggio deve avere un nome".to_string())
+            }
+            Identity if !filled(&self.base_info.objective) => {
+                Err("Indica per cosa sei disposto a rischiare".to_string())
+            }
+            Archetype if !filled(&self.nodes[ARCHETYPE_NODE]) => {
+                Err("Scegli un Archetipo".to_string())
+            }
+            Qualities if !QUALITY_NODES.iter().all(|&i| filled(&self.nodes[i])) => {
+                Err("Compila tutte e sei le Qualità".to_string())
+            }
+            Abilities => {
+                let abilities = Self::ability_nodes();
+                if !abilities.iter().any(|&i| filled(&self.nodes[i])) {
+                    return Err("Aggiungi almeno un'Abilità".to_string());
+                }
+                match HoneycombNode::invalid_abilities(&self.honeycomb()).first() {
+                    Some(&node) => Err(format!(
+                        "L'Abilità \"{}\" non è collegata a nessuna Qualità",
+                        self.nodes[node].trim()
+                    )),
+                    None => Ok(()),
+                }
+            }
+            Resources if !self.resources.iter().any(filled) => {
+                Err("Indica almeno una Risorsa".to_string())
+            }
+            Lesson if !filled(&self.lesson) => Err("Scrivi la tua prima Lezione".to_string()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Passa al passo successivo se quello corrente è valido.
+    /// Ritorna `true` quando il riepilogo è stato confermato.
+    pub fn advance(&mut self) -> bool {
+        if let Err(e) = self.validate_step() {
+            self.error = Some(e);
+            return false;
+        }
+        self.error = None;
+        if self.step == WizardStep::Summary {
+            return true;
+        }
+        self.step = self.step.next();
+        self.field = 0;
+        false
+    }
+
+    /// Torna al passo precedente senza perdere i dati inseriti
+    pub fn back(&mut self) {
+        self.error = None;
+        self.step = self.step.prev();
+        self.field = 0;
+    }
+
+    /// Carica la bozza salvata, se presente
+    pub(crate) fn load_draft() -> Option<Self> {
+        let contents = fs::read_to_string(data_file(DRAFT_FILE)).ok()?;
+        let mut draft = toml::from_str::<CharacterWizard>(&contents).ok()?;
+        draft.nodes.resize(HONEYCOMB_SIZE, String::new());
+        Some(draft)
+    }
+
+    /// Salva la bozza per poterla riprendere in seguito
+    pub(crate) fn save_draft(&self) {
+        if let Ok(toml_string) = toml::to_string_pretty(self) {
+            let _ = fs::write(data_file(DRAFT_FILE), toml_string);
+        }
+    }
+
+    /// Elimina la bozza una volta completata la creazione
+    pub(crate) fn discard_draft() {
+        let _ = fs::remove_file(data_file(DRAFT_FILE));
+    }
+}
+
+#[cfg(test)]
+mod wizard_tests {
+    use crate::app::wizard::*;
+
+    fn complete_identity(wizard: &mut CharacterWizard) {
+        wizard.base_info.name = "Aria".to_string();
+        wizard.base_info.objective = "Ritrovare il fratello".to_string();
+    }
+
+    #[test]
+    fn test_wizard_step_cycle() {
+        let mut step = WizardStep::Identity;
+        for _ in 0..WIZARD_STEPS {
+            step = step.next();
+        }
+        assert_eq!(step, WizardStep::Summary);
+        assert_eq!(WizardStep::Identity.prev(), WizardStep::Identity);
+        assert_eq!(WizardStep::Summary.idx(), WIZARD_STEPS - 1);
+    }
+
+    #[test]
+    fn test_identity_requires_name() {
+        let mut wizard = CharacterWizard::default();
+        assert!(!wizard.advance());
+        assert!(wizard.error.is_some());
+        assert_eq!(wizard.step, WizardStep::Identity);
+
+        complete_identity(&mut wizard);
+        assert!(!wizard.advance());
+        assert!(wizard.error.is_none());
+        assert_eq!(wizard.step, WizardStep::Archetype);
+    }
+
+    #[test]
+    fn test_typing_goes_to_current_field() {
+        let mut wizard = CharacterWizard::default();
+        wizard.next_field();
+        wizard.push_char('x');
+        assert_eq!(wizard.base_info.objective, "x");
+
+        wizard.step = WizardStep::Qualities;
+        wizard.field = 2;
+        wizard.push_char('q');
+        assert_eq!(wizard.nodes[QUALITY_NODES[2]], "q");
+        wizard.pop_char();
+        assert!(wizard.nodes[QUALITY_NODES[2]].is_empty());
+    }
+
+    #[test]
+    fn test_qualities_require_all_six() {
+        let mut wizard = CharacterWizard {
+            step: WizardStep::Qualities,
+            ..Default::default()
+        };
+        for &i in &QUALITY_NODES[..5] {
+            wizard.nodes[i] = "Tenace".to_string();
+        }
+        assert!(wizard.validate_step().is_err());
+        wizard.nodes[QUALITY_NODES[5]] = "Astuto".to_string();
+        assert!(wizard.validate_step().is_ok());
+    }
+
+    #[test]
+    fn test_abilities_must_follow_adjacency() {
+        let mut wizard = CharacterWizard {
+            step: WizardStep::Abilities,
+            ..Default::default()
+        };
+        wizard.nodes[ARCHETYPE_NODE] = "Cavaliere".to_string();
+        wizard.nodes[4] = "Coraggioso".to_string();
+        assert!(wizard.validate_step().is_err());
+
+        wizard.nodes[18] = "Arco".to_string();
+        assert!(wizard.validate_step().is_err());
+
+        wizard.nodes[18].clear();
+        wizard.nodes[0] = "Spada".to_string();
+        assert!(wizard.validate_step().is_ok());
+    }
+
+    #[test]
+    fn test_back_keeps_data() {
+        let mut wizard = CharacterWizard::default();
+        complete_identity(&mut wizard);
+        wizard.advance();
+        wizard.back();
+        assert_eq!(wizard.step, WizardStep::Identity);
+        assert_eq!(wizard.base_info.name, "Aria");
+    }
+
+    #[test]
+    fn test_draft_round_trip() {
+        let mut wizard = CharacterWizard {
+            step: WizardStep::Resources,
+            ..Default::default()
+        };
+        complete_identity(&mut wizard);
+        wizard.resources[3] = "Corda".to_string();
+
+        let serialized = toml::to_string_pretty(&wizard).unwrap();
+        let restored = toml::from_str::<CharacterWizard>(&serialized).unwrap();
+        assert_eq!(restored.step, WizardStep::Resources);
+        assert_eq!(restored.base_info.name, "Aria");
+        assert_eq!(restored.resources[3], "Corda");
+    }
+}
@@ -1,15 +1,36 @@
 use crossterm::event::{self, Event, KeyEventKind};
 use std::io;
+use std::time::Duration;
 
-use crate::app::{App, PopupType};
+use crate::app::{App, AppMode, PopupType};
 
+mod campaign;
+mod context_menu;
 mod editing;
+mod import;
+mod journal;
 mod keyboard;
+mod log;
+mod manual;
 mod mouse;
+mod narrator;
+mod palette;
+mod quick_entry;
+mod search;
+mod sheet_conflict;
+mod wizard;
+
+/// Intervallo massimo di attesa di un evento prima di tornare al loop
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
 
 /// Gestisce tutti gli eventi di input (tastiera, mouse)
 /// Ritorna `true` se l'applicazione deve terminare
 pub fn handle_input(app: &mut App) -> io::Result<bool> {
+    // Attesa breve, così il loop può ridisegnare i messaggi della sessione
+    if !event::poll(POLL_INTERVAL)? {
+        return Ok(false);
+    }
+
     match event::read()? {
         Event::Key(key) => {
             // Considera solo eventi di pressione (ignora rilascio)
@@ -17,15 +38,57 @@ pub fn handle_input(app: &mut App) -> io::Result<bool> {
                 return Ok(false);
             }
 
+            // Il messaggio di stato resta visibile fino al tasto successivo
+            app.status_message = None;
+
             // Determina se siamo in modalità editing/popup
             let is_editing = app.editing_node
                 || app.editing_list_item
                 || app.editing_character_info
+                || app.narrator_field.is_some()
                 || app.popup != PopupType::None;
 
-            if is_editing {
+            if app.popup == PopupType::Wizard {
+                wizard::handle_wizard_mode(app, key);
+                Ok(false)
+            } else if app.popup == PopupType::TraitSearch {
+                search::handle_search_mode(app, key);
+                Ok(false)
+            } else if app.popup == PopupType::TraitPicker {
+                search::handle_picker_mode(app, key);
+                Ok(false)
+            } else if app.popup == PopupType::Campaigns {
+                campaign::handle_campaigns_mode(app, key);
+                Ok(false)
+            } else if app.popup == PopupType::Sessions {
+                journal::handle_sessions_mode(app, key);
+                Ok(false)
+            } else if app.popup == PopupType::ContextMenu {
+                context_menu::handle_context_menu_mode(app, key);
+                Ok(false)
+            } else if app.popup == PopupType::LogFilter {
+                log::handle_log_filter_mode(app, key);
+                Ok(false)
+            } else if app.popup == PopupType::CommandPalette {
+                palette::handle_palette_mode(app, key);
+                Ok(false)
+            } else if app.popup == PopupType::QuickEntry {
+                quick_entry::handle_quick_entry_mode(app, key);
+                Ok(false)
+            } else if app.popup == PopupType::ManualResult {
+                manual::handle_manual_mode(app, key);
+                Ok(false)
+            } else if app.popup == PopupType::SheetImport {
+                import::handle_import_mode(app, key);
+                Ok(false)
+            } else if app.popup == PopupType::SheetConflict {
+                sheet_conflict::handle_sheet_conflict_mode(app, key);
+                Ok(false)
+            } else if is_editing {
                 editing::handle_editing_mode(app, key);
                 Ok(false)
+            } else if app.mode == AppMode::Narrator {
+                Ok(narrator::handle_narrator_mode(app, key))
             } else {
                 Ok(keyboard::handle_normal_mode(app, key))
             }
@@ -34,6 +97,11 @@ pub fn handle_input(app: &mut App) -> io::Result<bool> {
             mouse::handle_mouse_event(app, mouse);
             Ok(false)
         }
+        Event::Resize(_, _) => {
+            // Le aree cliccabili tornano valide al prossimo frame
+            app.hits.clear();
+            Ok(false)
+        }
         _ => Ok(false),
     }
 }
@@ -0,0 +1,86 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::app::{App, CampaignInput, PopupType};
+
+/// Lunghezza massima dei nomi e delle voci della cronaca
+const CAMPAIGN_INPUT_LENGTH: usize = 200;
+
+/// Gestisce gli eventi della tastiera nella finestra delle campagne
+pub fn handle_campaigns_mode(app: &mut App, key: KeyEvent) {
+    if let Some((_, text)) = &mut app.campaign_input {
+        match key.code {
+            KeyCode::Esc => app.campaign_input = None,
+            KeyCode::Enter => {
+                if let Err(e) = app.confirm_campaign_input() {
+                    app.status_message = Some(e);
+                }
+            }
+            KeyCode::Backspace => {
+                text.pop();
+            }
+            KeyCode::Char(c) if text.chars().count() < CAMPAIGN_INPUT_LENGTH => text.push(c),
+            _ => {}
+        }
+        return;
+    }
+
+    match key.code {
+        KeyCode::Esc => app.popup = PopupType::None,
+        KeyCode::Tab | KeyCode::Left | KeyCode::Right => {
+            app.campaign_focus = app.campaign_focus.next();
+        }
+        KeyCode::Up => app.select_campaign_entry(false),
+        KeyCode::Down => app.select_campaign_entry(true),
+        KeyCode::Enter => {
+            if let Err(e) = app.confirm_campaign_entry() {
+                app.status_message = Some(e);
+            }
+        }
+        KeyCode::Char('n') | KeyCode::Char('N') => {
+            app.campaign_input = Some((CampaignInput::NewCampaign, String::new()));
+        }
+        KeyCode::Char('p') | KeyCode::Char('P') if app.campaign.is_some() => {
+            app.campaign_input = Some((CampaignInput::NewCharacter, String::new()));
+        }
+        KeyCode::Char('c') | KeyCode::Char('C') if app.campaign.is_some() => {
+            app.campaign_input = Some((CampaignInput::Chronicle, String::new()));
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::CampaignFocus;
+    use crate::test_support::press;
+
+    #[test]
+    fn test_character_and_chronicle_need_a_campaign() {
+        let mut app = App::new();
+        app.popup = PopupType::Campaigns;
+        press(&mut app, handle_campaigns_mode, KeyCode::Char('p'));
+        press(&mut app, handle_campaigns_mode, KeyCode::Char('c'));
+        assert_eq!(app.campaign_input, None);
+
+        press(&mut app, handle_campaigns_mode, KeyCode::Char('n'));
+        press(&mut app, handle_campaigns_mode, KeyCode::Char('x'));
+        assert_eq!(
+            app.campaign_input,
+            Some((CampaignInput::NewCampaign, "x".to_string()))
+        );
+        press(&mut app, handle_campaigns_mode, KeyCode::Esc);
+        assert_eq!(app.campaign_input, None);
+        assert_eq!(app.popup, PopupType::Campaigns);
+    }
+
+    #[test]
+    fn test_focus_switch() {
+        let mut app = App::new();
+        app.popup = PopupType::Campaigns;
+        press(&mut app, handle_campaigns_mode, KeyCode::Tab);
+        assert_eq!(app.campaign_focus, CampaignFocus::Characters);
+        press(&mut app, handle_campaigns_mode, KeyCode::Right);
+        assert_eq!(app.campaign_focus, CampaignFocus::Campaigns);
+    }
+}
@@ -0,0 +1,14 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::app::App;
+
+/// Gestisce gli eventi della tastiera nel menu contestuale
+pub fn handle_context_menu_mode(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => app.close_context_menu(),
+        KeyCode::Enter => app.run_context_action(),
+        KeyCode::Up => app.select_context_action(false),
+        KeyCode::Down => app.select_context_action(true),
+        _ => {}
+    }
+}
@@ -9,27 +9,48 @@ pub fn handle_editing_mode(app: &mut App, key: KeyEvent) {
         KeyCode::Char(c) => handle_char_input(app, c),
         KeyCode::Backspace => handle_backspace(app),
         KeyCode::Enter => handle_enter_editing(app),
+        KeyCode::Up => handle_popup_scroll(app, false),
+        KeyCode::Down => handle_popup_scroll(app, true),
         _ => {}
     }
 }
 
+/// Gestisce lo scorrimento nei popup di sola lettura
+fn handle_popup_scroll(app: &mut App, down: bool) {
+    if app.popup == PopupType::Timeline {
+        app.timeline_scroll = if down {
+            app.timeline_scroll.saturating_add(1)
+        } else {
+            app.timeline_scroll.saturating_sub(1)
+        };
+    }
+}
+
 /// Gestisce il tasto Escape in modalità editing
 fn handle_escape(app: &mut App) {
-    if app.editing_node {
+    if app.narrator_field.is_some() {
+        app.finish_narrator_editing();
+    } else if app.editing_node {
         app.finish_node_editing();
     } else if app.editing_list_item {
         app.finish_list_editing();
     } else if app.editing_character_info {
         app.finish_character_editing();
-    } else if app.popup == PopupType::ConfirmRisk {
+    } else if matches!(app.popup, PopupType::ConfirmRisk | PopupType::AwaitingHost) {
+        // Anche una prova abbandonata resta nel log, senza rischio
         app.cancel_draw();
     }
+    app.confirm = None;
     app.popup = PopupType::None;
 }
 
 /// Gestisce l'input di caratteri
 fn handle_char_input(app: &mut App, c: char) {
-    if app.editing_node && app.node_edit_buffer.len() < 35 {
+    if let Some(field) = app.narrator_field {
+        if app.narrator_edit_buffer.len() < field.length() {
+            app.narrator_edit_buffer.push(c);
+        }
+    } else if app.editing_node && app.node_edit_buffer.len() < app.node_field.length() {
         app.node_edit_buffer.push(c);
     } else if app.editing_character_info
         && app.character_edit_buffer.len() < app.character_base_info.length()
@@ -44,7 +65,9 @@ fn handle_char_input(app: &mut App, c: char) {
 
 /// Gestisce il backspace
 fn handle_backspace(app: &mut App) {
-    if app.editing_node {
+    if app.narrator_field.is_some() {
+        app.narrator_edit_buffer.pop();
+    } else if app.editing_node {
         app.node_edit_buffer.pop();
     } else if app.editing_character_info {
         app.character_edit_buffer.pop();
@@ -62,12 +85,32 @@ fn handle_enter_editing(app: &mut App) {
         PopupType::ConfirmRisk => {
             app.perform_risk_draw();
         }
+        PopupType::Confirm => app.accept_confirm(),
+        PopupType::AwaitingHost
+        | PopupType::ManualResult
+        | PopupType::Wizard
+        | PopupType::Timeline
+        | PopupType::Sessions
+        | PopupType::Campaigns
+        | PopupType::LogFilter
+        | PopupType::ContextMenu
+        | PopupType::TraitSearch
+        | PopupType::TraitPicker
+        | PopupType::CommandPalette
+        | PopupType::QuickEntry
+        | PopupType::SheetConflict
+        | PopupType::SheetImport => {}
         PopupType::None => {
             // In editing liste, permetti newline se c'è spazio
             if app.editing_list_item
                 && app.list_edit_buffer.len() < app.selected_list_item.unwrap().0.length()
             {
                 app.list_edit_buffer.push('\n');
+            } else if let Some(field) = app.narrator_field
+                && field.multiline()
+                && app.narrator_edit_buffer.len() < field.length()
+            {
+                app.narrator_edit_buffer.push('\n');
             }
         }
     }
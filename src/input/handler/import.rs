@@ -0,0 +1,17 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::app::App;
+
+/// Gestisce gli eventi della tastiera nell'anteprima dell'importazione
+pub fn handle_import_mode(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => app.cancel_sheet_import(),
+        KeyCode::Enter => app.confirm_sheet_import(),
+        KeyCode::Up => app.select_import_row(false),
+        KeyCode::Down => app.select_import_row(true),
+        KeyCode::Left => app.cycle_import_target(false),
+        KeyCode::Right => app.cycle_import_target(true),
+        KeyCode::Delete | KeyCode::Backspace => app.ignore_import_row(),
+        _ => {}
+    }
+}
@@ -0,0 +1,62 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::app::{App, PopupType};
+
+/// Lunghezza massima del titolo di una sessione
+const SESSION_TITLE_LENGTH: usize = 60;
+
+/// Gestisce gli eventi della tastiera nel diario delle sessioni
+pub fn handle_sessions_mode(app: &mut App, key: KeyEvent) {
+    if let Some(title) = &mut app.session_title {
+        match key.code {
+            KeyCode::Esc => app.session_title = None,
+            KeyCode::Enter => app.start_game_session(),
+            KeyCode::Backspace => {
+                title.pop();
+            }
+            KeyCode::Char(c) if title.chars().count() < SESSION_TITLE_LENGTH => title.push(c),
+            _ => {}
+        }
+        return;
+    }
+
+    match key.code {
+        KeyCode::Esc => app.popup = PopupType::None,
+        KeyCode::Up => app.select_session(false),
+        KeyCode::Down => app.select_session(true),
+        KeyCode::PageUp => app.sessions_scroll = app.sessions_scroll.saturating_sub(5),
+        KeyCode::PageDown => app.sessions_scroll = app.sessions_scroll.saturating_add(5),
+        KeyCode::Char('n') | KeyCode::Char('N') => {
+            if app.journal.active().is_some() {
+                app.status_message = Some("C'è già una sessione in corso".to_string());
+            } else {
+                app.session_title = Some(String::new());
+            }
+        }
+        KeyCode::Char('f') | KeyCode::Char('F') => app.end_game_session(),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::press;
+
+    #[test]
+    fn test_title_typing_does_not_trigger_commands() {
+        let mut app = App::new();
+        app.journal = Default::default();
+        app.open_sessions();
+
+        press(&mut app, handle_sessions_mode, KeyCode::Char('n'));
+        press(&mut app, handle_sessions_mode, KeyCode::Char('f'));
+        press(&mut app, handle_sessions_mode, KeyCode::Char('n'));
+        assert_eq!(app.session_title.as_deref(), Some("fn"));
+
+        press(&mut app, handle_sessions_mode, KeyCode::Esc);
+        assert_eq!(app.session_title, None);
+        assert_eq!(app.popup, PopupType::Sessions);
+        assert!(app.journal.sessions.is_empty());
+    }
+}
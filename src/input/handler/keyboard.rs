@@ -1,19 +1,28 @@
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 use crate::app::{
-    App, CharacterSection, FocusedSection, ListSection, MAX_DRAW, MIN_DRAW, PopupType, TabType,
+    Action, App, CharacterSection, FocusedSection, ListSection, LogFilterField, MAX_DRAW, MIN_DRAW,
+    NodeField, PlayPane, TabType,
 };
 
 /// Gestisce gli eventi della tastiera quando non si è in modalità editing/popup
 pub fn handle_normal_mode(app: &mut App, key: KeyEvent) -> bool {
     match key.code {
-        KeyCode::Char('q') | KeyCode::Char('Q') => true, // Quit
+        KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.open_palette();
+            false
+        }
+        KeyCode::Char(':') => {
+            app.open_palette();
+            false
+        }
+        KeyCode::Char('q') | KeyCode::Char('Q') => app.ready_to_quit(), // Quit
         KeyCode::Char('r') | KeyCode::Char('R') => {
-            app.reset();
+            run(app, Action::Reset);
             false
         }
         KeyCode::Tab => {
-            app.current_tab = app.current_tab.next();
+            app.next_tab();
             false
         }
         KeyCode::Enter => {
@@ -24,6 +33,89 @@ pub fn handle_normal_mode(app: &mut App, key: KeyEvent) -> bool {
             handle_enable_key(app);
             false
         }
+        KeyCode::Char('t') | KeyCode::Char('T') => {
+            run(app, Action::OpenTimeline);
+            false
+        }
+        KeyCode::Char('j') | KeyCode::Char('J') => {
+            run(app, Action::OpenSessions);
+            false
+        }
+        KeyCode::Char('o') | KeyCode::Char('O') => {
+            run(app, Action::OpenCampaigns);
+            false
+        }
+        KeyCode::Char('u') | KeyCode::Char('U') => {
+            if app.current_tab == TabType::CharacterSheetTab {
+                app.status_message = Some(match app.unlock_slot_with_lesson() {
+                    Ok(node) => format!("Slot {} sbloccato", node),
+                    Err(e) => e,
+                });
+            }
+            false
+        }
+        KeyCode::Char('/') => {
+            if app.current_tab == TabType::LogTab {
+                app.open_log_filter(LogFilterField::Query);
+            } else {
+                run(app, Action::OpenTraitSearch);
+            }
+            false
+        }
+        KeyCode::Char('f') | KeyCode::Char('F') => {
+            if app.current_tab == TabType::LogTab {
+                app.open_log_filter(LogFilterField::Session);
+            }
+            false
+        }
+        KeyCode::Char('d') | KeyCode::Char('D') => {
+            if app.current_tab == TabType::CharacterSheetTab
+                && app.selected_character_info == CharacterSection::None
+            {
+                app.start_node_field_editing(NodeField::Description);
+            }
+            false
+        }
+        KeyCode::Char('g') | KeyCode::Char('G') => {
+            if app.current_tab == TabType::CharacterSheetTab
+                && app.selected_character_info == CharacterSection::None
+            {
+                app.start_node_field_editing(NodeField::Tags);
+            }
+            false
+        }
+        KeyCode::Char('s') | KeyCode::Char('S') => {
+            if matches!(app.current_tab, TabType::DrawTab | TabType::PlayTab) {
+                run(app, Action::OpenTraitPicker);
+            }
+            false
+        }
+        KeyCode::Char('i') | KeyCode::Char('I') => {
+            if matches!(app.current_tab, TabType::DrawTab | TabType::PlayTab) {
+                run(app, Action::OpenQuickEntry);
+            }
+            false
+        }
+        KeyCode::Char('m') | KeyCode::Char('M') => {
+            if matches!(app.current_tab, TabType::DrawTab | TabType::PlayTab) {
+                run(app, Action::ToggleManual);
+            }
+            false
+        }
+        KeyCode::Char('+') | KeyCode::Char('-') if app.current_tab == TabType::PlayTab => {
+            if key.code == KeyCode::Char('+') {
+                app.increment_balls();
+            } else {
+                app.decrement_balls();
+            }
+            false
+        }
+        KeyCode::Char('n') | KeyCode::Char('N') => {
+            if app.current_tab == TabType::CharacterSheetTab {
+                run(app, Action::StartWizard);
+            }
+            false
+        }
         KeyCode::Right => {
             handle_right_arrow(app);
             false
@@ -40,20 +132,38 @@ pub fn handle_normal_mode(app: &mut App, key: KeyEvent) -> bool {
             handle_down_arrow(app);
             false
         }
+        KeyCode::PageUp | KeyCode::PageDown if app.current_tab == TabType::LogTab => {
+            app.scroll_log_page(key.code == KeyCode::PageDown);
+            false
+        }
+        KeyCode::Home | KeyCode::End if app.current_tab == TabType::LogTab => {
+            app.scroll_log_to_end(key.code == KeyCode::End);
+            false
+        }
         _ => false,
     }
 }
 
+/// Esegue un'azione, mostrando l'eventuale errore nella barra di stato
+fn run(app: &mut App, action: Action) {
+    if let Err(e) = app.run_action(action) {
+        app.status_message = Some(e);
+    }
+}
+
 /// Gestisce il tasto Enter in modalità normale
 fn handle_enter_key(app: &mut App) {
     match app.current_tab {
         TabType::DrawTab => handle_enter_draw_tab(app),
         TabType::CharacterSheetTab => handle_enter_character_tab(app),
-        TabType::AdditionalInfoTab => {
-            if app.selected_list_item.is_some() {
-                app.start_list_editing();
-            }
+        TabType::AdditionalInfoTab if app.selected_list_item.is_some() => {
+            app.start_list_editing();
         }
+        TabType::PlayTab => match app.play_focus {
+            PlayPane::Traits => handle_enter_character_tab(app),
+            PlayPane::Misfortunes => handle_enable_misfortune(app),
+            PlayPane::Draw => handle_enter_draw_tab(app),
+        },
         _ => {}
     }
 }
@@ -61,11 +171,7 @@ fn handle_enter_key(app: &mut App) {
 /// Gestisce Enter nel tab di estrazione
 fn handle_enter_draw_tab(app: &mut App) {
     match app.focused_section {
-        FocusedSection::DrawInput => {
-            if app.white_balls > 0 && app.red_balls > 0 {
-                app.popup = PopupType::ConfirmDraw;
-            }
-        }
+        FocusedSection::DrawInput => run(app, Action::StartDraw),
         FocusedSection::ForcedFour => {
             app.forced_four_mode = !app.forced_four_mode;
             if app.forced_four_mode {
@@ -99,6 +205,11 @@ fn handle_enable_key(app: &mut App) {
         TabType::CharacterSheetTab => handle_enable_trait(app),
         TabType::AdditionalInfoTab => handle_enable_misfortune(app),
         TabType::DrawTab => handle_enable_status(app),
+        TabType::PlayTab => match app.play_focus {
+            PlayPane::Traits => handle_enable_trait(app),
+            PlayPane::Misfortunes => handle_enable_misfortune(app),
+            PlayPane::Draw => handle_enable_status(app),
+        },
         _ => {}
     }
 }
@@ -106,20 +217,7 @@ fn handle_enable_key(app: &mut App) {
 /// Abilita/disabilita un tratto
 fn handle_enable_trait(app: &mut App) {
     if let Some(idx) = app.selected_node {
-        // Ignora nodi vuoti
-        if app.honeycomb_nodes[idx].text.is_empty() {
-            return;
-        }
-
-        // Toggle del tratto
-        if app.used_traits.contains(&idx) {
-            let pos = app.used_traits.iter().position(|n| *n == idx).unwrap();
-            app.used_traits.swap_remove(pos);
-            app.white_balls -= 1;
-        } else {
-            app.used_traits.push(idx);
-            app.white_balls += 1;
-        }
+        run(app, Action::ToggleTrait(idx));
     }
 }
 
@@ -128,24 +226,7 @@ fn handle_enable_misfortune(app: &mut App) {
     if let Some((ListSection::Misfortunes | ListSection::MisfortunesDifficult, idx)) =
         app.selected_list_item
     {
-        // Ignora sfortune vuote
-        if app.list_data.misfortunes[idx].is_empty() {
-            return;
-        }
-
-        let value = app.list_data.misfortunes_red_balls[idx]
-            .trim()
-            .parse::<usize>()
-            .unwrap_or(0);
-
-        // Toggle della sfortuna
-        if app.additional_red_balls[idx] != 0 {
-            app.red_balls -= app.additional_red_balls[idx];
-            app.additional_red_balls[idx] = 0;
-        } else {
-            app.additional_red_balls[idx] = value;
-            app.red_balls += value;
-        }
+        run(app, Action::ToggleMisfortune(idx));
     }
 }
 
@@ -179,6 +260,7 @@ fn handle_right_arrow(app: &mut App) {
         TabType::AdditionalInfoTab => {
             app.next_section();
         }
+        TabType::PlayTab => app.play_focus = app.play_focus.next(),
         _ => {}
     }
 }
@@ -199,6 +281,7 @@ fn handle_left_arrow(app: &mut App) {
         TabType::AdditionalInfoTab => {
             app.prev_section();
         }
+        TabType::PlayTab => app.play_focus = app.play_focus.prev(),
         _ => {}
     }
 }
@@ -209,18 +292,14 @@ fn handle_up_arrow(app: &mut App) {
         TabType::DrawTab => {
             app.increment_balls();
         }
-        TabType::CharacterSheetTab => {
-            if app.selected_character_info == CharacterSection::None {
-                app.up_hex();
-            }
+        TabType::CharacterSheetTab if app.selected_character_info == CharacterSection::None => {
+            app.up_hex();
         }
         TabType::AdditionalInfoTab => {
             app.up_section();
         }
-        TabType::LogTab => {
-            app.vertical_scroll = app.vertical_scroll.saturating_sub(1);
-            app.vertical_scroll_state = app.vertical_scroll_state.position(app.vertical_scroll);
-        }
+        TabType::LogTab => app.select_log_entry(false),
+        TabType::PlayTab => app.play_select(false),
         _ => {}
     }
 }
@@ -231,20 +310,14 @@ fn handle_down_arrow(app: &mut App) {
         TabType::DrawTab => {
             app.decrement_balls();
         }
-        TabType::CharacterSheetTab => {
-            if app.selected_character_info == CharacterSection::None {
-                app.down_hex();
-            }
+        TabType::CharacterSheetTab if app.selected_character_info == CharacterSection::None => {
+            app.down_hex();
         }
         TabType::AdditionalInfoTab => {
             app.down_section();
         }
-        TabType::LogTab => {
-            if app.vertical_scroll < app.history.len() * 13 {
-                app.vertical_scroll = app.vertical_scroll.saturating_add(1);
-                app.vertical_scroll_state = app.vertical_scroll_state.position(app.vertical_scroll);
-            }
-        }
+        TabType::LogTab => app.select_log_entry(true),
+        TabType::PlayTab => app.play_select(true),
         _ => {}
     }
 }
@@ -254,6 +327,7 @@ mod tests {
     use crossterm::event::KeyModifiers;
 
     use super::*;
+    use crate::app::PopupType;
 
     // Test keyboard
     #[test]
@@ -285,6 +359,64 @@ mod tests {
         assert_eq!(app.popup, PopupType::ConfirmDraw);
     }
 
+    #[test]
+    fn test_palette_keys() {
+        let mut app = App::new();
+        handle_normal_mode(
+            &mut app,
+            KeyEvent::new(KeyCode::Char(':'), KeyModifiers::empty()),
+        );
+        assert_eq!(app.popup, PopupType::CommandPalette);
+
+        let mut app = App::new();
+        handle_normal_mode(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL),
+        );
+        assert_eq!(app.popup, PopupType::CommandPalette);
+    }
+
+    #[test]
+    fn test_empty_draw_reports_why() {
+        let mut app = App::new();
+        app.focused_section = FocusedSection::DrawInput;
+        handle_enter_draw_tab(&mut app);
+        assert_eq!(app.popup, PopupType::None);
+        assert!(app.status_message.is_some());
+    }
+
+    #[test]
+    fn test_play_tab_runs_a_whole_challenge() {
+        let mut app = App::new();
+        app.current_tab = TabType::PlayTab;
+        app.honeycomb_nodes[9].text = "Coraggioso".to_string();
+        app.list_data.misfortunes[0] = "Ferito".to_string();
+        app.list_data.misfortunes_red_balls[0] = "2".to_string();
+        let press = |app: &mut App, code| {
+            handle_normal_mode(app, KeyEvent::new(code, KeyModifiers::empty()));
+        };
+
+        // Tratto dell'archetipo, poi la sventura nel riquadro accanto
+        press(&mut app, KeyCode::Char('e'));
+        press(&mut app, KeyCode::Right);
+        press(&mut app, KeyCode::Char('e'));
+        assert_eq!((app.white_balls, app.red_balls), (1, 2));
+
+        // Difficoltà e conferma nel riquadro della prova
+        press(&mut app, KeyCode::Right);
+        assert_eq!(app.play_focus, PlayPane::Draw);
+        app.focused_section = FocusedSection::RedBalls;
+        press(&mut app, KeyCode::Char('+'));
+        assert_eq!(app.red_balls, 3);
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.focused_section, FocusedSection::DrawInput);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.popup, PopupType::ConfirmDraw);
+        assert_eq!(app.current_tab, TabType::PlayTab);
+    }
+
     #[test]
     fn test_enable_trait() {
         let mut app = App::new();
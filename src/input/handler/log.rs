@@ -0,0 +1,47 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::app::{App, PopupType};
+
+/// Gestisce gli eventi della tastiera nei filtri del log
+pub fn handle_log_filter_mode(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc | KeyCode::Enter => app.popup = PopupType::None,
+        KeyCode::Up => app.select_log_filter_field(false),
+        KeyCode::Down | KeyCode::Tab => app.select_log_filter_field(true),
+        KeyCode::Left => app.adjust_log_filter(false),
+        KeyCode::Right => app.adjust_log_filter(true),
+        KeyCode::Delete => app.clear_log_filter(),
+        KeyCode::Backspace => app.edit_log_filter(None),
+        KeyCode::Char(' ') if !app.log_filter_field.is_text() => app.adjust_log_filter(true),
+        KeyCode::Char(c) => app.edit_log_filter(Some(c)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::LogFilterField;
+    use crate::test_support::press;
+
+    #[test]
+    fn test_typing_and_flags() {
+        let mut app = App::new();
+        app.open_log_filter(LogFilterField::Query);
+        press(&mut app, handle_log_filter_mode, KeyCode::Char('m'));
+        press(&mut app, handle_log_filter_mode, KeyCode::Char(' '));
+        press(&mut app, handle_log_filter_mode, KeyCode::Char('x'));
+        press(&mut app, handle_log_filter_mode, KeyCode::Backspace);
+        assert_eq!(app.log_filter.query, "m ");
+
+        app.log_filter_field = LogFilterField::Confused;
+        press(&mut app, handle_log_filter_mode, KeyCode::Char(' '));
+        press(&mut app, handle_log_filter_mode, KeyCode::Char('q'));
+        assert_eq!(app.log_filter.confused, Some(true));
+
+        press(&mut app, handle_log_filter_mode, KeyCode::Delete);
+        assert!(!app.log_filter.is_active());
+        press(&mut app, handle_log_filter_mode, KeyCode::Enter);
+        assert_eq!(app.popup, PopupType::None);
+    }
+}
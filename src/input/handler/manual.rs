@@ -0,0 +1,67 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::app::{App, PopupType};
+
+/// Campi dell'esito manuale: bianchi e rossi del primo tentativo e del rischio
+const MANUAL_FIELDS: usize = 4;
+
+/// Gestisce gli eventi della tastiera durante l'inserimento dell'esito manuale
+pub fn handle_manual_mode(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => app.popup = PopupType::None,
+        KeyCode::Enter => {
+            if let Err(e) = app.record_manual_draw() {
+                app.status_message = Some(e);
+            }
+        }
+        KeyCode::Up => app.manual_field = app.manual_field.saturating_sub(1),
+        KeyCode::Down => app.manual_field = (app.manual_field + 1).min(MANUAL_FIELDS - 1),
+        KeyCode::Right | KeyCode::Char('+') => app.adjust_manual_result(true),
+        KeyCode::Left | KeyCode::Char('-') => app.adjust_manual_result(false),
+        KeyCode::Char(c) if c.is_ascii_digit() => {
+            app.manual_result[app.manual_field] = c.to_digit(10).unwrap_or(0) as usize;
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::press;
+
+    #[test]
+    fn test_manual_result_is_recorded() {
+        let mut app = App::new();
+        app.white_balls = 2;
+        app.red_balls = 2;
+        app.draw_count = 2;
+        app.open_manual_result();
+
+        press(&mut app, handle_manual_mode, KeyCode::Char('1'));
+        press(&mut app, handle_manual_mode, KeyCode::Down);
+        press(&mut app, handle_manual_mode, KeyCode::Right);
+        press(&mut app, handle_manual_mode, KeyCode::Enter);
+
+        assert_eq!(app.popup, PopupType::None);
+        let entry = app.history.last().unwrap();
+        assert!(entry.manual);
+        assert!(!entry.risked);
+        assert_eq!(entry.first_draw.len(), 2);
+    }
+
+    #[test]
+    fn test_invalid_manual_result_is_kept_open() {
+        let mut app = App::new();
+        app.white_balls = 1;
+        app.red_balls = 1;
+        app.open_manual_result();
+
+        press(&mut app, handle_manual_mode, KeyCode::Char('3'));
+        press(&mut app, handle_manual_mode, KeyCode::Enter);
+
+        assert_eq!(app.popup, PopupType::ManualResult);
+        assert!(app.history.is_empty());
+        assert!(app.status_message.is_some());
+    }
+}
@@ -1,32 +1,268 @@
 use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
 
-use crate::app::{App, PopupType, TabType};
+use crate::app::{App, HitId, PopupType};
 
-/// Gestisce gli eventi del mouse
+/// Righe scorse a ogni scatto della rotella
+const WHEEL_LINES: isize = 3;
+
+/// Gestisce gli eventi del mouse, risolti sugli elementi dell'ultimo frame
 pub fn handle_mouse_event(app: &mut App, mouse: MouseEvent) {
-    // Ignora eventi mouse se siamo in editing o popup
-    if app.popup != PopupType::None
-        || app.editing_node
+    let (x, y) = (mouse.column, mouse.row);
+    if let MouseEventKind::Moved = mouse.kind {
+        app.hover = Some((x, y));
+    }
+
+    if app.popup == PopupType::ContextMenu {
+        handle_context_menu_mouse(app, mouse);
+        return;
+    }
+
+    // Durante la modifica di un campo i click non spostano la selezione:
+    // si esce con Invio o Esc
+    let editing = app.editing_node
         || app.editing_list_item
         || app.editing_character_info
-    {
+        || app.narrator_field.is_some();
+    if editing && matches!(mouse.kind, MouseEventKind::Down(_)) {
         return;
     }
 
-    // Gestisci solo click sinistro
-    if let MouseEventKind::Down(MouseButton::Left) = mouse.kind {
-        handle_left_click(app, mouse.column, mouse.row);
+    match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) => app.handle_mouse_click(x, y),
+        MouseEventKind::Down(MouseButton::Right) => app.handle_right_click(x, y),
+        MouseEventKind::ScrollUp => app.handle_mouse_scroll(x, y, -WHEEL_LINES),
+        MouseEventKind::ScrollDown => app.handle_mouse_scroll(x, y, WHEEL_LINES),
+        _ => {}
+    }
+}
+
+/// Mouse sul menu contestuale: il puntatore seleziona, il click esegue o chiude
+fn handle_context_menu_mouse(app: &mut App, mouse: MouseEvent) {
+    let action = match app.hit_at(mouse.column, mouse.row) {
+        Some(HitId::MenuAction(i)) => Some(i),
+        _ => None,
+    };
+    match mouse.kind {
+        MouseEventKind::Moved => {
+            if let (Some(menu), Some(action)) = (&mut app.context_menu, action) {
+                menu.selected = action;
+            }
+        }
+        MouseEventKind::Down(_) => match (&mut app.context_menu, action) {
+            (Some(menu), Some(action)) => {
+                menu.selected = action;
+                app.run_context_action();
+            }
+            _ => app.close_context_menu(),
+        },
+        _ => {}
     }
 }
 
-/// Gestisce il click sinistro del mouse
-fn handle_left_click(app: &mut App, x: u16, y: u16) {
-    // Gestione click generale (tab, sezioni comuni)
-    app.handle_mouse_click(x, y);
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyModifiers;
+    use ratatui::{Terminal, backend::TestBackend, layout::Rect};
+
+    use super::*;
+    use crate::app::{Campaign, CharacterSection, MAX_TOKEN, TabType};
+    use crate::test_support::{ActiveScope, TempDir};
+    use crossterm::event::KeyCode;
+
+    /// Disegna un frame, così da registrare le aree cliccabili
+    fn draw(app: &mut App) {
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        terminal.draw(|f| crate::ui::ui(f, app)).unwrap();
+    }
+
+    fn area(app: &App, id: HitId) -> Rect {
+        app.hits.area(id).expect("area non registrata")
+    }
+
+    fn mouse(app: &mut App, kind: MouseEventKind, column: u16, row: u16) {
+        handle_mouse_event(
+            app,
+            MouseEvent {
+                kind,
+                column,
+                row,
+                modifiers: KeyModifiers::empty(),
+            },
+        );
+        draw(app);
+    }
+
+    #[test]
+    fn test_click_tokens_changes_counters() {
+        let mut app = App::new();
+        draw(&mut app);
+        let white = area(&app, HitId::WhiteBalls);
+        let red = area(&app, HitId::RedBalls);
+        let (before_white, before_red) = (app.white_balls, app.red_balls);
+
+        mouse(
+            &mut app,
+            MouseEventKind::Down(MouseButton::Left),
+            white.x + 1,
+            white.y + 1,
+        );
+        mouse(
+            &mut app,
+            MouseEventKind::Down(MouseButton::Left),
+            white.x + 1,
+            white.y + 1,
+        );
+        mouse(
+            &mut app,
+            MouseEventKind::Down(MouseButton::Left),
+            red.x + 1,
+            red.y + 1,
+        );
+        mouse(
+            &mut app,
+            MouseEventKind::Down(MouseButton::Right),
+            white.x + 1,
+            white.y + 1,
+        );
+        assert_eq!(
+            (app.white_balls, app.red_balls),
+            (before_white + 1, before_red + 1)
+        );
+    }
+
+    #[test]
+    fn test_click_tokens_stops_at_max() {
+        let mut app = App::new();
+        app.white_balls = MAX_TOKEN;
+        draw(&mut app);
+        let white = area(&app, HitId::WhiteBalls);
+        mouse(
+            &mut app,
+            MouseEventKind::Down(MouseButton::Left),
+            white.x + 1,
+            white.y + 1,
+        );
+        assert_eq!(app.white_balls, MAX_TOKEN);
+    }
+
+    #[test]
+    fn test_click_while_editing_keeps_the_edit() {
+        // Esc salva la scheda: si scrive in una campagna temporanea
+        let root = TempDir::new("mouse");
+        let campaign = Campaign::create(root.path(), "Prova").unwrap();
+        let _scope = ActiveScope::new(&campaign);
+        let mut app = App::new();
+        app.current_tab = TabType::CharacterSheetTab;
+        app.character_base_info.name = "Lia".into();
+        draw(&mut app);
+        let name = area(&app, HitId::CharacterName);
+        mouse(
+            &mut app,
+            MouseEventKind::Down(MouseButton::Left),
+            name.x + 1,
+            name.y + 1,
+        );
+        app.start_character_editing();
+        assert!(app.editing_character_info);
+
+        // Click altrove: la modifica resta aperta e Esc la chiude
+        let tab = area(&app, HitId::Tab(0));
+        mouse(
+            &mut app,
+            MouseEventKind::Down(MouseButton::Left),
+            tab.x,
+            tab.y,
+        );
+        assert_eq!(app.current_tab, TabType::CharacterSheetTab);
+        assert_eq!(app.selected_character_info, CharacterSection::CharacterName);
+
+        let esc = crossterm::event::KeyEvent::new(KeyCode::Esc, KeyModifiers::empty());
+        super::super::editing::handle_editing_mode(&mut app, esc);
+        assert!(!app.editing_character_info);
+        assert_eq!(app.character_base_info.name, "Lia");
+    }
+
+    #[test]
+    fn test_click_tab_switches_tab() {
+        let mut app = App::new();
+        draw(&mut app);
+        let tab = area(&app, HitId::Tab(1));
+        mouse(
+            &mut app,
+            MouseEventKind::Down(MouseButton::Left),
+            tab.x,
+            tab.y,
+        );
+        assert_eq!(app.current_tab, app.mode.tabs()[1]);
+    }
+
+    #[test]
+    fn test_click_inside_hexagon_selects_node() {
+        let mut app = App::new();
+        app.current_tab = TabType::CharacterSheetTab;
+        draw(&mut app);
+
+        // Prima riga dell'esagono: gli angoli del riquadro restano fuori
+        let top = area(&app, HitId::Node(7));
+        assert_eq!(app.hit_at(top.x - 1, top.y), None);
+        mouse(
+            &mut app,
+            MouseEventKind::Down(MouseButton::Left),
+            top.x + 3,
+            top.y + 2,
+        );
+        assert_eq!(app.selected_node, Some(7));
+    }
+
+    #[test]
+    fn test_popup_blocks_clicks_below() {
+        let mut app = App::new();
+        draw(&mut app);
+        let white = area(&app, HitId::WhiteBalls);
+        let before = app.white_balls;
+
+        app.popup = PopupType::LogFilter;
+        draw(&mut app);
+        mouse(
+            &mut app,
+            MouseEventKind::Down(MouseButton::Left),
+            white.x + 1,
+            white.y + 1,
+        );
+        assert_eq!(app.white_balls, before);
+    }
+
+    #[test]
+    fn test_right_click_opens_misfortune_menu() {
+        let mut app = App::new();
+        app.current_tab = TabType::AdditionalInfoTab;
+        app.list_data.misfortunes[1] = "Ferito".into();
+        app.list_data.misfortunes_red_balls[1] = "1".into();
+        let before = app.red_balls;
+        draw(&mut app);
+
+        let misfortune = area(&app, HitId::Misfortune(1));
+        let (x, y) = (misfortune.x + 1, misfortune.y + 1);
+        mouse(&mut app, MouseEventKind::Down(MouseButton::Right), x, y);
+        assert_eq!(app.popup, PopupType::ContextMenu);
+
+        // Primo elemento del menu: attiva la sventura
+        let action = area(&app, HitId::MenuAction(0));
+        mouse(&mut app, MouseEventKind::Moved, action.x + 1, action.y);
+        mouse(
+            &mut app,
+            MouseEventKind::Down(MouseButton::Left),
+            action.x + 1,
+            action.y,
+        );
+        assert_eq!(app.popup, PopupType::None);
+        assert_eq!(app.red_balls, before + 1);
 
-    // Gestione specifica per tab personaggio
-    if app.current_tab == TabType::CharacterSheetTab {
-        app.handle_character_click(x, y);
-        app.handle_node_click(x, y);
+        // Un click fuori chiude il menu senza fare nulla
+        mouse(&mut app, MouseEventKind::Down(MouseButton::Right), x, y);
+        assert_eq!(app.popup, PopupType::ContextMenu);
+        mouse(&mut app, MouseEventKind::Down(MouseButton::Left), 119, 39);
+        assert_eq!(app.popup, PopupType::None);
+        assert_eq!(app.red_balls, before + 1);
     }
 }
@@ -0,0 +1,148 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::app::{App, NarratorField, TabType};
+
+use super::keyboard;
+
+/// Gestisce gli eventi della tastiera nella plancia del narratore
+pub fn handle_narrator_mode(app: &mut App, key: KeyEvent) -> bool {
+    match app.current_tab {
+        TabType::NarratorChallengeTab => handle_challenge_tab(app, key.code),
+        TabType::NarratorCastTab => handle_cast_tab(app, key.code),
+        TabType::NarratorPlayersTab => handle_players_tab(app, key.code),
+        TabType::NarratorNotesTab if key.code == KeyCode::Enter => {
+            app.start_narrator_editing(NarratorField::SceneNotes);
+        }
+        _ => {}
+    }
+
+    // Uscita, cambio tab, diario, campagne e scorrimento del log restano quelli del giocatore
+    match key.code {
+        KeyCode::Char('q')
+        | KeyCode::Char('Q')
+        | KeyCode::Char('j')
+        | KeyCode::Char('J')
+        | KeyCode::Char('o')
+        | KeyCode::Char('O')
+        | KeyCode::Tab => keyboard::handle_normal_mode(app, key),
+        KeyCode::Up | KeyCode::Down if app.current_tab == TabType::LogTab => {
+            keyboard::handle_normal_mode(app, key)
+        }
+        _ => false,
+    }
+}
+
+/// Tab della sfida: difficoltà, PNG e token da estrarre
+fn handle_challenge_tab(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Left => app.narrator_focus = app.narrator_focus.prev(),
+        KeyCode::Right => app.narrator_focus = app.narrator_focus.next(),
+        KeyCode::Up => app.adjust_challenge(true),
+        KeyCode::Down => app.adjust_challenge(false),
+        KeyCode::Enter => {
+            if let Err(e) = app.prepare_npc_draw() {
+                app.status_message = Some(e);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Tab di PNG e minacce
+fn handle_cast_tab(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Left | KeyCode::Right => {
+            app.cast_section = app.cast_section.next();
+            app.selected_cast = 0;
+        }
+        KeyCode::Up => app.selected_cast = app.selected_cast.saturating_sub(1),
+        KeyCode::Down if app.selected_cast + 1 < app.cast_len() => app.selected_cast += 1,
+        KeyCode::Char('a') | KeyCode::Char('A') => app.add_cast_entry(),
+        KeyCode::Char('x') | KeyCode::Char('X') => app.remove_cast_entry(),
+        KeyCode::Char('+') => app.adjust_cast_tokens(true),
+        KeyCode::Char('-') => app.adjust_cast_tokens(false),
+        KeyCode::Char('e') | KeyCode::Char('E') => app.toggle_threat(),
+        KeyCode::Enter => {
+            if let Some(field) = cast_field(app, false) {
+                app.start_narrator_editing(field);
+            }
+        }
+        KeyCode::Char('d') | KeyCode::Char('D') => {
+            if let Some(field) = cast_field(app, true) {
+                app.start_narrator_editing(field);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Campo (nome o note) dell'elemento selezionato nel tab di PNG e minacce
+fn cast_field(app: &App, notes: bool) -> Option<NarratorField> {
+    use crate::app::CastSection::*;
+    if app.selected_cast >= app.cast_len() {
+        return None;
+    }
+    let idx = app.selected_cast;
+    Some(match (app.cast_section, notes) {
+        (Npcs, false) => NarratorField::NpcName(idx),
+        (Npcs, true) => NarratorField::NpcNotes(idx),
+        (Threats, false) => NarratorField::ThreatName(idx),
+        (Threats, true) => NarratorField::ThreatNotes(idx),
+    })
+}
+
+/// Tab dei giocatori
+fn handle_players_tab(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Up => app.selected_player = app.selected_player.saturating_sub(1),
+        KeyCode::Down if app.selected_player + 1 < app.narrator.players.len() => {
+            app.selected_player += 1;
+        }
+        KeyCode::Char('i') | KeyCode::Char('I') => {
+            app.start_narrator_editing(NarratorField::PlayerSheet);
+        }
+        KeyCode::Char('u') | KeyCode::Char('U') => {
+            app.status_message = Some(match app.reload_players() {
+                Ok(()) => "Schede aggiornate".to_string(),
+                Err(e) => e,
+            });
+        }
+        KeyCode::Char('c') | KeyCode::Char('C') => app.toggle_player_condition(false),
+        KeyCode::Char('a') | KeyCode::Char('A') => app.toggle_player_condition(true),
+        KeyCode::Char('x') | KeyCode::Char('X') => app.remove_player(),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyModifiers;
+
+    use super::*;
+    use crate::app::{AppMode, PopupType};
+
+    #[test]
+    fn test_tab_cycles_narrator_tabs() {
+        let mut app = App::new();
+        app.mode = AppMode::Narrator;
+        app.current_tab = TabType::NarratorNotesTab;
+
+        let key = KeyEvent::new(KeyCode::Tab, KeyModifiers::empty());
+        handle_narrator_mode(&mut app, key);
+
+        assert_eq!(app.current_tab, TabType::LogTab);
+    }
+
+    #[test]
+    fn test_enter_without_npc_reports_error() {
+        let mut app = App::new();
+        app.mode = AppMode::Narrator;
+        app.current_tab = TabType::NarratorChallengeTab;
+
+        let key = KeyEvent::new(KeyCode::Enter, KeyModifiers::empty());
+        handle_narrator_mode(&mut app, key);
+
+        assert_eq!(app.popup, PopupType::None);
+        assert!(app.status_message.is_some());
+    }
+}
@@ -0,0 +1,20 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::app::App;
+
+/// Gestisce gli eventi della tastiera nella palette dei comandi
+pub fn handle_palette_mode(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => app.cancel_palette(),
+        KeyCode::Enter => {
+            if let Err(e) = app.confirm_palette() {
+                app.status_message = Some(e);
+            }
+        }
+        KeyCode::Up => app.select_palette_entry(false),
+        KeyCode::Down => app.select_palette_entry(true),
+        KeyCode::Backspace => app.edit_palette_query(None),
+        KeyCode::Char(c) => app.edit_palette_query(Some(c)),
+        _ => {}
+    }
+}
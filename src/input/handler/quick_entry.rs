@@ -0,0 +1,59 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::app::{App, PopupType};
+
+/// Lunghezza massima della riga di inserimento rapido
+const QUICK_ENTRY_LENGTH: usize = 200;
+
+/// Gestisce gli eventi della tastiera nell'inserimento rapido della prova
+pub fn handle_quick_entry_mode(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => app.popup = PopupType::None,
+        KeyCode::Enter => {
+            if let Err(e) = app.confirm_quick_entry() {
+                app.status_message = Some(e);
+            }
+        }
+        KeyCode::Tab => app.complete_quick_entry(),
+        KeyCode::Backspace => {
+            app.quick_entry.pop();
+        }
+        KeyCode::Char(c) if app.quick_entry.chars().count() < QUICK_ENTRY_LENGTH => {
+            app.quick_entry.push(c);
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyModifiers;
+
+    use super::*;
+
+    #[test]
+    fn test_type_complete_and_confirm() {
+        let mut app = App::new();
+        app.honeycomb_nodes[9].text = "Coraggioso".to_string();
+        app.open_quick_entry();
+        let press = |app: &mut App, code| {
+            handle_quick_entry_mode(app, KeyEvent::new(code, KeyModifiers::empty()));
+        };
+
+        for c in "tratti co".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Tab);
+        for c in "; dif 2".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Enter);
+        assert!(app.status_message.is_some());
+        assert_eq!(app.popup, PopupType::QuickEntry);
+
+        app.quick_entry = app.quick_entry.replace("dif", "diff");
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.popup, PopupType::None);
+        assert_eq!((app.white_balls, app.red_balls), (1, 2));
+    }
+}
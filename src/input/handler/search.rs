@@ -0,0 +1,56 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::app::{App, PopupType};
+
+/// Gestisce gli eventi della tastiera durante la ricerca dei tratti
+pub fn handle_search_mode(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => app.popup = PopupType::None,
+        KeyCode::Enter => app.confirm_trait_search(),
+        KeyCode::Up => app.search_selected = app.search_selected.saturating_sub(1),
+        KeyCode::Down => {
+            let results = app.search_traits(&app.search_query).len();
+            if app.search_selected + 1 < results {
+                app.search_selected += 1;
+            }
+        }
+        KeyCode::Char(c) => {
+            app.search_query.push(c);
+            app.search_selected = 0;
+        }
+        KeyCode::Backspace => {
+            app.search_query.pop();
+            app.search_selected = 0;
+        }
+        _ => {}
+    }
+}
+
+/// Lunghezza massima della descrizione di una prova
+const CHALLENGE_DESCRIPTION_LENGTH: usize = 100;
+
+/// Gestisce gli eventi della tastiera nel selettore dei tratti suggeriti
+pub fn handle_picker_mode(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => app.popup = PopupType::None,
+        KeyCode::Enter => app.toggle_picked_trait(),
+        KeyCode::Up => app.picker_selected = app.picker_selected.saturating_sub(1),
+        KeyCode::Down => {
+            let results = app.suggest_traits().len();
+            if app.picker_selected + 1 < results {
+                app.picker_selected += 1;
+            }
+        }
+        KeyCode::Char(c)
+            if app.challenge_description.chars().count() < CHALLENGE_DESCRIPTION_LENGTH =>
+        {
+            app.challenge_description.push(c);
+            app.picker_selected = 0;
+        }
+        KeyCode::Backspace => {
+            app.challenge_description.pop();
+            app.picker_selected = 0;
+        }
+        _ => {}
+    }
+}
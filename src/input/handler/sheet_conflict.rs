@@ -0,0 +1,37 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::app::{App, PopupType};
+
+/// Gestisce gli eventi della tastiera nel confronto tra scheda e file
+pub fn handle_sheet_conflict_mode(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Char('d') | KeyCode::Char('D') => app.keep_disk_sheet(),
+        KeyCode::Char('m') | KeyCode::Char('M') => app.keep_my_sheet(),
+        // Rimanda la scelta: il confronto torna al prossimo salvataggio
+        KeyCode::Esc => app.popup = PopupType::None,
+        KeyCode::Up => app.sheet_conflict_scroll = app.sheet_conflict_scroll.saturating_sub(1),
+        KeyCode::Down => app.sheet_conflict_scroll = app.sheet_conflict_scroll.saturating_add(1),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::press;
+
+    #[test]
+    fn test_scroll_and_postpone() {
+        let mut app = App::new();
+        app.popup = PopupType::SheetConflict;
+        press(&mut app, handle_sheet_conflict_mode, KeyCode::Up);
+        assert_eq!(app.sheet_conflict_scroll, 0);
+        press(&mut app, handle_sheet_conflict_mode, KeyCode::Down);
+        press(&mut app, handle_sheet_conflict_mode, KeyCode::Down);
+        assert_eq!(app.sheet_conflict_scroll, 2);
+        press(&mut app, handle_sheet_conflict_mode, KeyCode::Char('x'));
+        assert_eq!(app.popup, PopupType::SheetConflict);
+        press(&mut app, handle_sheet_conflict_mode, KeyCode::Esc);
+        assert_eq!(app.popup, PopupType::None);
+    }
+}
@@ -0,0 +1,54 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::app::App;
+
+/// Gestisce gli eventi della tastiera durante la creazione guidata
+pub fn handle_wizard_mode(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => app.close_wizard(),
+        KeyCode::Enter => app.wizard_confirm(),
+        _ => {
+            let Some(wizard) = app.wizard.as_mut() else {
+                return;
+            };
+            match key.code {
+                KeyCode::Char(c) => wizard.push_char(c),
+                KeyCode::Backspace => wizard.pop_char(),
+                KeyCode::Up => wizard.prev_field(),
+                KeyCode::Down | KeyCode::Tab => wizard.next_field(),
+                KeyCode::Left => wizard.back(),
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyModifiers;
+
+    use super::*;
+    use crate::app::{CharacterWizard, PopupType, WizardStep};
+
+    #[test]
+    fn test_wizard_typing_and_back() {
+        let mut app = App::new();
+        app.wizard = Some(CharacterWizard {
+            step: WizardStep::Archetype,
+            ..Default::default()
+        });
+        app.popup = PopupType::Wizard;
+
+        handle_wizard_mode(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('M'), KeyModifiers::empty()),
+        );
+        assert_eq!(app.wizard.as_ref().unwrap().nodes[9], "M");
+
+        handle_wizard_mode(
+            &mut app,
+            KeyEvent::new(KeyCode::Left, KeyModifiers::empty()),
+        );
+        assert_eq!(app.wizard.as_ref().unwrap().step, WizardStep::Identity);
+    }
+}
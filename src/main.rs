@@ -1,23 +1,130 @@
 mod app;
 mod input;
+mod rpc;
+mod session;
 mod terminal;
+#[cfg(test)]
+mod test_support;
 mod ui;
+mod watch;
+mod web;
 
 /// Entry point dell'applicazione
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Inizializza il terminale
-    terminal::setup()?;
+    let args: Vec<String> = std::env::args().collect();
+
+    // La campagna va attivata prima di caricare i dati dell'applicazione
+    let campaign = match campaign_arg(&args) {
+        Some(arg) => Some(open_campaign(&arg)?),
+        None => None,
+    };
 
     // Crea l'applicazione e avvia il loop principale
     let mut app = app::App::new();
+    app.campaign = campaign;
+    app.load_campaign_characters();
+    let rpc = args.iter().any(|arg| arg == "--rpc");
+    if args
+        .iter()
+        .any(|arg| arg == "--narratore" || arg == "--narrator")
+    {
+        // Plancia del narratore al posto della scheda
+        app.start_narrator();
+    } else if app.is_sheet_empty() && !rpc {
+        // Se la scheda è vuota guida la creazione del personaggio
+        app.start_wizard();
+    }
+
+    // Sessione condivisa con altre istanze, avviata prima del terminale
+    // così un errore di rete resta leggibile
+    let session = match session::SessionConfig::from_args(&args) {
+        Some(config) => {
+            // Con `--autorevole` l'host estrae tutte le prove della sessione
+            app.authoritative = matches!(config, session::SessionConfig::Host(_))
+                && args
+                    .iter()
+                    .any(|arg| arg == "--autorevole" || arg == "--authoritative");
+            Some(session::Session::start(&config, &mut app)?)
+        }
+        None => None,
+    };
+
+    // Visualizzatore web in sola lettura, es. per una sorgente browser di OBS
+    let web = match web::WebServer::port_from_args(&args) {
+        Some(port) => {
+            let origin = web::WebServer::origin_from_args(&args)?;
+            let server = web::WebServer::bind(port, origin)?;
+            app.status_message = Some(format!(
+                "Visualizzatore web su http://{}",
+                server.local_addr()
+            ));
+            Some(server)
+        }
+        None => None,
+    };
+
+    // Modifiche esterne al file della scheda; con `--polling` il file viene
+    // controllato a intervalli, es. su cartelle di rete o sincronizzate
+    let polling = args.iter().any(|arg| arg == "--polling");
+    let mut watcher = match app.mode {
+        app::AppMode::Player => watch::SheetWatcher::start(polling).ok(),
+        app::AppMode::Narrator => None,
+    };
+
+    // Automazione su stdin/stdout, senza terminale
+    if rpc {
+        return Ok(rpc::run(
+            &mut app,
+            session.as_ref(),
+            web.as_ref(),
+            watcher.as_mut(),
+        )?);
+    }
+
+    // Inizializza il terminale
+    terminal::setup()?;
+
     // Cleanup è gestito nel match del risultato
-    run_app(&mut app)
+    run_app(&mut app, session.as_ref(), web.as_ref(), watcher.as_mut())
+}
+
+/// Valore di `--campagna <cartella o nome>`
+fn campaign_arg(args: &[String]) -> Option<String> {
+    let pos = args
+        .iter()
+        .position(|arg| arg == "--campagna" || arg == "--campaign")?;
+    args.get(pos + 1).cloned()
+}
+
+/// Apre e attiva una campagna indicata per cartella o per nome in `campagne/`
+fn open_campaign(arg: &str) -> std::io::Result<app::Campaign> {
+    let path = std::path::Path::new(arg);
+    let campaign = app::Campaign::open(path)
+        .or_else(|_| app::Campaign::open(&std::path::Path::new(app::CAMPAIGNS_DIR).join(arg)))?;
+    campaign.activate();
+    Ok(campaign)
 }
 
 /// Loop principale dell'applicazione
-fn run_app(app: &mut app::App) -> Result<(), Box<dyn std::error::Error>> {
+fn run_app(
+    app: &mut app::App,
+    session: Option<&session::Session>,
+    web: Option<&web::WebServer>,
+    mut watcher: Option<&mut watch::SheetWatcher>,
+) -> Result<(), Box<dyn std::error::Error>> {
     ratatui::run(|terminal| {
         loop {
+            if let Some(watcher) = watcher.as_deref_mut() {
+                watcher.sync(app);
+            }
+            // Scambia i messaggi con le altre istanze
+            if let Some(session) = session {
+                session.sync(app);
+            }
+            if let Some(web) = web {
+                web.publish(app);
+            }
+
             // Renderizza l'UI
             let _ = terminal.draw(|frame| ui::ui(frame, app));
 
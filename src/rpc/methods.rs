@@ -0,0 +1,196 @@
+use serde_json::{Value, json};
+
+use super::views;
+use super::{INVALID_PARAMS, METHOD_NOT_FOUND, RpcError};
+use crate::app::{Action, App, HoneycombNode, MAX_DRAW, MAX_TOKEN, MIN_DRAW, NodeField, PopupType};
+
+/// Esegue un metodo con i suoi parametri
+pub fn call(app: &mut App, method: &str, params: &Value) -> Result<Value, RpcError> {
+    match method {
+        "get_sheet" => Ok(views::sheet(app)),
+        "get_history" => Ok(views::history(app, optional_count(params, "last")?)),
+        "set_node" => set_node(app, params),
+        "resolve_conflict" => {
+            if app.sheet_conflicts.is_empty() {
+                return Err("Nessun conflitto da risolvere".to_string().into());
+            }
+            match params.get("keep").and_then(Value::as_str) {
+                Some("disk") => app.keep_disk_sheet(),
+                Some("mine") => app.keep_my_sheet(),
+                _ => return Err(invalid("«keep» deve essere \"disk\" o \"mine\"")),
+            }
+            Ok(views::sheet(app))
+        }
+        "toggle_trait" => {
+            let idx = match params.get("name").and_then(Value::as_str) {
+                Some(name) => app.resolve_trait(name)?,
+                None => index(params, app.honeycomb_nodes.len())?,
+            };
+            app.run_action(Action::ToggleTrait(idx))?;
+            Ok(json!({ "used_traits": app.used_traits, "white_balls": app.white_balls }))
+        }
+        "set_difficulty" => {
+            let tokens = count(params, "tokens")?;
+            if tokens > MAX_TOKEN {
+                return Err(invalid(format!(
+                    "«tokens» deve essere un numero da 0 a {}",
+                    MAX_TOKEN
+                )));
+            }
+            app.run_action(Action::SetRedBalls(tokens))?;
+            Ok(json!({ "red_balls": app.red_balls }))
+        }
+        "draw" => draw(app, params),
+        "risk" => {
+            if app.popup != PopupType::ConfirmRisk {
+                return Err("Nessuna prova attende la decisione sul rischio"
+                    .to_string()
+                    .into());
+            }
+            let risk = params.get("risk").and_then(Value::as_bool).unwrap_or(true);
+            if risk {
+                app.perform_risk_draw();
+            } else {
+                app.cancel_draw();
+            }
+            Ok(last_draw(app))
+        }
+        _ => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("Metodo sconosciuto: {}", method),
+        )),
+    }
+}
+
+/// Cambia titolo, descrizione o tag di un nodo e salva la scheda
+fn set_node(app: &mut App, params: &Value) -> Result<Value, RpcError> {
+    // Salvando ora si scriverebbe sopra le modifiche esterne ancora in sospeso
+    if !app.sheet_conflicts.is_empty() {
+        return Err(
+            "La scheda è cambiata anche sul file: usa prima resolve_conflict"
+                .to_string()
+                .into(),
+        );
+    }
+    let idx = index(params, app.honeycomb_nodes.len())?;
+    let tags = match params.get("tags") {
+        Some(Value::Array(tags)) => Some(
+            tags.iter()
+                .map(|t| t.as_str().map(str::to_string))
+                .collect::<Option<Vec<String>>>()
+                .ok_or_else(|| invalid("«tags» deve essere un elenco di testi"))?
+                .join(", "),
+        ),
+        Some(Value::String(tags)) => Some(tags.clone()),
+        Some(_) => return Err(invalid("«tags» deve essere un elenco di testi")),
+        None => None,
+    };
+
+    let fields = [
+        (NodeField::Title, text(params, "text")?),
+        (NodeField::Description, text(params, "description")?),
+        (NodeField::Tags, tags),
+    ];
+    for (field, value) in &fields {
+        if let Some(value) = value
+            && value.chars().count() > field.length()
+        {
+            return Err(invalid(format!(
+                "{}: al massimo {} caratteri",
+                field,
+                field.length()
+            )));
+        }
+    }
+    if let Some(text) = &fields[0].1 {
+        app.check_new_trait(idx, text)?;
+    }
+    for (field, value) in fields {
+        if let Some(value) = value {
+            app.set_node_field(idx, field, &value)?;
+        }
+    }
+
+    let node: &HoneycombNode = &app.honeycomb_nodes[idx];
+    Ok(json!({
+        "index": idx,
+        "text": node.text,
+        "description": node.description,
+        "tags": node.tags,
+    }))
+}
+
+/// Prima estrazione; se non servono altre decisioni la prova è già nella cronologia
+fn draw(app: &mut App, params: &Value) -> Result<Value, RpcError> {
+    if let Some(count) = optional_count(params, "count")? {
+        if !(MIN_DRAW..=MAX_DRAW).contains(&count) {
+            return Err(invalid(format!(
+                "«count» va da {} a {}",
+                MIN_DRAW, MAX_DRAW
+            )));
+        }
+        app.draw_count = count;
+    }
+    if app.popup == PopupType::ConfirmRisk {
+        return Err("Una prova attende la decisione sul rischio"
+            .to_string()
+            .into());
+    }
+    if app.white_balls == 0 || app.red_balls == 0 {
+        return Err("Servono almeno un token bianco e uno rosso"
+            .to_string()
+            .into());
+    }
+
+    let drawn = app.history.len();
+    app.perform_first_draw();
+    if app.popup == PopupType::ConfirmRisk {
+        Ok(json!({ "first_draw": app.current_first_draw, "awaiting_risk": true }))
+    } else if app.history.len() > drawn {
+        Ok(last_draw(app))
+    } else {
+        // L'estrazione spetta all'host: l'esito arriva con la notifica `draw`
+        Ok(json!({ "pending": true }))
+    }
+}
+
+fn last_draw(app: &App) -> Value {
+    app.history.last().map_or(Value::Null, |entry| json!(entry))
+}
+
+fn invalid(message: impl Into<String>) -> RpcError {
+    RpcError::new(INVALID_PARAMS, message)
+}
+
+/// Posizione di un nodo nella griglia
+fn index(params: &Value, len: usize) -> Result<usize, RpcError> {
+    match params.get("index").and_then(Value::as_u64) {
+        Some(i) if (i as usize) < len => Ok(i as usize),
+        _ => Err(invalid(format!(
+            "«index» deve essere un numero da 0 a {}",
+            len - 1
+        ))),
+    }
+}
+
+fn count(params: &Value, key: &str) -> Result<usize, RpcError> {
+    optional_count(params, key)?.ok_or_else(|| invalid(format!("Manca «{}»", key)))
+}
+
+fn optional_count(params: &Value, key: &str) -> Result<Option<usize>, RpcError> {
+    match params.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => value
+            .as_u64()
+            .map(|n| Some(n as usize))
+            .ok_or_else(|| invalid(format!("«{}» deve essere un numero", key))),
+    }
+}
+
+fn text(params: &Value, key: &str) -> Result<Option<String>, RpcError> {
+    match params.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(text)) => Ok(Some(text.clone())),
+        Some(_) => Err(invalid(format!("«{}» deve essere un testo", key))),
+    }
+}
@@ -0,0 +1,372 @@
+use std::io::{self, BufRead, Write};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use serde_json::{Value, json};
+
+use crate::app::App;
+use crate::session::Session;
+use crate::watch::SheetWatcher;
+use crate::web::WebServer;
+
+mod methods;
+pub mod views;
+
+/// Intervallo massimo di attesa di una richiesta prima di sincronizzare la sessione
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Errori previsti da JSON-RPC 2.0
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+/// Operazione rifiutata dall'applicazione
+pub const APP_ERROR: i64 = -32000;
+
+/// Errore restituito al chiamante
+#[derive(Debug, Clone, PartialEq)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RpcError {
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        RpcError {
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn to_json(&self, id: Value) -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": self.code, "message": self.message },
+        })
+    }
+}
+
+/// Errore dell'applicazione, es. una prova senza token
+impl From<String> for RpcError {
+    fn from(message: String) -> Self {
+        RpcError::new(APP_ERROR, message)
+    }
+}
+
+/// Interfaccia JSON-RPC 2.0 sull'applicazione, senza terminale.
+///
+/// Ogni richiesta e ogni risposta occupano una riga JSON. Le prove concluse
+/// e i messaggi di stato arrivano come notifiche `draw` e `status`.
+pub struct RpcServer {
+    /// Prove della cronologia già notificate
+    notified: usize,
+}
+
+impl RpcServer {
+    pub fn new(app: &App) -> Self {
+        RpcServer {
+            notified: app.history.len(),
+        }
+    }
+
+    /// Risposta a una riga ricevuta; le notifiche del chiamante non ne hanno
+    pub fn handle(&mut self, app: &mut App, line: &str) -> Option<Value> {
+        let request: Value = match serde_json::from_str(line) {
+            Ok(request) => request,
+            Err(e) => {
+                return Some(
+                    RpcError::new(PARSE_ERROR, format!("JSON non valido: {}", e))
+                        .to_json(Value::Null),
+                );
+            }
+        };
+
+        match request {
+            Value::Array(batch) if !batch.is_empty() => {
+                let responses: Vec<Value> = batch
+                    .into_iter()
+                    .filter_map(|request| self.handle_request(app, request))
+                    .collect();
+                (!responses.is_empty()).then_some(Value::Array(responses))
+            }
+            request => self.handle_request(app, request),
+        }
+    }
+
+    fn handle_request(&mut self, app: &mut App, request: Value) -> Option<Value> {
+        let id = request.get("id").cloned();
+        let method = request.get("method").and_then(Value::as_str);
+        let (Some(method), Some("2.0")) = (method, request.get("jsonrpc").and_then(Value::as_str))
+        else {
+            return Some(
+                RpcError::new(INVALID_REQUEST, "Richiesta JSON-RPC 2.0 non valida")
+                    .to_json(id.unwrap_or(Value::Null)),
+            );
+        };
+        let params = request.get("params").cloned().unwrap_or(Value::Null);
+
+        let result = methods::call(app, method, &params);
+        let id = id?;
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(e) => e.to_json(id),
+        })
+    }
+
+    /// Notifiche per quanto è successo dall'ultima chiamata
+    pub fn events(&mut self, app: &mut App) -> Vec<Value> {
+        let mut events: Vec<Value> = app.history[self.notified.min(app.history.len())..]
+            .iter()
+            .map(|entry| notification("draw", json!(entry)))
+            .collect();
+        self.notified = app.history.len();
+
+        if let Some(message) = app.status_message.take() {
+            events.push(notification("status", json!({ "message": message })));
+        }
+        events
+    }
+}
+
+fn notification(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+/// Avvia l'interfaccia su stdin e stdout, fino alla chiusura di stdin
+pub fn run(
+    app: &mut App,
+    session: Option<&Session>,
+    web: Option<&WebServer>,
+    mut watcher: Option<&mut SheetWatcher>,
+) -> io::Result<()> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            let Ok(line) = line else { break };
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut server = RpcServer::new(app);
+    let mut stdout = io::stdout().lock();
+    loop {
+        let mut output = Vec::new();
+        match rx.recv_timeout(POLL_INTERVAL) {
+            Ok(line) if line.trim().is_empty() => {}
+            Ok(line) => output.extend(server.handle(app, &line)),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+
+        // Scambia i messaggi con le altre istanze
+        if let Some(session) = session {
+            session.sync(app);
+        }
+        if let Some(watcher) = watcher.as_deref_mut() {
+            watcher.sync(app);
+        }
+        if let Some(web) = web {
+            web.publish(app);
+        }
+        output.extend(server.events(app));
+
+        for value in output {
+            writeln!(stdout, "{}", value)?;
+        }
+        stdout.flush()?;
+    }
+
+    // Senza più richieste i conflitti non si possono risolvere
+    if let Some(path) = app.save_unresolved_sheet() {
+        eprintln!("Scheda con conflitti salvata in {}", path.display());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod rpc_tests {
+    use serde_json::{Value, json};
+
+    use crate::app::{App, Campaign, MAX_TOKEN};
+    use crate::rpc::*;
+    use crate::test_support::{ActiveScope, TempDir};
+
+    fn call(server: &mut RpcServer, app: &mut App, method: &str, params: Value) -> Value {
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        server.handle(app, &request.to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_challenge_round_trip() {
+        let mut app = App::new();
+        app.honeycomb_nodes[9].text = "Coraggioso".into();
+        let mut server = RpcServer::new(&app);
+
+        let res = call(
+            &mut server,
+            &mut app,
+            "toggle_trait",
+            json!({ "name": "cora" }),
+        );
+        assert_eq!(res["result"]["white_balls"], 1);
+        let res = call(
+            &mut server,
+            &mut app,
+            "set_difficulty",
+            json!({ "tokens": 6 }),
+        );
+        assert_eq!(res["result"]["red_balls"], 6);
+
+        let res = call(&mut server, &mut app, "draw", json!({ "count": 1 }));
+        let first = &res["result"]["first_draw"];
+        assert_eq!(first.as_array().unwrap().len(), 1);
+        assert_eq!(res["result"]["awaiting_risk"], true);
+        assert!(server.events(&mut app).is_empty());
+
+        let res = call(&mut server, &mut app, "risk", json!({ "risk": true }));
+        assert_eq!(res["result"]["risked"], true);
+        let events = server.events(&mut app);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["method"], "draw");
+        assert!(events[0].get("id").is_none());
+
+        let res = call(&mut server, &mut app, "get_history", json!({ "last": 5 }));
+        let history = res["result"].as_array().unwrap();
+        assert_eq!(history.len(), 1);
+        let drawn = history[0]["first_draw"].as_array().unwrap().len()
+            + history[0]["risk_draw"].as_array().unwrap().len();
+        assert_eq!(drawn, 5);
+        assert_eq!(app.history[0].trait_names, vec!["Coraggioso"]);
+    }
+
+    #[test]
+    fn test_errors_follow_json_rpc() {
+        let mut app = App::new();
+        let mut server = RpcServer::new(&app);
+
+        let res = server.handle(&mut app, "{ rotto").unwrap();
+        assert_eq!(res["error"]["code"], PARSE_ERROR);
+        let res = server
+            .handle(&mut app, r#"{"id": 3, "method": "draw"}"#)
+            .unwrap();
+        assert_eq!(res["error"]["code"], INVALID_REQUEST);
+        assert_eq!(res["id"], 3);
+
+        let res = call(&mut server, &mut app, "vola", Value::Null);
+        assert_eq!(res["error"]["code"], METHOD_NOT_FOUND);
+        let res = call(
+            &mut server,
+            &mut app,
+            "set_difficulty",
+            json!({ "tokens": "tre" }),
+        );
+        assert_eq!(res["error"]["code"], INVALID_PARAMS);
+        let res = call(&mut server, &mut app, "draw", Value::Null);
+        assert_eq!(res["error"]["code"], APP_ERROR);
+        let res = call(&mut server, &mut app, "risk", Value::Null);
+        assert_eq!(res["error"]["code"], APP_ERROR);
+    }
+
+    #[test]
+    fn test_oversized_difficulty_is_refused() {
+        let mut app = App::new();
+        let mut server = RpcServer::new(&app);
+        let res = call(
+            &mut server,
+            &mut app,
+            "set_difficulty",
+            json!({ "tokens": 1_000_000_000_000u64 }),
+        );
+        assert_eq!(res["error"]["code"], INVALID_PARAMS);
+        assert_eq!(app.red_balls, 0);
+
+        let res = call(
+            &mut server,
+            &mut app,
+            "set_difficulty",
+            json!({ "tokens": MAX_TOKEN }),
+        );
+        assert_eq!(res["result"]["red_balls"], MAX_TOKEN);
+    }
+
+    #[test]
+    fn test_conflicts_block_edits_until_resolved() {
+        let root = TempDir::new("rpc-conflict");
+        let campaign = Campaign::create(root.path(), "Prova").unwrap();
+        let _scope = ActiveScope::new(&campaign);
+
+        let mut app = App::new();
+        app.character_base_info.name = "Lia".into();
+        app.write_sheet();
+        let mut external = app.clone();
+        external.character_base_info.name = "Aria".into();
+        let contents = external.sheet_contents(app.sheet_format());
+        app.character_base_info.name = "Bea".into();
+        app.sheet_changed_on_disk(contents);
+        assert_eq!(app.sheet_conflicts.len(), 1);
+
+        let mut server = RpcServer::new(&app);
+        let res = call(&mut server, &mut app, "get_sheet", Value::Null);
+        assert_eq!(res["result"]["conflicts"][0]["disk"], "Aria");
+        let edit = json!({ "index": 9, "text": "Cartografa" });
+        let res = call(&mut server, &mut app, "set_node", edit.clone());
+        assert_eq!(res["error"]["code"], APP_ERROR);
+        let res = call(
+            &mut server,
+            &mut app,
+            "resolve_conflict",
+            json!({ "keep": "entrambe" }),
+        );
+        assert_eq!(res["error"]["code"], INVALID_PARAMS);
+
+        let res = call(
+            &mut server,
+            &mut app,
+            "resolve_conflict",
+            json!({ "keep": "disk" }),
+        );
+        assert_eq!(res["result"]["name"], "Aria");
+        assert!(app.sheet_conflicts.is_empty());
+        let res = call(&mut server, &mut app, "set_node", edit);
+        assert_eq!(res["result"]["text"], "Cartografa");
+        let res = call(
+            &mut server,
+            &mut app,
+            "resolve_conflict",
+            json!({ "keep": "mine" }),
+        );
+        assert_eq!(res["error"]["code"], APP_ERROR);
+    }
+
+    #[test]
+    fn test_notifications_and_batches() {
+        let mut app = App::new();
+        let mut server = RpcServer::new(&app);
+
+        // Senza id non c'è risposta, ma l'operazione viene eseguita
+        let notify =
+            json!({ "jsonrpc": "2.0", "method": "set_difficulty", "params": { "tokens": 3 } });
+        assert_eq!(server.handle(&mut app, &notify.to_string()), None);
+        assert_eq!(app.red_balls, 3);
+
+        let batch = json!([
+            notify,
+            { "jsonrpc": "2.0", "id": "a", "method": "get_sheet" },
+        ]);
+        let res = server.handle(&mut app, &batch.to_string()).unwrap();
+        let res = res.as_array().unwrap();
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0]["id"], "a");
+        assert_eq!(res[0]["result"]["nodes"].as_array().unwrap().len(), 19);
+        assert_eq!(res[0]["result"]["challenge"]["red_balls"], 3);
+
+        app.status_message = Some("Ciao".into());
+        let events = server.events(&mut app);
+        assert_eq!(events[0]["method"], "status");
+        assert_eq!(events[0]["params"]["message"], "Ciao");
+    }
+}
@@ -0,0 +1,90 @@
+use serde_json::{Value, json};
+
+use crate::app::{App, PopupType, TraitKind};
+
+/// Nome stabile del tipo di tratto, indipendente dalla lingua dell'interfaccia
+fn kind_name(kind: TraitKind) -> &'static str {
+    match kind {
+        TraitKind::Archetype => "archetype",
+        TraitKind::Quality => "quality",
+        TraitKind::Ability => "ability",
+    }
+}
+
+/// Scheda completa del personaggio, con la prova in preparazione
+pub fn sheet(app: &App) -> Value {
+    let nodes: Vec<Value> = app
+        .honeycomb_nodes
+        .iter()
+        .enumerate()
+        .map(|(i, node)| {
+            json!({
+                "index": i,
+                "kind": kind_name(TraitKind::of(i)),
+                "text": node.text,
+                "description": node.description,
+                "tags": node.tags,
+                "used": app.used_traits.contains(&i),
+            })
+        })
+        .collect();
+    let misfortunes: Vec<Value> = (0..4)
+        .map(|i| {
+            json!({
+                "name": app.list_data.misfortunes[i],
+                "difficulty": app.list_data.misfortunes_red_balls[i].trim().parse::<usize>().unwrap_or(0),
+                "active": app.additional_red_balls[i] != 0,
+            })
+        })
+        .collect();
+    let conflicts: Vec<Value> = app
+        .sheet_conflicts
+        .iter()
+        .map(|conflict| {
+            json!({
+                "field": conflict.field.label(),
+                "mine": conflict.mine,
+                "disk": conflict.disk,
+            })
+        })
+        .collect();
+
+    json!({
+        "name": app.character_base_info.name,
+        "objective": app.character_base_info.objective,
+        "nodes": nodes,
+        "misfortunes": misfortunes,
+        "resources": app.list_data.left_resources,
+        "lessons": app.list_data.lessons,
+        "notes": app.list_data.notes,
+        "challenge": challenge(app),
+        "conflicts": conflicts,
+    })
+}
+
+/// Prova in preparazione o in attesa della decisione sul rischio
+pub fn challenge(app: &App) -> Value {
+    let traits: Vec<&str> = app
+        .used_traits
+        .iter()
+        .map(|&i| app.honeycomb_nodes[i].text.as_str())
+        .collect();
+    let awaiting_risk = app.popup == PopupType::ConfirmRisk;
+    json!({
+        "white_balls": app.white_balls,
+        "red_balls": app.red_balls,
+        "draw_count": app.draw_count,
+        "confusion": app.random_mode,
+        "adrenaline": app.forced_four_mode,
+        "traits": traits,
+        "description": app.challenge_description,
+        "awaiting_risk": awaiting_risk,
+        "first_draw": if awaiting_risk { json!(app.current_first_draw) } else { json!([]) },
+    })
+}
+
+/// Ultime prove della cronologia, dalla più vecchia
+pub fn history(app: &App, last: Option<usize>) -> Value {
+    let skip = last.map_or(0, |n| app.history.len().saturating_sub(n));
+    json!(app.history[skip..])
+}
@@ -0,0 +1,52 @@
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::Duration;
+
+use super::protocol::Message;
+
+/// Attesa massima per consegnare un messaggio all'host
+const WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Istanza collegata a una sessione ospitata da un'altra
+pub struct Client {
+    stream: TcpStream,
+    rx: Receiver<Message>,
+}
+
+impl Client {
+    /// Si collega all'host (es. `192.168.1.10:7878`)
+    pub fn connect(addr: &str) -> io::Result<Self> {
+        let stream = TcpStream::connect(addr)?;
+        // Un host bloccato non deve fermare l'interfaccia
+        stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+        let reader = stream.try_clone()?;
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for line in BufReader::new(reader).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                if let Some(msg) = Message::decode(&line)
+                    && tx.send(msg).is_err()
+                {
+                    break;
+                }
+            }
+        });
+
+        Ok(Client { stream, rx })
+    }
+
+    /// Invia un messaggio all'host
+    pub fn send(&self, msg: &Message) -> io::Result<()> {
+        (&self.stream).write_all(msg.encode()?.as_bytes())
+    }
+
+    /// Prossimo messaggio ricevuto, se presente
+    pub fn try_recv(&self) -> Option<Message> {
+        self.rx.try_recv().ok()
+    }
+}
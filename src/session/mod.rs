@@ -0,0 +1,289 @@
+use std::io;
+
+use crate::app::App;
+
+mod client;
+mod protocol;
+mod server;
+
+pub use client::Client;
+pub use protocol::{Message, Role};
+pub use server::Server;
+
+/// Porta predefinita della sessione
+pub const DEFAULT_PORT: u16 = 7878;
+
+/// Sessione richiesta da riga di comando
+#[derive(Debug, Clone, PartialEq)]
+pub enum SessionConfig {
+    /// Ospita la sessione sull'indirizzo indicato
+    Host(String),
+    /// Si unisce alla sessione ospitata all'indirizzo indicato
+    Join(String),
+}
+
+impl SessionConfig {
+    /// Legge `--ospita [indirizzo]` o `--unisciti <indirizzo>` dagli argomenti.
+    ///
+    /// Senza indirizzo la sessione è raggiungibile solo da questa macchina:
+    /// per la LAN va indicato esplicitamente, es. `--ospita 0.0.0.0`.
+    pub fn from_args(args: &[String]) -> Option<Self> {
+        let value = |i: usize| args.get(i + 1).filter(|a| !a.starts_with("--")).cloned();
+        for (i, arg) in args.iter().enumerate() {
+            match arg.as_str() {
+                "--ospita" | "--host" => {
+                    let addr = value(i).unwrap_or_else(|| format!("127.0.0.1:{}", DEFAULT_PORT));
+                    return Some(SessionConfig::Host(with_port(addr)));
+                }
+                "--unisciti" | "--join" => {
+                    return value(i).map(|addr| SessionConfig::Join(with_port(addr)));
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+}
+
+/// Aggiunge la porta predefinita se l'indirizzo non ne specifica una
+fn with_port(addr: String) -> String {
+    if addr.contains(':') {
+        addr
+    } else {
+        format!("{}:{}", addr, DEFAULT_PORT)
+    }
+}
+
+/// Collegamento attivo con le altre istanze
+pub enum Session {
+    Host(Server),
+    Client(Client),
+}
+
+impl Session {
+    /// Avvia la sessione e la annuncia all'applicazione
+    pub fn start(config: &SessionConfig, app: &mut App) -> io::Result<Self> {
+        match config {
+            SessionConfig::Host(addr) => {
+                let server = Server::bind(addr)?;
+                app.host_session(&server.local_addr().to_string());
+                Ok(Session::Host(server))
+            }
+            SessionConfig::Join(addr) => {
+                let client = Client::connect(addr)?;
+                app.join_session(addr);
+                Ok(Session::Client(client))
+            }
+        }
+    }
+
+    /// Invia i messaggi prodotti dall'applicazione e le consegna quelli ricevuti
+    pub fn sync(&self, app: &mut App) {
+        for msg in app.outbox.drain(..) {
+            match self {
+                Session::Host(server) => server.broadcast(&msg),
+                Session::Client(client) => {
+                    if client.send(&msg).is_err() {
+                        app.status_message = Some("Connessione con l'host persa".to_string());
+                    }
+                }
+            }
+        }
+
+        while let Some(msg) = self.try_recv() {
+            app.apply_message(msg);
+        }
+
+        if let Session::Host(server) = self {
+            app.session_peers = server.peers();
+        }
+    }
+
+    fn try_recv(&self) -> Option<Message> {
+        match self {
+            Session::Host(server) => server.try_recv(),
+            Session::Client(client) => client.try_recv(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod session_tests {
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use crate::session::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    /// Attende un messaggio, con un limite di tempo
+    fn wait_for(mut recv: impl FnMut() -> Option<Message>) -> Message {
+        let start = Instant::now();
+        loop {
+            if let Some(msg) = recv() {
+                return msg;
+            }
+            assert!(start.elapsed() < Duration::from_secs(5), "nessun messaggio");
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    fn wait_for_peers(server: &Server, count: usize) {
+        let start = Instant::now();
+        while server.peers() < count {
+            assert!(
+                start.elapsed() < Duration::from_secs(5),
+                "nessun partecipante"
+            );
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn test_session_config_from_args() {
+        assert_eq!(
+            SessionConfig::from_args(&args(&["nte", "--ospita"])),
+            Some(SessionConfig::Host("127.0.0.1:7878".into()))
+        );
+        assert_eq!(
+            SessionConfig::from_args(&args(&["nte", "--ospita", "0.0.0.0"])),
+            Some(SessionConfig::Host("0.0.0.0:7878".into()))
+        );
+        assert_eq!(
+            SessionConfig::from_args(&args(&["nte", "--unisciti", "192.168.1.5"])),
+            Some(SessionConfig::Join("192.168.1.5:7878".into()))
+        );
+        assert_eq!(
+            SessionConfig::from_args(&args(&["nte", "--join", "host:9000"])),
+            Some(SessionConfig::Join("host:9000".into()))
+        );
+        assert_eq!(SessionConfig::from_args(&args(&["nte", "--join"])), None);
+        assert_eq!(SessionConfig::from_args(&args(&["nte"])), None);
+    }
+
+    #[test]
+    fn test_messages_reach_host_and_other_clients() {
+        let server = Server::bind("127.0.0.1:0").unwrap();
+        let addr = server.local_addr().to_string();
+        let aria = Client::connect(&addr).unwrap();
+        let bruno = Client::connect(&addr).unwrap();
+        wait_for_peers(&server, 2);
+
+        let msg = Message::Traits {
+            player: "Aria".into(),
+            traits: vec!["Spada".into()],
+        };
+        aria.send(&msg).unwrap();
+
+        assert_eq!(wait_for(|| server.try_recv()), msg);
+        assert_eq!(wait_for(|| bruno.try_recv()), msg);
+        assert_eq!(aria.try_recv(), None);
+
+        let difficulty = Message::Difficulty { tokens: 3 };
+        server.broadcast(&difficulty);
+        assert_eq!(wait_for(|| aria.try_recv()), difficulty);
+        assert_eq!(wait_for(|| bruno.try_recv()), difficulty);
+    }
+
+    #[test]
+    fn test_welcome_reaches_only_the_new_client() {
+        let server = Server::bind("127.0.0.1:0").unwrap();
+        let addr = server.local_addr().to_string();
+        let aria = Client::connect(&addr).unwrap();
+        let bruno = Client::connect(&addr).unwrap();
+        wait_for_peers(&server, 2);
+
+        let hello = Message::Hello {
+            name: "Bruno".into(),
+            role: Role::Player,
+        };
+        bruno.send(&hello).unwrap();
+        assert_eq!(wait_for(|| server.try_recv()), hello);
+        assert_eq!(wait_for(|| aria.try_recv()), hello);
+
+        let welcome = Message::Welcome {
+            history: Vec::new(),
+            difficulty: Some(2),
+            authoritative: false,
+        };
+        server.broadcast(&welcome);
+        let difficulty = Message::Difficulty { tokens: 2 };
+        server.broadcast(&difficulty);
+
+        assert_eq!(wait_for(|| bruno.try_recv()), welcome);
+        assert_eq!(wait_for(|| bruno.try_recv()), difficulty);
+        assert_eq!(wait_for(|| aria.try_recv()), difficulty);
+    }
+
+    #[test]
+    fn test_host_only_messages_from_clients_are_dropped() {
+        let server = Server::bind("127.0.0.1:0").unwrap();
+        let addr = server.local_addr().to_string();
+        let cheater = Client::connect(&addr).unwrap();
+        let other = Client::connect(&addr).unwrap();
+        wait_for_peers(&server, 2);
+
+        cheater
+            .send(&Message::Commit {
+                id: 1,
+                commitment: "falso".into(),
+            })
+            .unwrap();
+        let difficulty = Message::Difficulty { tokens: 2 };
+        cheater.send(&difficulty).unwrap();
+
+        assert_eq!(wait_for(|| server.try_recv()), difficulty);
+        assert_eq!(wait_for(|| other.try_recv()), difficulty);
+    }
+
+    #[test]
+    fn test_draws_keep_logs_in_sync() {
+        let mut host = App::new();
+        let host_session =
+            Session::start(&SessionConfig::Host("127.0.0.1:0".into()), &mut host).unwrap();
+        let Session::Host(server) = &host_session else {
+            unreachable!()
+        };
+        let addr = server.local_addr().to_string();
+
+        let mut player = App::new();
+        let player_session = Session::start(&SessionConfig::Join(addr), &mut player).unwrap();
+        wait_for_peers(server, 1);
+
+        // Prova del giocatore: arriva all'host
+        player.white_balls = 2;
+        player.red_balls = 1;
+        player.perform_first_draw();
+        player.cancel_draw();
+        player_session.sync(&mut player);
+        let start = Instant::now();
+        while host.history.is_empty() {
+            assert!(
+                start.elapsed() < Duration::from_secs(5),
+                "prova non ricevuta"
+            );
+            host_session.sync(&mut host);
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(host.history[0].first_draw, player.history[0].first_draw);
+
+        // Prova dell'host: arriva al giocatore
+        host.white_balls = 1;
+        host.red_balls = 1;
+        host.perform_first_draw();
+        host.cancel_draw();
+        host_session.sync(&mut host);
+        let start = Instant::now();
+        while player.history.len() < 2 {
+            assert!(
+                start.elapsed() < Duration::from_secs(5),
+                "prova non ricevuta"
+            );
+            player_session.sync(&mut player);
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(player.history, host.history);
+    }
+}
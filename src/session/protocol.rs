@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+
+use crate::app::{BallType, DrawHistory};
+
+/// Ruolo di chi partecipa alla sessione
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    Player,
+    Narrator,
+}
+
+/// Messaggio scambiato tra le istanze della sessione.
+///
+/// Ogni messaggio viaggia come una riga JSON terminata da `\n`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Message {
+    /// Presentazione di chi si unisce
+    Hello { name: String, role: Role },
+    /// Stato della sessione inviato dall'host a chi si unisce
+    Welcome {
+        history: Vec<DrawHistory>,
+        difficulty: Option<usize>,
+        /// Le estrazioni spettano all'host
+        #[serde(default)]
+        authoritative: bool,
+    },
+    /// Difficoltà della prova decisa dal narratore
+    Difficulty { tokens: usize },
+    /// Tratti messi in gioco da un giocatore
+    Traits { player: String, traits: Vec<String> },
+    /// Esito di una prova
+    Draw { entry: DrawHistory },
+    /// Richiesta all'host di estrarre una prova
+    DrawRequest {
+        id: u64,
+        entry: DrawHistory,
+        count: usize,
+    },
+    /// Impronta del seme annunciata dall'host prima dell'estrazione
+    Commit { id: u64, commitment: String },
+    /// Nonce di chi ha chiesto la prova, scelto dopo l'impronta
+    Nonce { id: u64, nonce: String },
+    /// Primo tentativo estratto dall'host
+    FirstDraw { id: u64, balls: Vec<BallType> },
+    /// Decisione del giocatore di rischiare o meno
+    RiskRequest { id: u64, risk: bool },
+}
+
+impl Message {
+    /// Serializza il messaggio in una riga
+    pub fn encode(&self) -> serde_json::Result<String> {
+        let mut line = serde_json::to_string(self)?;
+        line.push('\n');
+        Ok(line)
+    }
+
+    /// Messaggi che solo l'host può inviare: quelli dei partecipanti vengono scartati
+    pub fn host_only(&self) -> bool {
+        matches!(
+            self,
+            Message::Welcome { .. } | Message::Commit { .. } | Message::FirstDraw { .. }
+        )
+    }
+
+    /// Interpreta una riga ricevuta, ignorando quelle non valide
+    pub fn decode(line: &str) -> Option<Self> {
+        serde_json::from_str(line.trim()).ok()
+    }
+}
+
+#[cfg(test)]
+mod protocol_tests {
+    use crate::session::protocol::*;
+
+    #[test]
+    fn test_message_round_trip() {
+        let msg = Message::Traits {
+            player: "Aria".into(),
+            traits: vec!["Spada".into()],
+        };
+        let line = msg.encode().unwrap();
+        assert!(line.ends_with('\n'));
+        assert_eq!(Message::decode(&line), Some(msg));
+    }
+
+    #[test]
+    fn test_decode_rejects_garbage() {
+        assert_eq!(Message::decode("{\"type\":\"boh\"}"), None);
+        assert_eq!(Message::decode("non json"), None);
+    }
+}
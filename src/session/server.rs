@@ -0,0 +1,164 @@
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+use super::protocol::Message;
+
+/// Attesa massima per consegnare un messaggio: oltre, il partecipante viene scartato
+const WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Connessione di un partecipante, usata per inoltrargli i messaggi
+struct Peer {
+    id: usize,
+    stream: TcpStream,
+    /// Si è presentato e attende lo stato della sessione
+    awaiting_welcome: bool,
+}
+
+/// Messaggi per il thread che scrive ai partecipanti
+enum Outgoing {
+    /// Nuova connessione
+    Join(Peer),
+    /// Connessione chiusa dal partecipante
+    Leave(usize),
+    /// Messaggio per tutti tranne il mittente, se indicato
+    Relay(Option<usize>, Box<Message>),
+}
+
+/// Istanza che ospita la sessione.
+///
+/// Ogni messaggio ricevuto da un partecipante viene inoltrato a tutti gli altri
+/// e consegnato all'host; i messaggi dell'host arrivano a tutti i partecipanti,
+/// tranne lo stato della sessione che va solo a chi si è appena presentato.
+/// I messaggi riservati all'host non vengono accettati dai partecipanti.
+pub struct Server {
+    addr: SocketAddr,
+    peers: Arc<AtomicUsize>,
+    out: Sender<Outgoing>,
+    rx: Receiver<Message>,
+}
+
+impl Server {
+    /// Apre la sessione sull'indirizzo indicato (es. `0.0.0.0:7878` per la LAN)
+    pub fn bind(addr: &str) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let addr = listener.local_addr()?;
+        let peers = Arc::new(AtomicUsize::new(0));
+        let (tx, rx) = mpsc::channel();
+        let (out, out_rx) = mpsc::channel();
+
+        let writer_peers = Arc::clone(&peers);
+        thread::spawn(move || write_loop(out_rx, writer_peers));
+        let accept_out = out.clone();
+        thread::spawn(move || accept_loop(listener, accept_out, tx));
+
+        Ok(Server {
+            addr,
+            peers,
+            out,
+            rx,
+        })
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Numero di partecipanti collegati
+    pub fn peers(&self) -> usize {
+        self.peers.load(Ordering::Relaxed)
+    }
+
+    /// Invia un messaggio a tutti i partecipanti, senza attendere la consegna
+    pub fn broadcast(&self, msg: &Message) {
+        let _ = self.out.send(Outgoing::Relay(None, Box::new(msg.clone())));
+    }
+
+    /// Prossimo messaggio ricevuto, se presente
+    pub fn try_recv(&self) -> Option<Message> {
+        self.rx.try_recv().ok()
+    }
+}
+
+/// Accetta le connessioni e avvia un lettore per ciascuna
+fn accept_loop(listener: TcpListener, out: Sender<Outgoing>, tx: Sender<Message>) {
+    for (id, stream) in listener.incoming().enumerate() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        let Ok(writer) = stream.try_clone() else {
+            continue;
+        };
+        if writer.set_write_timeout(Some(WRITE_TIMEOUT)).is_err() {
+            continue;
+        }
+        let peer = Peer {
+            id,
+            stream: writer,
+            awaiting_welcome: false,
+        };
+        if out.send(Outgoing::Join(peer)).is_err() {
+            break;
+        }
+
+        let out = out.clone();
+        let tx = tx.clone();
+        thread::spawn(move || {
+            for line in BufReader::new(stream).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                if let Some(msg) = Message::decode(&line)
+                    && !msg.host_only()
+                {
+                    // Passa dallo scrittore prima che l'host possa rispondere
+                    let _ = out.send(Outgoing::Relay(Some(id), Box::new(msg.clone())));
+                    if tx.send(msg).is_err() {
+                        break;
+                    }
+                }
+            }
+            let _ = out.send(Outgoing::Leave(id));
+        });
+    }
+}
+
+/// Scrive ai partecipanti fuori dal loop dell'interfaccia, scartando le
+/// connessioni che non rispondono più
+fn write_loop(rx: Receiver<Outgoing>, count: Arc<AtomicUsize>) {
+    let mut peers: Vec<Peer> = Vec::new();
+    for outgoing in rx {
+        match outgoing {
+            Outgoing::Join(peer) => peers.push(peer),
+            Outgoing::Leave(id) => peers.retain(|p| p.id != id),
+            Outgoing::Relay(from, msg) => {
+                let Ok(line) = msg.encode() else {
+                    continue;
+                };
+                let welcome = matches!(*msg, Message::Welcome { .. });
+                if let (Some(id), Message::Hello { .. }) = (from, &*msg)
+                    && let Some(peer) = peers.iter_mut().find(|p| p.id == id)
+                {
+                    peer.awaiting_welcome = true;
+                }
+                peers.retain_mut(|peer| {
+                    if Some(peer.id) == from || (welcome && !peer.awaiting_welcome) {
+                        return true;
+                    }
+                    peer.awaiting_welcome &= !welcome;
+                    let sent = peer.stream.write_all(line.as_bytes()).is_ok();
+                    if !sent {
+                        // Chiude anche il lettore del partecipante
+                        let _ = peer.stream.shutdown(Shutdown::Both);
+                    }
+                    sent
+                });
+            }
+        }
+        count.store(peers.len(), Ordering::Relaxed);
+    }
+}
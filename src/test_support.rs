@@ -0,0 +1,62 @@
+//! Strumenti condivisi dai test
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::app::{App, Campaign, deactivate};
+
+/// Cartella temporanea eliminata a fine test
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    /// Cartella vuota, diversa per ogni test anche se eseguiti in parallelo
+    pub fn new(name: &str) -> Self {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let dir = std::env::temp_dir().join(format!(
+            "nte-{}-{}-{}",
+            name,
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        TempDir(dir)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+
+    pub fn join(&self, name: &str) -> PathBuf {
+        self.0.join(name)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Campagna attiva per il test; all'uscita i file tornano nella cartella corrente
+pub struct ActiveScope;
+
+impl ActiveScope {
+    pub fn new(campaign: &Campaign) -> Self {
+        campaign.activate();
+        ActiveScope
+    }
+}
+
+impl Drop for ActiveScope {
+    fn drop(&mut self) {
+        deactivate();
+    }
+}
+
+/// Preme un tasto senza modificatori passando per il gestore indicato
+pub fn press(app: &mut App, handler: fn(&mut App, KeyEvent), code: KeyCode) {
+    handler(app, KeyEvent::new(code, KeyModifiers::empty()));
+}
@@ -1,7 +1,29 @@
+mod popup_campaigns;
+mod popup_context_menu;
 mod popup_draw;
 mod popup_edit;
+mod popup_import;
+mod popup_log_filter;
+mod popup_palette;
+mod popup_quick_entry;
+mod popup_search;
+mod popup_sessions;
+mod popup_sheet_conflict;
+mod popup_timeline;
+mod popup_wizard;
 mod tabs_bar;
 
-pub use popup_draw::render_draw_popup;
-pub use popup_edit::{render_list_edit_popup, render_node_edit_popup};
+pub use popup_campaigns::render_campaigns_popup;
+pub use popup_context_menu::render_context_menu;
+pub use popup_draw::{render_draw_popup, render_manual_popup};
+pub use popup_edit::{render_list_edit_popup, render_narrator_edit_popup, render_node_edit_popup};
+pub use popup_import::render_import_popup;
+pub use popup_log_filter::render_log_filter_popup;
+pub use popup_palette::render_palette_popup;
+pub use popup_quick_entry::render_quick_entry_popup;
+pub use popup_search::{render_picker_popup, render_search_popup};
+pub use popup_sessions::render_sessions_popup;
+pub use popup_sheet_conflict::render_sheet_conflict_popup;
+pub use popup_timeline::render_timeline_popup;
+pub use popup_wizard::render_wizard_popup;
 pub use tabs_bar::render_tabs_bar;
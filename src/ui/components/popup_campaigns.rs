@@ -0,0 +1,174 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+};
+
+use super::super::utils::centered_rect;
+use crate::app::{App, CAMPAIGNS_DIR, CampaignFocus, format_time};
+
+/// Renderizza la finestra di campagne, personaggi e cronaca
+pub fn render_campaigns_popup(f: &mut Frame, app: &App) {
+    let area = centered_rect(80, 80, f.area());
+    f.render_widget(Clear, area);
+
+    let title = match &app.campaign {
+        Some(campaign) => format!(" Campagna: {} ", campaign.name),
+        None => " Campagne (nessuna aperta) ".to_string(),
+    };
+    let popup_block = Block::default()
+        .title(Line::from(title).alignment(Alignment::Center))
+        .title_bottom(
+            Line::from(
+                " Tab elenco · Enter apri · N campagna · P personaggio · C cronaca · Esc chiudi ",
+            )
+            .alignment(Alignment::Center),
+        )
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .style(Style::default().bg(Color::Black));
+    let inner = popup_block.inner(area);
+    f.render_widget(popup_block, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(0)])
+        .split(inner);
+
+    // Testo in digitazione
+    let input = match &app.campaign_input {
+        Some((input, text)) => Line::from(vec![
+            Span::styled(
+                format!("{}: ", input.label()),
+                Style::default().fg(Color::Gray),
+            ),
+            Span::styled(text.clone(), Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled("▌", Style::default().fg(Color::LightYellow)),
+        ]),
+        None => Line::from(Span::styled(
+            format!("Cartella delle campagne: {}/", CAMPAIGNS_DIR),
+            Style::default().fg(Color::Gray),
+        )),
+    };
+    f.render_widget(Paragraph::new(input), rows[0].inner(Margin::new(1, 0)));
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(30),
+            Constraint::Percentage(25),
+            Constraint::Percentage(45),
+        ])
+        .split(rows[1]);
+
+    // Campagne disponibili
+    let active_dir = app.campaign.as_ref().map(|c| &c.dir);
+    let campaigns = app
+        .campaign_list
+        .iter()
+        .map(|(dir, name)| (name.clone(), Some(dir) == active_dir))
+        .collect::<Vec<_>>();
+    render_list(
+        f,
+        columns[0],
+        " Campagne ",
+        &campaigns,
+        app.campaign_selected,
+        app.campaign_focus == CampaignFocus::Campaigns,
+    );
+
+    // Personaggi della campagna aperta
+    let active_character = app.campaign.as_ref().map(|c| &c.character);
+    let characters = app
+        .campaign_characters
+        .iter()
+        .map(|p| (p.clone(), Some(p) == active_character))
+        .collect::<Vec<_>>();
+    render_list(
+        f,
+        columns[1],
+        " Personaggi ",
+        &characters,
+        app.character_selected,
+        app.campaign_focus == CampaignFocus::Characters,
+    );
+
+    // Cronaca, dalla voce più recente
+    let mut lines = Vec::new();
+    let chronicle = app
+        .campaign
+        .as_ref()
+        .map(|c| c.chronicle.as_slice())
+        .unwrap_or_default();
+    if chronicle.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "Nessuna voce",
+            Style::default().fg(Color::Gray),
+        )));
+    }
+    for entry in chronicle.iter().rev() {
+        lines.push(Line::from(Span::styled(
+            format_time(&entry.time),
+            Style::default().fg(Color::LightYellow),
+        )));
+        lines.push(Line::from(entry.text.clone()));
+        lines.push(Line::from(""));
+    }
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(" Cronaca ")
+                .borders(Borders::LEFT | Borders::TOP),
+        )
+        .wrap(Wrap { trim: false });
+    f.render_widget(paragraph, columns[2]);
+}
+
+/// Elenco con la voce selezionata evidenziata e quella in uso marcata con ●
+fn render_list(
+    f: &mut Frame,
+    area: Rect,
+    title: &str,
+    items: &[(String, bool)],
+    selected: usize,
+    focused: bool,
+) {
+    let mut lines = Vec::new();
+    if items.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "Nessuna voce",
+            Style::default().fg(Color::Gray),
+        )));
+    }
+    for (i, (name, active)) in items.iter().enumerate() {
+        let marker = if *active { "● " } else { "  " };
+        let style = if i == selected && focused {
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else if i == selected {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        lines.push(Line::from(Span::styled(
+            format!("{}{}", marker, name),
+            style,
+        )));
+    }
+    let border_style = if focused {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default()
+    };
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(title.to_string())
+            .borders(Borders::TOP | Borders::RIGHT)
+            .border_style(border_style),
+    );
+    f.render_widget(paragraph, area);
+}
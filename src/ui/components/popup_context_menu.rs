@@ -0,0 +1,53 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+
+use crate::app::{App, HitId, Z_MENU};
+
+/// Renderizza il menu contestuale accanto al punto cliccato
+pub fn render_context_menu(f: &mut Frame, app: &mut App) {
+    let Some(menu) = &app.context_menu else {
+        return;
+    };
+    let area = menu.area(f.area());
+    f.render_widget(Clear, area);
+
+    // Il menu intero copre ciò che sta sotto, ogni voce è cliccabile
+    app.hits.register(HitId::Popup, area, Z_MENU);
+    for i in 0..menu.actions.len() {
+        let row = Rect::new(
+            area.x + 1,
+            area.y + 1 + i as u16,
+            area.width.saturating_sub(2),
+            1,
+        );
+        app.hits.register(HitId::MenuAction(i), row, Z_MENU);
+    }
+
+    let lines: Vec<Line> = menu
+        .actions
+        .iter()
+        .enumerate()
+        .map(|(i, action)| {
+            let style = if i == menu.selected {
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            Line::from(Span::styled(format!(" {} ", action.label()), style))
+        })
+        .collect();
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .style(Style::default().bg(Color::Black).fg(Color::Yellow));
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
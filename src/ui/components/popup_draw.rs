@@ -1,7 +1,7 @@
 use ratatui::{
     Frame,
     layout::Alignment,
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
 };
@@ -9,13 +9,15 @@ use ratatui::{
 use super::super::utils::centered_rect;
 use crate::app::{App, PopupType};
 
-/// Renderizza i popup di conferma per l'estrazione
+/// Renderizza i popup di conferma per l'estrazione e le operazioni che sovrascrivono dati
 pub fn render_draw_popup(f: &mut Frame, app: &App) {
     let area = centered_rect(30, 25, f.area());
 
     let title = match app.popup {
         PopupType::ConfirmDraw => " Conferma Pescata? ",
         PopupType::ConfirmRisk => " Vuoi Rischiare? ",
+        PopupType::AwaitingHost => " Estrazione ",
+        PopupType::Confirm => " Conferma ",
         _ => "",
     };
 
@@ -53,6 +55,33 @@ pub fn render_draw_popup(f: &mut Frame, app: &App) {
                 Span::raw(" per annullare"),
             ]),
         ],
+        PopupType::AwaitingHost => vec![
+            Line::from(""),
+            Line::from("L'host sta estraendo i pallini..."),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("Esc", Style::default().fg(Color::Red)),
+                Span::raw(" per chiudere senza rischiare"),
+            ]),
+        ],
+        PopupType::Confirm => vec![
+            Line::from(""),
+            Line::from(
+                app.confirm
+                    .as_ref()
+                    .map(|c| c.message())
+                    .unwrap_or_default(),
+            ),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("Enter", Style::default().fg(Color::Green)),
+                Span::raw(" per confermare"),
+            ]),
+            Line::from(vec![
+                Span::styled("Esc", Style::default().fg(Color::Red)),
+                Span::raw(" per annullare"),
+            ]),
+        ],
         _ => vec![],
     };
 
@@ -64,3 +93,70 @@ pub fn render_draw_popup(f: &mut Frame, app: &App) {
     f.render_widget(Clear, area);
     f.render_widget(paragraph, area);
 }
+
+/// Etichette dei campi dell'esito manuale, nell'ordine di `App::manual_result`
+const MANUAL_LABELS: [&str; 4] = [
+    "Primo tentativo ● bianchi",
+    "Primo tentativo ● rossi",
+    "Rischio ● bianchi",
+    "Rischio ● rossi",
+];
+
+/// Renderizza l'inserimento dell'esito pescato dal sacchetto fisico
+pub fn render_manual_popup(f: &mut Frame, app: &App) {
+    let area = centered_rect(50, 40, f.area());
+
+    let popup_block = Block::default()
+        .title(Line::from(" Sacchetto Fisico ").alignment(Alignment::Center))
+        .title_bottom(
+            Line::from(" ↑/↓ campo · ←/→ valore · Enter registra · Esc annulla ")
+                .alignment(Alignment::Center),
+        )
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .style(Style::default().bg(Color::Black));
+
+    let mut lines = vec![
+        Line::from(format!(
+            "Sacchetto: {} ● bianchi, {} ● rossi · pesca {}",
+            app.white_balls, app.red_balls, app.draw_count
+        )),
+        Line::from(""),
+    ];
+
+    for (i, label) in MANUAL_LABELS.iter().enumerate() {
+        let color = if i % 2 == 0 { Color::White } else { Color::Red };
+        let style = if i == app.manual_field {
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(color)
+        };
+        lines.push(Line::from(Span::styled(
+            format!(" {}: {} ", label, app.manual_result[i]),
+            style,
+        )));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(match app.check_manual_result() {
+        Ok(()) => Line::from(Span::styled(
+            "✓ Esito compatibile con il sacchetto",
+            Style::default().fg(Color::Green),
+        )),
+        Err(e) => Line::from(Span::styled(
+            format!("✗ {}", e),
+            Style::default().fg(Color::Red),
+        )),
+    });
+
+    let paragraph = Paragraph::new(lines)
+        .block(popup_block)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
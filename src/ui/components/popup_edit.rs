@@ -13,8 +13,14 @@ use crate::app::App;
 pub fn render_node_edit_popup(f: &mut Frame, app: &App) {
     let area = centered_rect(50, 20, f.area());
 
+    let title = if app.editing_node {
+        format!(" Modifica {} (Esc per confermare) ", app.node_field)
+    } else {
+        " Modifica (Esc per confermare) ".to_string()
+    };
+
     let popup_block = Block::default()
-        .title(Line::from(" Modifica (Esc per confermare) ").alignment(Alignment::Center))
+        .title(Line::from(title).alignment(Alignment::Center))
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
         .style(Style::default().bg(Color::Black).fg(Color::Yellow));
@@ -36,7 +42,8 @@ pub fn render_node_edit_popup(f: &mut Frame, app: &App) {
 
     let paragraph = Paragraph::new(text)
         .block(popup_block)
-        .alignment(Alignment::Center);
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
 
     f.render_widget(Clear, area);
     f.render_widget(paragraph, area);
@@ -83,3 +90,45 @@ pub fn render_list_edit_popup(f: &mut Frame, app: &App) {
     f.render_widget(Clear, area);
     f.render_widget(paragraph, area);
 }
+
+/// Renderizza il popup di editing dei campi del narratore
+pub fn render_narrator_edit_popup(f: &mut Frame, app: &App) {
+    let Some(field) = app.narrator_field else {
+        return;
+    };
+    let area = if field.multiline() {
+        centered_rect(70, 30, f.area())
+    } else {
+        centered_rect(50, 20, f.area())
+    };
+
+    let popup_block = Block::default()
+        .title(
+            Line::from(format!(" Modifica {} (Esc per confermare) ", field))
+                .alignment(Alignment::Center),
+        )
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .style(Style::default().bg(Color::Black).fg(Color::Yellow));
+
+    let mut text = vec![Line::from("")];
+    let mut rows = app.narrator_edit_buffer.split('\n').peekable();
+    while let Some(row) = rows.next() {
+        let mut spans = vec![Span::styled(
+            row,
+            Style::default().add_modifier(Modifier::BOLD),
+        )];
+        if rows.peek().is_none() {
+            spans.push(Span::styled("▌", Style::default().fg(Color::LightYellow)));
+        }
+        text.push(Line::from(spans));
+    }
+
+    let paragraph = Paragraph::new(text)
+        .block(popup_block)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
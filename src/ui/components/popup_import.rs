@@ -0,0 +1,110 @@
+use ratatui::{
+    Frame,
+    layout::Alignment,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+
+use super::super::utils::centered_rect;
+use crate::app::{App, target_key};
+
+/// Testo accorciato a `width` caratteri e allineato a sinistra
+fn fit(text: &str, width: usize) -> String {
+    if text.chars().count() > width {
+        let cut: String = text.chars().take(width.saturating_sub(1)).collect();
+        format!("{}…", cut)
+    } else {
+        format!("{:<width$}", text)
+    }
+}
+
+/// Renderizza l'anteprima del CSV e la scelta dei campi
+pub fn render_import_popup(f: &mut Frame, app: &App) {
+    let area = centered_rect(80, 80, f.area());
+    let Some(import) = &app.sheet_import else {
+        return;
+    };
+
+    let popup_block = Block::default()
+        .title(Line::from(format!(" Importa {} ", import.path)).alignment(Alignment::Center))
+        .title_bottom(
+            Line::from(" ↑/↓ riga · ←/→ campo · Canc ignora · Enter importa · Esc annulla ")
+                .alignment(Alignment::Center),
+        )
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .style(Style::default().bg(Color::Black).fg(Color::Yellow));
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!(
+                "{} righe, {} nella scheda. La scheda attuale viene sostituita solo con Enter.",
+                import.rows.len(),
+                import.mapped()
+            ),
+            Style::default().fg(Color::White),
+        )),
+        Line::from(Span::styled(
+            format!(
+                "{:<5} {} {} Campo",
+                "Riga",
+                fit("Nel file", 18),
+                fit("Valore", 34)
+            ),
+            Style::default().fg(Color::Gray),
+        )),
+    ];
+
+    // Mantiene visibile la riga selezionata
+    let visible = area.height.saturating_sub(4) as usize;
+    let skip = (app.sheet_import_selected + 1).saturating_sub(visible);
+
+    for (pos, row) in import.rows.iter().enumerate().skip(skip).take(visible) {
+        let value: Vec<&str> = row.cells[1..]
+            .iter()
+            .map(String::as_str)
+            .filter(|c| !c.is_empty())
+            .collect();
+        let selected = pos == app.sheet_import_selected;
+        let base = if selected {
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+
+        let mut spans = vec![Span::styled(
+            format!(
+                "{:<5} {} {} ",
+                row.line,
+                fit(row.key(), 18),
+                fit(&value.join(" · "), 34)
+            ),
+            base,
+        )];
+        match row.target {
+            Some(target) => spans.push(Span::styled(
+                format!("→ {}", target_key(target)),
+                base.fg(if selected { Color::Black } else { Color::Green }),
+            )),
+            None => spans.push(Span::styled(
+                "ignorata",
+                base.fg(if selected { Color::Black } else { Color::Gray }),
+            )),
+        }
+        if import.overridden(pos) {
+            spans.push(Span::styled(" (sostituita più sotto)", base.fg(Color::Red)));
+        } else if row.truncated() {
+            spans.push(Span::styled(" (accorciata)", base.fg(Color::Red)));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let paragraph = Paragraph::new(lines).block(popup_block);
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
@@ -0,0 +1,101 @@
+use ratatui::{
+    Frame,
+    layout::Alignment,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+
+use super::super::utils::centered_rect;
+use crate::app::{App, LogFilterField, parse_date};
+
+/// Renderizza i filtri e la ricerca del log
+pub fn render_log_filter_popup(f: &mut Frame, app: &App) {
+    let area = centered_rect(60, 60, f.area());
+    f.render_widget(Clear, area);
+
+    let popup_block = Block::default()
+        .title(Line::from(" Filtri del Log ").alignment(Alignment::Center))
+        .title_bottom(
+            Line::from(" ↑/↓ campo · ←/→ cambia · Canc azzera · Enter chiudi ")
+                .alignment(Alignment::Center),
+        )
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .style(Style::default().bg(Color::Black).fg(Color::Yellow));
+
+    let filter = &app.log_filter;
+    let flag = |value: Option<bool>| match value {
+        None => "indifferente",
+        Some(true) => "sì",
+        Some(false) => "no",
+    };
+    let date = |text: &str| {
+        if text.trim().is_empty() || parse_date(text).is_some() {
+            None
+        } else {
+            Some("data non valida (gg/mm/aaaa)")
+        }
+    };
+
+    let mut lines = vec![Line::from("")];
+    for field in LogFilterField::ALL {
+        let (value, warning) = match field {
+            LogFilterField::Query => (filter.query.clone(), None),
+            LogFilterField::Session => (
+                filter
+                    .session
+                    .and_then(|i| app.journal.sessions.get(i))
+                    .map_or("tutte".to_string(), |s| s.title.clone()),
+                None,
+            ),
+            LogFilterField::From => (filter.from.clone(), date(&filter.from)),
+            LogFilterField::To => (filter.to.clone(), date(&filter.to)),
+            LogFilterField::Trait => (filter.trait_name.clone(), None),
+            LogFilterField::Misfortune => (filter.misfortune.clone(), None),
+            LogFilterField::Risked => (flag(filter.risked).to_string(), None),
+            LogFilterField::Confused => (flag(filter.confused).to_string(), None),
+            LogFilterField::Adrenalined => (flag(filter.adrenalined).to_string(), None),
+        };
+
+        let selected = field == app.log_filter_field;
+        let label_style = if selected {
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Gray)
+        };
+        let mut spans = vec![
+            Span::styled(format!(" {:<11}", field.label()), label_style),
+            Span::raw(" "),
+        ];
+        if field.is_text() {
+            spans.push(Span::styled(value, Style::default().fg(Color::White)));
+            if selected {
+                spans.push(Span::styled("▌", Style::default().fg(Color::LightYellow)));
+            }
+        } else {
+            spans.push(Span::styled(
+                format!("‹ {} ›", value),
+                Style::default().fg(Color::White),
+            ));
+        }
+        if let Some(warning) = warning {
+            spans.push(Span::styled(
+                format!("  {}", warning),
+                Style::default().fg(Color::Red),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!(" Prove trovate: {}", app.log_entries().len()),
+        Style::default().fg(Color::Gray),
+    )));
+
+    f.render_widget(Paragraph::new(lines).block(popup_block), area);
+}
@@ -0,0 +1,74 @@
+use ratatui::{
+    Frame,
+    layout::Alignment,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+
+use super::super::utils::centered_rect;
+use crate::app::App;
+
+/// Renderizza la palette dei comandi
+pub fn render_palette_popup(f: &mut Frame, app: &App) {
+    let area = centered_rect(60, 60, f.area());
+
+    let bottom = if app.palette_prompt.is_some() {
+        " Enter per eseguire · Esc per tornare ai comandi "
+    } else {
+        " ↑/↓ per scegliere · Enter per eseguire · Esc per chiudere "
+    };
+    let popup_block = Block::default()
+        .title(Line::from(" Comandi ").alignment(Alignment::Center))
+        .title_bottom(Line::from(bottom).alignment(Alignment::Center))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .style(Style::default().bg(Color::Black).fg(Color::Yellow));
+
+    let prefix = match app.palette_prompt {
+        Some(prompt) => format!("{}: ", prompt.label()),
+        None => "> ".to_string(),
+    };
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(prefix, Style::default().fg(Color::Gray)),
+            Span::styled(
+                app.palette_query.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("▌", Style::default().fg(Color::LightYellow)),
+        ]),
+        Line::from(""),
+    ];
+
+    if app.palette_prompt.is_none() {
+        let entries = app.palette_matches();
+        if entries.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "Nessun comando corrisponde",
+                Style::default().fg(Color::Gray),
+            )));
+        }
+
+        // Mantiene visibile la riga selezionata
+        let visible = area.height.saturating_sub(4) as usize;
+        let skip = (app.palette_selected + 1).saturating_sub(visible);
+
+        for (pos, entry) in entries.iter().enumerate().skip(skip).take(visible) {
+            let style = if pos == app.palette_selected {
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            lines.push(Line::from(Span::styled(entry.label.clone(), style)));
+        }
+    }
+
+    let paragraph = Paragraph::new(lines).block(popup_block);
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
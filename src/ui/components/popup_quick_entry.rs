@@ -0,0 +1,82 @@
+use ratatui::{
+    Frame,
+    layout::Alignment,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+};
+
+use super::super::utils::centered_rect;
+use crate::app::{App, MIN_DRAW};
+
+/// Renderizza l'inserimento rapido della prova, con l'anteprima e i suggerimenti
+pub fn render_quick_entry_popup(f: &mut Frame, app: &App) {
+    let area = centered_rect(70, 50, f.area());
+
+    let popup_block = Block::default()
+        .title(Line::from(" Prova Rapida ").alignment(Alignment::Center))
+        .title_bottom(
+            Line::from(" Tab completa · Enter prepara la prova · Esc per chiudere ")
+                .alignment(Alignment::Center),
+        )
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .style(Style::default().bg(Color::Black).fg(Color::Yellow));
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("> ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                app.quick_entry.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("▌", Style::default().fg(Color::LightYellow)),
+        ]),
+        Line::from(""),
+    ];
+
+    if app.quick_entry.trim().is_empty() {
+        lines.push(Line::from(Span::styled(
+            "Es. tratti: Coraggioso, Spada; diff 3; estrai 2; confusione",
+            Style::default().fg(Color::Gray),
+        )));
+    } else {
+        lines.push(match app.quick_entry_plan() {
+            Ok((challenge, traits)) => {
+                let mut parts = vec![
+                    format!("✓ {} ○", traits.len()),
+                    format!("{} ●", challenge.difficulty),
+                ];
+                if challenge.adrenaline {
+                    parts.push("Adrenalina".to_string());
+                } else {
+                    parts.push(format!("estrai {}", challenge.draw.unwrap_or(MIN_DRAW)));
+                }
+                if challenge.confusion {
+                    parts.push("Confusione".to_string());
+                }
+                Line::from(Span::styled(
+                    parts.join(" · "),
+                    Style::default().fg(Color::Green),
+                ))
+            }
+            Err(e) => Line::from(Span::styled(e, Style::default().fg(Color::Red))),
+        });
+    }
+
+    let suggestions = app.quick_entry_suggestions();
+    if !suggestions.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            suggestions.join("  "),
+            Style::default().fg(Color::Cyan),
+        )));
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(popup_block)
+        .wrap(Wrap { trim: false });
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
@@ -0,0 +1,152 @@
+use ratatui::{
+    Frame,
+    layout::Alignment,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+
+use super::super::utils::centered_rect;
+use crate::app::{App, TraitKind};
+
+/// Renderizza la ricerca fuzzy dei tratti
+pub fn render_search_popup(f: &mut Frame, app: &App) {
+    let area = centered_rect(60, 60, f.area());
+
+    let popup_block = Block::default()
+        .title(Line::from(" Cerca Tratto (nome, tag o descrizione) ").alignment(Alignment::Center))
+        .title_bottom(
+            Line::from(" ↑/↓ per scegliere · Enter per saltare · Esc per chiudere ")
+                .alignment(Alignment::Center),
+        )
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .style(Style::default().bg(Color::Black).fg(Color::Yellow));
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("/ ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                app.search_query.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("▌", Style::default().fg(Color::LightYellow)),
+        ]),
+        Line::from(""),
+    ];
+
+    let results = app.search_traits(&app.search_query);
+    if results.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "Nessun tratto corrisponde",
+            Style::default().fg(Color::Gray),
+        )));
+    }
+
+    // Mantiene visibile la riga selezionata
+    let visible = area.height.saturating_sub(4) as usize;
+    let skip = (app.search_selected + 1).saturating_sub(visible);
+
+    for (pos, &idx) in results.iter().enumerate().skip(skip).take(visible) {
+        let node = &app.honeycomb_nodes[idx];
+        let style = if pos == app.search_selected {
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        let mut spans = vec![
+            Span::styled(format!("{:<10}", TraitKind::of(idx).to_string()), style),
+            Span::styled(format!(" {}", node.text), style),
+        ];
+        if !node.tags.is_empty() {
+            spans.push(Span::styled(
+                format!("  #{}", node.tags.join(" #")),
+                Style::default().fg(Color::Cyan),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let paragraph = Paragraph::new(lines).block(popup_block);
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+/// Renderizza il selettore dei tratti suggeriti per la prova
+pub fn render_picker_popup(f: &mut Frame, app: &App) {
+    let area = centered_rect(60, 60, f.area());
+
+    let popup_block = Block::default()
+        .title(Line::from(" Descrivi la Prova e scegli i Tratti ").alignment(Alignment::Center))
+        .title_bottom(
+            Line::from(" ↑/↓ per scegliere · Enter usa/togli · Esc per chiudere ")
+                .alignment(Alignment::Center),
+        )
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .style(Style::default().bg(Color::Black).fg(Color::Yellow));
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("Prova: ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                app.challenge_description.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("▌", Style::default().fg(Color::LightYellow)),
+        ]),
+        Line::from(""),
+    ];
+
+    let results = app.suggest_traits();
+    if results.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "La scheda non ha ancora tratti",
+            Style::default().fg(Color::Gray),
+        )));
+    }
+
+    // Mantiene visibile la riga selezionata
+    let visible = area.height.saturating_sub(4) as usize;
+    let skip = (app.picker_selected + 1).saturating_sub(visible);
+
+    for (pos, &(idx, score)) in results.iter().enumerate().skip(skip).take(visible) {
+        let node = &app.honeycomb_nodes[idx];
+        let style = if pos == app.picker_selected {
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else if score > 0 {
+            Style::default().fg(Color::LightGreen)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        let check = if app.used_traits.contains(&idx) {
+            "[x] "
+        } else {
+            "[ ] "
+        };
+        let mut spans = vec![
+            Span::styled(check, style),
+            Span::styled(format!("{:<10}", TraitKind::of(idx).to_string()), style),
+            Span::styled(format!(" {}", node.text), style),
+        ];
+        if score > 0 {
+            spans.push(Span::styled(
+                format!("  {}", "★".repeat(score.min(5))),
+                Style::default().fg(Color::LightYellow),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let paragraph = Paragraph::new(lines).block(popup_block);
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
@@ -0,0 +1,202 @@
+use chrono::Local;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Margin},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+};
+
+use super::super::utils::centered_rect;
+use crate::app::{App, BallType, GameSession, format_duration};
+
+/// Formato della data di inizio delle sessioni
+const DATE_FORMAT: &str = "%e %B %Y, %H:%M";
+
+/// Renderizza il diario delle sessioni di gioco
+pub fn render_sessions_popup(f: &mut Frame, app: &App) {
+    let area = centered_rect(85, 85, f.area());
+    f.render_widget(Clear, area);
+
+    let popup_block = Block::default()
+        .title(Line::from(" Diario delle Sessioni ").alignment(Alignment::Center))
+        .title_bottom(
+            Line::from(" ↑/↓ sessione · PgUp/PgDn scorri · N nuova · F termina · Esc chiudi ")
+                .alignment(Alignment::Center),
+        )
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .style(Style::default().bg(Color::Black));
+    let inner = popup_block.inner(area);
+    f.render_widget(popup_block, area);
+
+    let layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
+        .split(inner);
+
+    // Elenco delle sessioni
+    let mut list = Vec::new();
+    if app.journal.sessions.is_empty() {
+        list.push(Line::from(Span::styled(
+            "Nessuna sessione",
+            Style::default().fg(Color::Gray),
+        )));
+    }
+    for (i, session) in app.journal.sessions.iter().enumerate() {
+        let marker = if session.ended.is_none() {
+            "● "
+        } else {
+            "  "
+        };
+        let style = if i == app.sessions_selected {
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        list.push(Line::from(Span::styled(
+            format!("{}{}", marker, session.title),
+            style,
+        )));
+        list.push(Line::from(Span::styled(
+            format!("  {}", session.started.format(DATE_FORMAT)),
+            Style::default().fg(Color::Gray),
+        )));
+    }
+    let list = Paragraph::new(list)
+        .block(Block::default().borders(Borders::RIGHT))
+        .wrap(Wrap { trim: false });
+    f.render_widget(list, layout[0]);
+
+    // Dettaglio della sessione selezionata
+    let mut lines = Vec::new();
+    if let Some(title) = &app.session_title {
+        lines.push(Line::from(vec![
+            Span::styled("Nuova sessione: ", Style::default().fg(Color::Gray)),
+            Span::styled(title.clone(), Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled("▌", Style::default().fg(Color::LightYellow)),
+        ]));
+        lines.push(Line::from(Span::styled(
+            "Enter per iniziare · Esc per annullare",
+            Style::default().fg(Color::Gray),
+        )));
+        lines.push(Line::from(""));
+    }
+    if let Some(session) = app.journal.sessions.get(app.sessions_selected) {
+        lines.extend(session_lines(app, session));
+    }
+
+    let details = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::NONE))
+        .wrap(Wrap { trim: false })
+        .scroll((u16::try_from(app.sessions_scroll).unwrap_or(u16::MAX), 0));
+    f.render_widget(details, layout[1].inner(Margin::new(1, 0)));
+}
+
+/// Righe di dettaglio di una sessione: riepilogo, prove e modifiche alla scheda
+fn session_lines(app: &App, session: &GameSession) -> Vec<Line<'static>> {
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let heading = Style::default()
+        .fg(Color::LightYellow)
+        .add_modifier(Modifier::BOLD);
+
+    let status = if session.ended.is_some() {
+        "conclusa"
+    } else {
+        "in corso"
+    };
+    let mut lines = vec![
+        Line::from(Span::styled(session.title.clone(), heading)),
+        Line::from(vec![
+            Span::styled("Inizio: ", bold),
+            Span::raw(session.started.format(DATE_FORMAT).to_string()),
+        ]),
+        Line::from(vec![
+            Span::styled("Durata: ", bold),
+            Span::raw(format!(
+                "{} ({})",
+                format_duration(session.duration(Local::now())),
+                status
+            )),
+        ]),
+        Line::from(vec![
+            Span::styled("Partecipanti: ", bold),
+            Span::raw(if session.participants.is_empty() {
+                "-".to_string()
+            } else {
+                session.participants.join(", ")
+            }),
+        ]),
+        Line::from(""),
+    ];
+
+    // Il riepilogo delle sessioni in corso è parziale
+    let recap = session
+        .recap
+        .clone()
+        .unwrap_or_else(|| app.journal.recap(session));
+    lines.push(Line::from(Span::styled("Riepilogo", heading)));
+    lines.push(Line::from(format!(
+        "Prove affrontate: {} · riuscite: {} · complicazioni: {}",
+        recap.challenges, recap.successes, recap.complications
+    )));
+    let list = |items: &[String], empty: &str| {
+        if items.is_empty() {
+            empty.to_string()
+        } else {
+            items.join(", ")
+        }
+    };
+    lines.push(Line::from(vec![
+        Span::styled("Sventure: ", bold),
+        Span::raw(list(&recap.misfortunes, "Nessuna")),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("Lezioni: ", bold),
+        Span::raw(list(&recap.lessons, "Nessuna")),
+    ]));
+    lines.push(Line::from(""));
+
+    let draws = app.journal.session_draws(session);
+    if !draws.is_empty() {
+        lines.push(Line::from(Span::styled("Prove", heading)));
+    }
+    for entry in draws {
+        let mut spans = vec![Span::styled(
+            format!("{} ", entry.time.format(DATE_FORMAT)),
+            Style::default().fg(Color::Gray),
+        )];
+        if !entry.player.is_empty() {
+            spans.push(Span::styled(format!("{}: ", entry.player), bold));
+        }
+        if !entry.description.is_empty() {
+            spans.push(Span::raw(format!("{} ", entry.description)));
+        }
+        for ball in entry.first_draw.iter().chain(entry.risk_draw.iter()) {
+            let color = match ball {
+                BallType::White => Color::White,
+                BallType::Red => Color::Red,
+            };
+            spans.push(Span::styled("●", Style::default().fg(color)));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    if !session.changes.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Modifiche alla scheda", heading)));
+    }
+    for change in &session.changes {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{}: ", change.field), bold),
+            Span::styled(change.before.clone(), Style::default().fg(Color::Gray)),
+            Span::raw(" → "),
+            Span::raw(change.after.clone()),
+        ]));
+    }
+
+    lines
+}
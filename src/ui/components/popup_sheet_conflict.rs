@@ -0,0 +1,64 @@
+use ratatui::{
+    Frame,
+    layout::Alignment,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+};
+
+use super::super::utils::centered_rect;
+use crate::app::App;
+
+/// Renderizza il confronto tra la scheda in memoria e il file modificato
+pub fn render_sheet_conflict_popup(f: &mut Frame, app: &App) {
+    let area = centered_rect(70, 70, f.area());
+
+    let popup_block = Block::default()
+        .title(Line::from(" Scheda modificata anche sul file ").alignment(Alignment::Center))
+        .title_bottom(
+            Line::from(" D tieni il file · M tieni la tua versione · Esc rimanda ")
+                .alignment(Alignment::Center),
+        )
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .style(Style::default().bg(Color::Black).fg(Color::Yellow));
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            "Questi campi sono cambiati sia qui sia nel file:",
+            Style::default().fg(Color::White),
+        )),
+        Line::from(""),
+    ];
+    let value = |text: &str| {
+        if text.is_empty() {
+            "[Vuoto]".to_string()
+        } else {
+            text.to_string()
+        }
+    };
+    for conflict in &app.sheet_conflicts {
+        lines.push(Line::from(Span::styled(
+            conflict.field.label(),
+            Style::default().add_modifier(Modifier::BOLD),
+        )));
+        lines.push(Line::from(Span::styled(
+            format!("- qui:  {}", value(&conflict.mine)),
+            Style::default().fg(Color::Red),
+        )));
+        lines.push(Line::from(Span::styled(
+            format!("+ file: {}", value(&conflict.disk)),
+            Style::default().fg(Color::Green),
+        )));
+        lines.push(Line::from(""));
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(popup_block)
+        .wrap(Wrap { trim: false })
+        .scroll((app.sheet_conflict_scroll, 0));
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
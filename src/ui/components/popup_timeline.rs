@@ -0,0 +1,88 @@
+use ratatui::{
+    Frame,
+    layout::Alignment,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+};
+
+use super::super::utils::centered_rect;
+use crate::app::{App, format_time};
+
+/// Renderizza la linea temporale dell'evoluzione del personaggio
+pub fn render_timeline_popup(f: &mut Frame, app: &App) {
+    let area = centered_rect(80, 80, f.area());
+
+    let popup_block = Block::default()
+        .title(Line::from(" Evoluzione del Personaggio ").alignment(Alignment::Center))
+        .title_bottom(
+            Line::from(" ↑/↓ per scorrere · Esc per chiudere ").alignment(Alignment::Center),
+        )
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .style(Style::default().bg(Color::Black));
+
+    let mut lines = vec![Line::from("")];
+    let advancement = &app.advancement;
+
+    if advancement.changes.is_empty() {
+        lines.push(Line::from("Nessuna modifica registrata").alignment(Alignment::Center));
+    }
+
+    for change in advancement.changes.iter().rev() {
+        lines.push(Line::from(Span::styled(
+            format_time(&change.time),
+            Style::default()
+                .fg(Color::LightYellow)
+                .add_modifier(Modifier::BOLD),
+        )));
+
+        let before = if change.before.is_empty() {
+            "[Vuoto]"
+        } else {
+            &change.before
+        };
+        let after = if change.after.is_empty() {
+            "[Vuoto]"
+        } else {
+            &change.after
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{}: ", change.field),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(before.to_string(), Style::default().fg(Color::Gray)),
+            Span::raw(" → "),
+            Span::styled(after.to_string(), Style::default().fg(Color::White)),
+        ]));
+
+        if let Some(lesson) = change.lesson.and_then(|i| advancement.lessons.get(i)) {
+            let origin = match (&lesson.draw, &lesson.draw_time) {
+                (Some(n), Some(time)) => {
+                    format!("Appresa dalla Prova #{} ({})", n, format_time(time))
+                }
+                _ => "Appresa fuori da una prova".to_string(),
+            };
+            lines.push(Line::from(Span::styled(
+                format!("  {}", origin),
+                Style::default().fg(Color::Cyan),
+            )));
+            if let Some(node) = lesson.unlocked_node {
+                lines.push(Line::from(Span::styled(
+                    format!("  Ha sbloccato lo slot {}", node),
+                    Style::default().fg(Color::LightMagenta),
+                )));
+            }
+        }
+        lines.push(Line::from(""));
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(popup_block)
+        .scroll((u16::try_from(app.timeline_scroll).unwrap_or(u16::MAX), 0))
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
@@ -0,0 +1,142 @@
+use ratatui::{
+    Frame,
+    layout::Alignment,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+};
+
+use super::super::utils::centered_rect;
+use crate::app::{App, CharacterWizard, TraitKind, WIZARD_STEPS, WizardStep};
+
+/// Renderizza la procedura guidata di creazione del personaggio
+pub fn render_wizard_popup(f: &mut Frame, app: &App) {
+    let Some(wizard) = app.wizard.as_ref() else {
+        return;
+    };
+
+    let area = centered_rect(60, 70, f.area());
+
+    let popup_block = Block::default()
+        .title(
+            Line::from(format!(
+                " Nuovo Personaggio - Passo {}/{}: {} ",
+                wizard.step.idx() + 1,
+                WIZARD_STEPS,
+                wizard.step.title()
+            ))
+            .alignment(Alignment::Center),
+        )
+        .title_bottom(
+            Line::from(" Enter avanti · ← indietro · ↑/↓ campo · Esc salva bozza ")
+                .alignment(Alignment::Center),
+        )
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .style(Style::default().bg(Color::Black).fg(Color::Yellow));
+
+    let mut text = vec![Line::from("")];
+
+    if wizard.step == WizardStep::Summary {
+        text.extend(summary_lines(wizard));
+    } else {
+        for i in 0..wizard.field_count() {
+            let is_selected = i == wizard.field;
+            let label_style = if is_selected {
+                Style::default()
+                    .fg(Color::LightYellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Gray)
+            };
+            let mut spans = vec![
+                Span::styled(format!("{}: ", wizard.field_label(i)), label_style),
+                Span::styled(
+                    wizard.field_value(i).to_string(),
+                    Style::default().fg(Color::White),
+                ),
+            ];
+            if is_selected {
+                spans.push(Span::styled("▌", Style::default().fg(Color::LightYellow)));
+            }
+            text.push(Line::from(spans));
+        }
+    }
+
+    if let Some(error) = &wizard.error {
+        text.push(Line::from(""));
+        text.push(Line::from(Span::styled(
+            error.clone(),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        )));
+    }
+
+    let paragraph = Paragraph::new(text)
+        .block(popup_block)
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+/// Righe del riepilogo finale
+fn summary_lines(wizard: &CharacterWizard) -> Vec<Line<'static>> {
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let join = |values: Vec<&String>| {
+        values
+            .into_iter()
+            .filter(|v| !v.trim().is_empty())
+            .map(|v| v.trim().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let traits_of = |kind: TraitKind| {
+        join(
+            wizard
+                .nodes
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| TraitKind::of(*i) == kind)
+                .map(|(_, t)| t)
+                .collect(),
+        )
+    };
+
+    vec![
+        Line::from(vec![
+            Span::styled("Nome: ", bold),
+            Span::raw(wizard.base_info.name.clone()),
+        ]),
+        Line::from(vec![
+            Span::styled("Rischierei per: ", bold),
+            Span::raw(wizard.base_info.objective.clone()),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Archetipo: ", bold),
+            Span::raw(traits_of(TraitKind::Archetype)),
+        ]),
+        Line::from(vec![
+            Span::styled("Qualità: ", bold),
+            Span::raw(traits_of(TraitKind::Quality)),
+        ]),
+        Line::from(vec![
+            Span::styled("Abilità: ", bold),
+            Span::raw(traits_of(TraitKind::Ability)),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Risorse: ", bold),
+            Span::raw(join(wizard.resources.iter().collect())),
+        ]),
+        Line::from(vec![
+            Span::styled("Lezione: ", bold),
+            Span::raw(wizard.lesson.clone()),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Premi Enter per creare il personaggio",
+            Style::default().fg(Color::Green),
+        )),
+    ]
+}
@@ -2,24 +2,66 @@ use ratatui::{
     Frame,
     layout::Rect,
     style::{Color, Modifier, Style},
+    text::Line,
     widgets::{Block, BorderType, Borders, Tabs},
 };
 
-use crate::app::App;
+use chrono::Local;
 
-/// Titoli dei tab
-pub const TAB_TITLES: [&str; 4] = ["Fai una Prova", "Scheda pt.1", "Scheda pt.2", "Logs Prove"];
+use crate::app::{App, AppMode, HitId, TabType, Z_BASE, format_duration};
+
+/// Titoli dei tab del ruolo corrente
+fn tab_titles(mode: AppMode) -> Vec<&'static str> {
+    mode.tabs().iter().map(TabType::title).collect()
+}
 
 /// Renderizza la barra dei tab e calcola le aree per l'interazione mouse
 pub fn render_tabs_bar(f: &mut Frame, area: Rect, app: &mut App) {
-    let tabs = Tabs::new(TAB_TITLES.to_vec())
+    let titles = tab_titles(app.mode)
+        .into_iter()
+        .enumerate()
+        .map(|(i, title)| {
+            if app.is_hovered(HitId::Tab(i)) {
+                Line::styled(title, Style::default().fg(Color::LightYellow))
+            } else {
+                Line::from(title)
+            }
+        });
+    let tabs = Tabs::new(titles)
         .block(
             Block::default()
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
-                .title(" Menù (Tab per muoverti) "),
+                .title(match &app.session_label {
+                    Some(label) if app.session_host => format!(
+                        " Menù (Tab per muoverti) · {} · {} collegati ",
+                        label, app.session_peers
+                    ),
+                    Some(label) => format!(" Menù (Tab per muoverti) · {} ", label),
+                    None => " Menù (Tab per muoverti) ".to_string(),
+                })
+                .title(match &app.campaign {
+                    Some(campaign) => format!(" {} ", campaign.name),
+                    None => String::new(),
+                })
+                .title(match app.journal.active() {
+                    Some(session) => format!(
+                        " ⏱ {} {} ",
+                        session.title,
+                        format_duration(session.duration(Local::now()))
+                    ),
+                    None => String::new(),
+                })
+                .title(
+                    Line::from(match &app.status_message {
+                        Some(message) => format!(" {} ", message),
+                        None => String::new(),
+                    })
+                    .right_aligned()
+                    .style(Style::default().fg(Color::LightYellow)),
+                ),
         )
-        .select(app.current_tab.idx())
+        .select(app.mode.tab_idx(app.current_tab))
         .style(Style::default().fg(Color::White))
         .highlight_style(
             Style::default()
@@ -29,30 +71,26 @@ pub fn render_tabs_bar(f: &mut Frame, area: Rect, app: &mut App) {
 
     f.render_widget(tabs, area);
 
-    // Calcola le aree dei tab per l'interazione mouse
-    calculate_tab_areas(area, app);
+    // Registra le aree dei tab per l'interazione mouse
+    register_tab_areas(area, app);
 }
 
-/// Calcola le aree interattive dei singoli tab
-fn calculate_tab_areas(tabs_area: Rect, app: &mut App) {
-    let tab_bar_inner = Rect {
-        x: tabs_area.x + 1,
-        y: tabs_area.y + 1,
-        width: tabs_area.width - 2,
-        height: tabs_area.height - 2,
-    };
-
-    app.tab_areas.clear();
-    let mut current_x = tab_bar_inner.x + 1; // Inizia con 1 spazio di padding
-
-    for title in &TAB_TITLES {
-        let tab_width = title.len() as u16 + 2; // Titolo + 1 spazio per lato
-        app.tab_areas.push(Rect {
-            x: current_x,
-            y: tab_bar_inner.y,
-            width: tab_width,
-            height: tab_bar_inner.height,
-        });
-        current_x += tab_width + 1; // Passa al prossimo tab (con separatore)
+/// Registra le aree interattive dei singoli tab, come le dispone `Tabs`:
+/// uno spazio prima e dopo ogni titolo e un separatore tra un tab e l'altro
+fn register_tab_areas(tabs_area: Rect, app: &mut App) {
+    let inner = Block::default().borders(Borders::ALL).inner(tabs_area);
+    let mut x = inner.x;
+
+    for (i, title) in tab_titles(app.mode).into_iter().enumerate() {
+        let width = (title.chars().count() as u16 + 2).min(inner.right().saturating_sub(x));
+        if width == 0 {
+            break;
+        }
+        app.hits.register(
+            HitId::Tab(i),
+            Rect::new(x, inner.y, width, inner.height),
+            Z_BASE,
+        );
+        x += width + 1;
     }
 }
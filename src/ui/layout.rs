@@ -0,0 +1,104 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph, Wrap},
+};
+
+/// Dimensioni minime del terminale: sotto questa soglia si mostra solo un avviso
+pub const MIN_WIDTH: u16 = 60;
+pub const MIN_HEIGHT: u16 = 22;
+
+/// Dimensioni da cui la griglia esagonale e le sezioni affiancate hanno spazio
+const STANDARD_WIDTH: u16 = 80;
+const STANDARD_HEIGHT: u16 = 38;
+
+/// Larghezza da cui prova e scheda vengono mostrate fianco a fianco
+const WIDE_WIDTH: u16 = 200;
+
+/// Disposizione dell'interfaccia in base alle dimensioni del terminale
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LayoutMode {
+    /// Pannelli impilati e tratti in elenco
+    Compact,
+    Standard,
+    /// Prova e scheda personaggio affiancate
+    Wide,
+}
+
+impl LayoutMode {
+    /// Disposizione adatta all'area; `None` se il terminale è troppo piccolo
+    pub fn for_area(area: Rect) -> Option<Self> {
+        if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+            None
+        } else if area.width < STANDARD_WIDTH || area.height < STANDARD_HEIGHT {
+            Some(LayoutMode::Compact)
+        } else if area.width >= WIDE_WIDTH {
+            Some(LayoutMode::Wide)
+        } else {
+            Some(LayoutMode::Standard)
+        }
+    }
+}
+
+/// Schermata mostrata al posto dell'interfaccia quando il terminale è troppo piccolo
+pub fn render_too_small(f: &mut Frame) {
+    let area = f.area();
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let text = vec![
+        Line::from(Span::styled(
+            "Terminale troppo piccolo",
+            bold.fg(Color::Red),
+        )),
+        Line::from(""),
+        Line::from(format!(
+            "Dimensioni attuali: {}×{}",
+            area.width, area.height
+        )),
+        Line::from(format!("Minimo richiesto: {}×{}", MIN_WIDTH, MIN_HEIGHT)),
+        Line::from(""),
+        Line::from("Ingrandire la finestra o ridurre il carattere."),
+        Line::from(Span::styled(
+            "Q per uscire",
+            Style::default().fg(Color::Gray),
+        )),
+    ];
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Red));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    // Testo centrato verticalmente
+    let top = inner.height.saturating_sub(text.len() as u16) / 2;
+    let inner = Rect {
+        y: inner.y + top,
+        height: inner.height - top,
+        ..inner
+    };
+    let paragraph = Paragraph::new(text)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    f.render_widget(paragraph, inner);
+}
+
+#[cfg(test)]
+mod layout_tests {
+    use crate::ui::layout::*;
+
+    #[test]
+    fn test_breakpoints() {
+        let mode = |w, h| LayoutMode::for_area(Rect::new(0, 0, w, h));
+        assert_eq!(mode(59, 40), None);
+        assert_eq!(mode(120, 21), None);
+        assert_eq!(mode(80, 24), Some(LayoutMode::Compact));
+        assert_eq!(mode(120, 37), Some(LayoutMode::Compact));
+        assert_eq!(mode(120, 40), Some(LayoutMode::Standard));
+        assert_eq!(mode(220, 50), Some(LayoutMode::Wide));
+        // Molto largo ma basso: la scheda non ci starebbe
+        assert_eq!(mode(220, 30), Some(LayoutMode::Compact));
+    }
+}
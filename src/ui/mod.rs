@@ -1,17 +1,33 @@
 use ratatui::{
     Frame,
-    layout::{Constraint, Direction, Layout},
+    layout::{Constraint, Direction, Layout, Rect},
 };
 
-use crate::app::{App, PopupType, TabType};
+use crate::app::{App, HitId, PopupType, TabType, Z_POPUP};
+use layout::LayoutMode;
 
 // Moduli UI
 mod components;
+mod layout;
 mod tabs;
 mod utils;
 
+/// Larghezza della prova quando è affiancata alla scheda
+const WIDE_DRAW_WIDTH: u16 = 80;
+
 /// Funzione principale di rendering dell'interfaccia
 pub fn ui(f: &mut Frame, app: &mut App) {
+    // Le aree cliccabili vengono registrate di nuovo a ogni frame
+    app.hovered = app.hover.and_then(|(x, y)| app.hit_at(x, y));
+    app.hits.clear();
+
+    // Sotto le dimensioni minime l'avviso copre tutto, anche per il mouse
+    let Some(mode) = LayoutMode::for_area(f.area()) else {
+        layout::render_too_small(f);
+        app.hits.register(HitId::Popup, f.area(), Z_POPUP);
+        return;
+    };
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Length(3), Constraint::Min(0)])
@@ -21,24 +37,37 @@ pub fn ui(f: &mut Frame, app: &mut App) {
     components::render_tabs_bar(f, chunks[0], app);
 
     // Renderizza il contenuto in base al tab selezionato
-    render_tab_content(f, chunks[1], app);
+    render_tab_content(f, chunks[1], app, mode);
 
     // Renderizza popup se presenti
     render_popups(f, app);
 }
 
 /// Renderizza il contenuto del tab corrente
-fn render_tab_content(f: &mut Frame, area: ratatui::prelude::Rect, app: &mut App) {
+fn render_tab_content(f: &mut Frame, area: Rect, app: &mut App, mode: LayoutMode) {
     match app.current_tab {
-        TabType::DrawTab => tabs::tab_draw::render(f, area, app),
-        TabType::CharacterSheetTab => tabs::tab_character::render(f, area, app),
+        TabType::DrawTab | TabType::CharacterSheetTab if mode == LayoutMode::Wide => {
+            // Prova e scheda fianco a fianco
+            let layout = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Length(WIDE_DRAW_WIDTH), Constraint::Fill(1)])
+                .split(area);
+            tabs::tab_draw::render(f, layout[0], app, mode);
+            tabs::tab_character::render(f, layout[1], app, mode);
+        }
+        TabType::DrawTab => tabs::tab_draw::render(f, area, app, mode),
+        TabType::CharacterSheetTab => tabs::tab_character::render(f, area, app, mode),
         TabType::AdditionalInfoTab => {
             tabs::tab_list::render(f, area, app);
             // Forza la scrollbar a essere visibile se la sezione ha testo
             ensure_scrollbars_visible(app);
         }
         TabType::LogTab => tabs::tab_log::render(f, area, app),
-        _ => {}
+        TabType::PlayTab => tabs::tab_play::render(f, area, app),
+        TabType::NarratorChallengeTab => tabs::tab_narrator_challenge::render(f, area, app),
+        TabType::NarratorCastTab => tabs::tab_narrator_cast::render(f, area, app),
+        TabType::NarratorPlayersTab => tabs::tab_narrator_players::render(f, area, app),
+        TabType::NarratorNotesTab => tabs::tab_narrator_notes::render(f, area, app),
     }
 }
 
@@ -55,15 +84,124 @@ fn ensure_scrollbars_visible(app: &mut App) {
 }
 
 /// Renderizza i popup sovrapposti al contenuto
-fn render_popups(f: &mut Frame, app: &App) {
-    if app.popup != PopupType::None {
+fn render_popups(f: &mut Frame, app: &mut App) {
+    // I popup coprono tutto lo schermo per il mouse, tranne il menu contestuale
+    let modal = (app.popup != PopupType::None && app.popup != PopupType::ContextMenu)
+        || app.editing_node
+        || app.editing_character_info
+        || app.narrator_field.is_some()
+        || app.editing_list_item;
+    if modal {
+        app.hits.register(HitId::Popup, f.area(), Z_POPUP);
+    }
+
+    if app.popup == PopupType::ContextMenu {
+        // Menu del tasto destro
+        components::render_context_menu(f, app);
+    } else if app.popup == PopupType::CommandPalette {
+        // Palette dei comandi
+        components::render_palette_popup(f, app);
+    } else if app.popup == PopupType::QuickEntry {
+        // Prova scritta in una riga
+        components::render_quick_entry_popup(f, app);
+    } else if app.popup == PopupType::Wizard {
+        // Creazione guidata del personaggio
+        components::render_wizard_popup(f, app);
+    } else if app.popup == PopupType::TraitSearch {
+        // Ricerca dei tratti
+        components::render_search_popup(f, app);
+    } else if app.popup == PopupType::TraitPicker {
+        // Tratti suggeriti per la prova
+        components::render_picker_popup(f, app);
+    } else if app.popup == PopupType::ManualResult {
+        // Esito del sacchetto fisico
+        components::render_manual_popup(f, app);
+    } else if app.popup == PopupType::Campaigns {
+        // Campagne, personaggi e cronaca
+        components::render_campaigns_popup(f, app);
+    } else if app.popup == PopupType::LogFilter {
+        // Filtri e ricerca del log
+        components::render_log_filter_popup(f, app);
+    } else if app.popup == PopupType::Sessions {
+        // Diario delle sessioni di gioco
+        components::render_sessions_popup(f, app);
+    } else if app.popup == PopupType::SheetImport {
+        // Anteprima della scheda da CSV
+        components::render_import_popup(f, app);
+    } else if app.popup == PopupType::SheetConflict {
+        // Scheda cambiata anche sul file
+        components::render_sheet_conflict_popup(f, app);
+    } else if app.popup == PopupType::Timeline {
+        // Evoluzione del personaggio
+        components::render_timeline_popup(f, app);
+    } else if app.popup != PopupType::None {
         // Popup di conferma estrazione/rischio
         components::render_draw_popup(f, app);
     } else if app.editing_node || app.editing_character_info {
         // Popup editing nodi o info personaggio
         components::render_node_edit_popup(f, app);
+    } else if app.narrator_field.is_some() {
+        // Popup editing del narratore
+        components::render_narrator_edit_popup(f, app);
     } else if app.editing_list_item {
         // Popup editing liste
         components::render_list_edit_popup(f, app);
     }
 }
+
+#[cfg(test)]
+mod ui_tests {
+    use ratatui::{Terminal, backend::TestBackend};
+
+    use crate::app::{App, HitId, TabType};
+
+    fn draw(app: &mut App, width: u16, height: u16) {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|f| super::ui(f, app)).unwrap();
+    }
+
+    #[test]
+    fn test_too_small_covers_everything() {
+        let mut app = App::new();
+        draw(&mut app, 50, 15);
+        assert_eq!(app.hit_at(0, 0), Some(HitId::Popup));
+        assert_eq!(app.hit_at(49, 14), Some(HitId::Popup));
+    }
+
+    #[test]
+    fn test_compact_list_keeps_selection_visible() {
+        let mut app = App::new();
+        app.current_tab = TabType::CharacterSheetTab;
+        app.selected_node = Some(18);
+        draw(&mut app, 80, 24);
+        let area = app.hits.area(HitId::Node(18)).unwrap();
+        assert!(area.bottom() <= 23);
+        // I primi tratti sono scorsi fuori dalla vista
+        assert_eq!(app.hits.area(HitId::Node(9)), None);
+    }
+
+    #[test]
+    fn test_wide_shows_draw_and_sheet() {
+        let mut app = App::new();
+        app.current_tab = TabType::DrawTab;
+        draw(&mut app, 220, 45);
+        let draw = app.hits.area(HitId::DrawInput).unwrap();
+        let node = app.hits.area(HitId::Node(9)).unwrap();
+        assert!(draw.right() <= node.left());
+    }
+
+    #[test]
+    fn test_play_tab_shows_traits_misfortunes_and_draw() {
+        let mut app = App::new();
+        app.current_tab = TabType::PlayTab;
+        draw(&mut app, 80, 24);
+        for id in [
+            HitId::Node(0),
+            HitId::Node(18),
+            HitId::Misfortune(3),
+            HitId::DrawInput,
+        ] {
+            assert!(app.hits.area(id).is_some(), "{:?} non disegnato", id);
+        }
+    }
+}
@@ -2,3 +2,8 @@ pub mod tab_character;
 pub mod tab_draw;
 pub mod tab_list;
 pub mod tab_log;
+pub mod tab_narrator_cast;
+pub mod tab_narrator_challenge;
+pub mod tab_narrator_notes;
+pub mod tab_narrator_players;
+pub mod tab_play;
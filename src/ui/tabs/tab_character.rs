@@ -2,24 +2,209 @@ use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    text::Line,
+    text::{Line, Span},
     widgets::{Block, BorderType, Borders, Paragraph, Wrap},
 };
 
-use crate::app::{App, CharacterSection};
+use super::super::layout::LayoutMode;
+use crate::app::{App, CharacterSection, HitId, HoneycombNode, TraitKind, Z_BASE};
+use crate::ui::utils::Hexagon;
 
 fn graph_node_title(idx: usize) -> String {
-    // let s = idx.to_string();
-    match idx {
-        // hardcoded position of cell inside hexgrid graph
-        // counting left to right, top to bottom (0 to 18)
-        9 => " Archetipo ".to_string(),                      //+ &s,
-        4 | 5 | 8 | 10 | 13 | 14 => " Qualità ".to_string(), //+ &s,
-        _ => " Abilità ".to_string(),                        //+ &s,
+    // counting left to right, top to bottom (0 to 18)
+    format!(" {} ", TraitKind::of(idx))
+}
+
+/// Larghezza del pannello con i dettagli del tratto selezionato
+const DETAIL_PANEL_WIDTH: u16 = 36;
+
+/// Larghezza minima per affiancare il pannello dei dettagli alla griglia
+const MIN_WIDTH_WITH_DETAILS: u16 = 72 + DETAIL_PANEL_WIDTH;
+
+/// Dimensioni di un esagono della griglia
+const HEX_WIDTH: u16 = 18;
+const HEX_HEIGHT: u16 = 6;
+
+/// Distanza tra due colonne di esagoni: i lati obliqui vicini restano affiancati
+const HEX_STEP_X: u16 = HEX_WIDTH - 2;
+
+/// Spazio occupato dalla griglia di esagoni (5 colonne, 9 mezze celle)
+const HEX_GRID_WIDTH: u16 = HEX_STEP_X * 4 + HEX_WIDTH;
+const HEX_GRID_HEIGHT: u16 = HEX_HEIGHT / 2 * 8 + HEX_HEIGHT;
+
+/// Riquadro dell'esagono del nodo `idx`, con la griglia centrata in `area`
+fn hex_rect(idx: usize, area: Rect) -> Rect {
+    let (col, row) = HoneycombNode::grid_position(idx);
+    let left = area.x + (area.width - HEX_GRID_WIDTH) / 2;
+    let top = area.y + (area.height - HEX_GRID_HEIGHT) / 2;
+    Rect {
+        x: left + (col + 2) as u16 * HEX_STEP_X,
+        y: top + (row + 4) as u16 * (HEX_HEIGHT / 2),
+        width: HEX_WIDTH,
+        height: HEX_HEIGHT,
+    }
+}
+
+/// Pannello con titolo, descrizione e tag del tratto selezionato
+fn render_trait_detail(f: &mut Frame, area: Rect, app: &App) {
+    let block = Block::default()
+        .title(" Dettagli Tratto ")
+        .title_bottom(" D descrizione · G tag · / cerca ")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded);
+
+    let Some(idx) = app.selected_node else {
+        f.render_widget(Paragraph::new("").block(block), area);
+        return;
+    };
+    let node = &app.honeycomb_nodes[idx];
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            TraitKind::of(idx).to_string(),
+            Style::default().fg(Color::Gray),
+        )),
+        Line::from(Span::styled(
+            if node.text.is_empty() {
+                "[Vuoto]".to_string()
+            } else {
+                node.text.clone()
+            },
+            bold.fg(Color::LightYellow),
+        )),
+        Line::from(""),
+        Line::from(Span::styled("Descrizione", bold)),
+        Line::from(if node.description.is_empty() {
+            "[Vuoto]".to_string()
+        } else {
+            node.description.clone()
+        }),
+        Line::from(""),
+        Line::from(Span::styled("Tag", bold)),
+    ];
+    if node.tags.is_empty() {
+        lines.push(Line::from("[Vuoto]"));
+    } else {
+        lines.push(Line::from(
+            node.tags
+                .iter()
+                .map(|t| Span::styled(format!("#{} ", t), Style::default().fg(Color::Cyan)))
+                .collect::<Vec<_>>(),
+        ));
+    }
+
+    let paragraph = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
+    f.render_widget(paragraph, area);
+}
+
+/// Colore di un tratto: in uso, selezionato, sotto il mouse, oppure secondo la sua posizione
+pub(super) fn node_style(
+    app: &App,
+    idx: usize,
+    legal_slots: &[usize],
+    invalid_abilities: &[usize],
+) -> Style {
+    let node = &app.honeycomb_nodes[idx];
+    if app.used_traits.contains(&idx) {
+        Style::default()
+            .fg(Color::Green)
+            .add_modifier(Modifier::BOLD)
+    } else if app.selected_node == Some(idx) {
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD)
+    } else if app.is_hovered(HitId::Node(idx)) {
+        Style::default().fg(Color::LightYellow)
+    } else if invalid_abilities.contains(&idx) {
+        Style::default().fg(Color::Red)
+    } else if app.advancement.unlocked_nodes().contains(&idx) && node.text.is_empty() {
+        Style::default().fg(Color::LightMagenta)
+    } else if legal_slots.contains(&idx) {
+        Style::default().fg(Color::Cyan)
+    } else {
+        Style::default()
+    }
+}
+
+/// Tratti in elenco, raggruppati per tipo, quando la griglia non entra nel terminale
+fn render_trait_list(f: &mut Frame, area: Rect, app: &mut App) {
+    let block = Block::default()
+        .title(" Tratti (Click per Selezionare, Enter per Modificare, E per Attivare) ")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let legal_slots = HoneycombNode::legal_slots(&app.honeycomb_nodes);
+    let invalid_abilities = HoneycombNode::invalid_abilities(&app.honeycomb_nodes);
+
+    // Righe dell'elenco, con il nodo a cui si riferiscono
+    let mut rows: Vec<(Option<usize>, Line)> = Vec::new();
+    for kind in [TraitKind::Archetype, TraitKind::Quality, TraitKind::Ability] {
+        if !rows.is_empty() {
+            rows.push((None, Line::from("")));
+        }
+        rows.push((
+            None,
+            Line::from(Span::styled(
+                kind.to_string(),
+                Style::default()
+                    .fg(Color::Gray)
+                    .add_modifier(Modifier::BOLD),
+            )),
+        ));
+        for (i, node) in app.honeycomb_nodes.iter().enumerate() {
+            if TraitKind::of(i) != kind {
+                continue;
+            }
+            let marker = if app.selected_node == Some(i) {
+                "▶ "
+            } else {
+                "  "
+            };
+            let text = if node.text.is_empty() {
+                "[Vuoto]"
+            } else {
+                &node.text
+            };
+            let style = node_style(app, i, &legal_slots, &invalid_abilities);
+            rows.push((
+                Some(i),
+                Line::from(Span::styled(format!("{}{}", marker, text), style)),
+            ));
+        }
+    }
+
+    // Scorre quanto basta per tenere visibile il tratto selezionato
+    let height = inner.height as usize;
+    let selected_row = rows
+        .iter()
+        .position(|(i, _)| i.is_some() && *i == app.selected_node)
+        .unwrap_or(0);
+    let offset = (selected_row + 1).saturating_sub(height);
+
+    let mut node_areas = Vec::new();
+    let mut lines = Vec::new();
+    for (row, (idx, line)) in rows.into_iter().skip(offset).take(height).enumerate() {
+        if let Some(idx) = idx {
+            let area = Rect {
+                y: inner.y + row as u16,
+                height: 1,
+                ..inner
+            };
+            node_areas.push((idx, area));
+        }
+        lines.push(line);
+    }
+    f.render_widget(Paragraph::new(lines), inner);
+
+    for (i, area) in node_areas {
+        app.hits.register(HitId::Node(i), area, Z_BASE);
     }
 }
 
-pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
+pub fn render(f: &mut Frame, area: Rect, app: &mut App, mode: LayoutMode) {
     let main_layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -29,13 +214,22 @@ pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
         .split(area);
 
     // Name and Objective section
-    let upper_layout = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
+    let upper_constraints = if mode == LayoutMode::Compact {
+        [
+            Constraint::Fill(2),
+            Constraint::Length(1),
+            Constraint::Fill(3),
+        ]
+    } else {
+        [
             Constraint::Length(30), // name section
             Constraint::Fill(1),    // blank
             Constraint::Length(45), // objective section
-        ])
+        ]
+    };
+    let upper_layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(upper_constraints)
         .split(main_layout[0]);
 
     // Name section
@@ -61,8 +255,9 @@ pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
         .wrap(Wrap { trim: true })
         .alignment(Alignment::Center);
 
-    app.character_name_area = upper_layout[0];
-    f.render_widget(paragraph, app.character_name_area);
+    app.hits
+        .register(HitId::CharacterName, upper_layout[0], Z_BASE);
+    f.render_widget(paragraph, upper_layout[0]);
 
     // Objective section
     let draw_style = if app.selected_character_info == CharacterSection::CharacterObjective {
@@ -87,22 +282,48 @@ pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
         .wrap(Wrap { trim: true })
         .alignment(Alignment::Center);
 
-    app.character_objective_area = upper_layout[2];
-    f.render_widget(paragraph, app.character_objective_area);
+    app.hits
+        .register(HitId::CharacterObjective, upper_layout[2], Z_BASE);
+    f.render_widget(paragraph, upper_layout[2]);
+
+    if mode == LayoutMode::Compact {
+        render_trait_list(f, main_layout[1], app);
+        return;
+    }
+
+    // Pannello dei dettagli a destra, solo se c'è spazio per la griglia
+    let (graph_section, detail_section) = if main_layout[1].width >= MIN_WIDTH_WITH_DETAILS {
+        let layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Fill(1), Constraint::Length(DETAIL_PANEL_WIDTH)])
+            .split(main_layout[1]);
+        (layout[0], Some(layout[1]))
+    } else {
+        (main_layout[1], None)
+    };
+
+    if let Some(area) = detail_section {
+        render_trait_detail(f, area, app);
+    }
 
     // Honeycomb section
     let block = Block::default()
         .title(
             " Scheda HexSys (Click per Selezionare, Enter per Modificare, E per Attivare Tratto) ",
         )
+        .title_bottom(Line::from(vec![
+            Span::styled(" Slot disponibili ", Style::default().fg(Color::Cyan)),
+            Span::styled(" Abilità senza Qualità ", Style::default().fg(Color::Red)),
+            Span::styled(
+                " Sbloccati da lezioni (U) ",
+                Style::default().fg(Color::LightMagenta),
+            ),
+        ]))
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded);
 
-    let inner_area = block.inner(main_layout[1]);
-    f.render_widget(block, main_layout[1]);
-
-    // Store area for click detection
-    app.graph_area = inner_area;
+    let inner_area = block.inner(graph_section);
+    f.render_widget(block, graph_section);
 
     // Check if area is too small
     if inner_area.width < 20 || inner_area.height < 10 {
@@ -114,67 +335,77 @@ pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
         return;
     }
 
+    // Esagoni veri se c'è spazio, altrimenti celle rettangolari
+    let hex_mode = inner_area.width >= HEX_GRID_WIDTH && inner_area.height >= HEX_GRID_HEIGHT;
+
     // Calculate center of the area
     let center_x = (inner_area.x / 2 + inner_area.width) / 2;
     let center_y = (inner_area.y / 2 + inner_area.height) / 2;
 
+    // Slot compilabili e Abilità scollegate dalle Qualità
+    let legal_slots = HoneycombNode::legal_slots(&app.honeycomb_nodes);
+    let invalid_abilities = HoneycombNode::invalid_abilities(&app.honeycomb_nodes);
+
     // Render each node
+    let mut node_areas = Vec::new();
     for (i, node) in app.honeycomb_nodes.iter().enumerate() {
-        // Calculate node position with proper bounds checking
-        let node_x_calc = (center_x - node.width / 2) as i32 + node.x as i32;
-        let node_y_calc = (center_y + node.height / 2) as i32 + node.y as i32;
-
-        // Skip if node would be outside bounds
-        if node_x_calc < inner_area.x as i32
-            || node_y_calc < inner_area.y as i32
-            || node_x_calc + node.width as i32 > (inner_area.x + inner_area.width) as i32
-            || node_y_calc + node.height as i32 > (inner_area.y + inner_area.height) as i32
-        {
-            continue;
-        }
+        let node_rect = if hex_mode {
+            hex_rect(i, inner_area)
+        } else {
+            // Calculate node position with proper bounds checking
+            let node_x_calc = (center_x - node.width / 2) as i32 + node.x as i32;
+            let node_y_calc = (center_y + node.height / 2) as i32 + node.y as i32;
 
-        let node_x = node_x_calc as u16;
-        let node_y = node_y_calc as u16;
+            // Skip if node would be outside bounds
+            if node_x_calc < inner_area.x as i32
+                || node_y_calc < inner_area.y as i32
+                || node_x_calc + node.width as i32 > (inner_area.x + inner_area.width) as i32
+                || node_y_calc + node.height as i32 > (inner_area.y + inner_area.height) as i32
+            {
+                continue;
+            }
 
-        let is_selected = app.selected_node == Some(i);
-        let is_trait_used = app.used_traits.contains(&i);
+            Rect {
+                x: node_x_calc as u16,
+                y: node_y_calc as u16,
+                width: node.width,
+                height: node.height,
+            }
+        };
+
+        let node_style = node_style(app, i, &legal_slots, &invalid_abilities);
 
-        let node_style = if is_trait_used {
-            Style::default()
-                .fg(Color::Green)
-                .add_modifier(Modifier::BOLD)
-        } else if is_selected {
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD)
+        let node_text = if node.text.is_empty() {
+            "[Vuoto]"
         } else {
-            Style::default()
+            &node.text
         };
 
+        if hex_mode {
+            let title = graph_node_title(i);
+            let hexagon = Hexagon::new(node_text).title(&title).style(node_style);
+            f.render_widget(hexagon, node_rect);
+            node_areas.extend(Hexagon::rows(node_rect).map(|row| (i, row)));
+            continue;
+        }
+
         let node_block = Block::default()
             .title(Line::from(graph_node_title(i)).centered())
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
             .style(node_style);
 
-        let node_rect = Rect {
-            x: node_x,
-            y: node_y,
-            width: node.width,
-            height: node.height,
-        };
-
-        let node_text = if node.text.is_empty() {
-            "[Vuoto]"
-        } else {
-            &node.text
-        };
-
         let paragraph = Paragraph::new("\n".to_owned() + node_text)
             .block(node_block)
             .alignment(Alignment::Center)
             .wrap(Wrap { trim: true });
 
         f.render_widget(paragraph, node_rect);
+        node_areas.push((i, node_rect));
+    }
+
+    // Store areas for click detection
+    for (i, area) in node_areas {
+        app.hits.register(HitId::Node(i), area, Z_BASE);
     }
 }
@@ -6,11 +6,17 @@ use ratatui::{
     widgets::{Block, BorderType, Borders, Paragraph, Wrap},
 };
 
+use super::super::layout::LayoutMode;
 use super::super::utils::{create_empty_balls_display, create_filled_balls_display};
-use crate::app::{App, BallType, FocusedSection};
+use crate::app::{App, BallType, FocusedSection, HitId, Z_BASE};
 
 /// Renderizza il tab principale per l'estrazione
-pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
+pub fn render(f: &mut Frame, area: Rect, app: &mut App, mode: LayoutMode) {
+    if mode != LayoutMode::Standard {
+        render_stacked(f, area, app);
+        return;
+    }
+
     let main_layout = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
@@ -20,6 +26,26 @@ pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
     render_right_section(f, main_layout[1], app);
 }
 
+/// Sezioni impilate in una colonna, senza istruzioni: terminali piccoli o prova affiancata alla scheda
+pub(super) fn render_stacked(f: &mut Frame, area: Rect, app: &mut App) {
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(4), // white balls
+            Constraint::Length(3), // red balls
+            Constraint::Min(5),    // draw balls
+            Constraint::Length(4), // status (confusione/adrenalina)
+            Constraint::Length(3), // reset
+        ])
+        .split(area);
+
+    render_white_balls_section(f, layout[0], app);
+    render_red_balls_section(f, layout[1], app);
+    render_draw_section(f, layout[2], app);
+    render_status_section(f, layout[3], app);
+    render_reset_section(f, layout[4], app);
+}
+
 /// Renderizza la sezione sinistra (palline bianche, rosse, reset, istruzioni)
 fn render_left_section(f: &mut Frame, area: Rect, app: &mut App) {
     let layout = Layout::default()
@@ -60,22 +86,37 @@ fn render_white_balls_section(f: &mut Frame, area: Rect, app: &mut App) {
         Style::default()
             .fg(Color::Yellow)
             .add_modifier(Modifier::BOLD)
+    } else if app.is_hovered(HitId::WhiteBalls) {
+        Style::default().fg(Color::LightYellow)
     } else {
         Style::default()
     };
 
     let block = Block::default()
-        .title(" Quanti TRATTI vuoi usare? (↑/↓ per selezionare) ")
+        .title(" Quanti TRATTI vuoi usare? (↑/↓ per selezionare, S per i suggeriti) ")
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
         .style(style);
 
-    let balls_text = create_filled_balls_display(app.white_balls, Color::White);
-    let paragraph = Paragraph::new(balls_text)
+    let mut lines = vec![create_filled_balls_display(app.white_balls, Color::White)];
+    if !app.used_traits.is_empty() {
+        let traits = app
+            .used_traits
+            .iter()
+            .map(|idx| app.honeycomb_nodes[*idx].text.clone())
+            .collect::<Vec<_>>()
+            .join(", ");
+        lines.push(Line::from(Span::styled(
+            traits,
+            Style::default().fg(Color::Gray),
+        )));
+    }
+    let paragraph = Paragraph::new(lines)
         .block(block)
-        .alignment(Alignment::Center);
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
 
-    app.white_balls_area = area;
+    app.hits.register(HitId::WhiteBalls, area, Z_BASE);
     f.render_widget(paragraph, area);
 }
 
@@ -85,6 +126,8 @@ fn render_red_balls_section(f: &mut Frame, area: Rect, app: &mut App) {
         Style::default()
             .fg(Color::Yellow)
             .add_modifier(Modifier::BOLD)
+    } else if app.is_hovered(HitId::RedBalls) {
+        Style::default().fg(Color::LightYellow)
     } else {
         Style::default()
     };
@@ -100,7 +143,7 @@ fn render_red_balls_section(f: &mut Frame, area: Rect, app: &mut App) {
         .block(block)
         .alignment(Alignment::Center);
 
-    app.red_balls_area = area;
+    app.hits.register(HitId::RedBalls, area, Z_BASE);
     f.render_widget(paragraph, area);
 }
 
@@ -110,12 +153,19 @@ fn render_draw_section(f: &mut Frame, area: Rect, app: &mut App) {
         Style::default()
             .fg(Color::Yellow)
             .add_modifier(Modifier::BOLD)
+    } else if app.is_hovered(HitId::DrawInput) {
+        Style::default().fg(Color::LightYellow)
     } else {
         Style::default()
     };
 
+    let title = if app.manual_mode {
+        " Registra una PROVA col sacchetto fisico (Enter, M per l'app) "
+    } else {
+        " Effettua una PROVA (↑/↓ per selezionare, poi Enter; M sacchetto fisico) "
+    };
     let block = Block::default()
-        .title(" Effettua una PROVA (↑/↓ per selezionare, poi Enter) ")
+        .title(title)
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
         .style(style);
@@ -126,14 +176,24 @@ fn render_draw_section(f: &mut Frame, area: Rect, app: &mut App) {
         .alignment(Alignment::Center)
         .wrap(Wrap { trim: true });
 
-    app.draw_input_area = area;
+    app.hits.register(HitId::DrawInput, area, Z_BASE);
     f.render_widget(paragraph, area);
 }
 
 /// Crea il contenuto della sezione estrazione
 fn create_draw_content(app: &App) -> Vec<Line<'static>> {
+    let description = if app.challenge_description.is_empty() {
+        Line::from("")
+    } else {
+        Line::from(Span::styled(
+            format!("« {} »", app.challenge_description),
+            Style::default()
+                .fg(Color::LightYellow)
+                .add_modifier(Modifier::ITALIC),
+        ))
+    };
     let mut lines = vec![
-        Line::from(""),
+        description,
         Line::from(Span::styled(
             " Quanti TOKEN vuoi ESTRARRE? ",
             Style::default().add_modifier(Modifier::BOLD),
@@ -170,8 +230,8 @@ fn render_status_section(f: &mut Frame, area: Rect, app: &mut App) {
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
         .split(area);
 
-    app.random_mode_area = layout[0];
-    app.forced_four_area = layout[1];
+    app.hits.register(HitId::RandomMode, layout[0], Z_BASE);
+    app.hits.register(HitId::ForcedFour, layout[1], Z_BASE);
 
     render_confusion_button(f, layout[0], app);
     render_adrenaline_button(f, layout[1], app);
@@ -187,6 +247,8 @@ fn render_confusion_button(f: &mut Frame, area: Rect, app: &App) {
         Style::default()
             .fg(Color::Yellow)
             .add_modifier(Modifier::BOLD)
+    } else if app.is_hovered(HitId::RandomMode) {
+        Style::default().fg(Color::LightYellow)
     } else {
         Style::default()
     };
@@ -229,6 +291,8 @@ fn render_adrenaline_button(f: &mut Frame, area: Rect, app: &App) {
         Style::default()
             .fg(Color::Yellow)
             .add_modifier(Modifier::BOLD)
+    } else if app.is_hovered(HitId::ForcedFour) {
+        Style::default().fg(Color::LightYellow)
     } else {
         Style::default()
     };
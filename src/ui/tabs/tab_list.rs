@@ -7,7 +7,7 @@ use ratatui::{
 };
 
 use super::super::utils::create_filled_balls_display;
-use crate::app::{App, ListSection, get_section_type};
+use crate::app::{App, HitId, ListSection, Z_BASE, get_section_type};
 
 fn render_list_items<'a>(
     list_idx: usize,
@@ -99,7 +99,8 @@ pub fn render_misfortunes_section(f: &mut Frame, area: Rect, app: &mut App) {
             .wrap(Wrap { trim: true })
             .alignment(Alignment::Center);
 
-        app.misfortunes_area[i] = misfortunes_layout[i];
+        app.hits
+            .register(HitId::Misfortune(i), misfortunes_layout[i], Z_BASE);
         f.render_widget(paragraph, misfortunes_layout[i]);
     }
 }
@@ -145,7 +146,11 @@ pub fn render_misfortunes_red_balls_section(f: &mut Frame, area: Rect, app: &mut
             .wrap(Wrap { trim: true })
             .alignment(Alignment::Center);
 
-        app.misfortunes_red_balls_area[i] = misfortunes_red_balls_layout[i];
+        app.hits.register(
+            HitId::MisfortuneDifficulty(i),
+            misfortunes_red_balls_layout[i],
+            Z_BASE,
+        );
         f.render_widget(paragraph, misfortunes_red_balls_layout[i]);
     }
 }
@@ -193,7 +198,8 @@ pub fn render_resources_section(f: &mut Frame, area: Rect, app: &mut App) {
         .collect();
 
     let paragraph = Paragraph::new(items).block(block).wrap(Wrap { trim: true });
-    app.resources_area[0] = resources_layout[0];
+    app.hits
+        .register(HitId::Resources, resources_layout[0], Z_BASE);
     f.render_widget(paragraph, resources_layout[0]);
 
     // Notes section
@@ -213,10 +219,13 @@ pub fn render_resources_section(f: &mut Frame, area: Rect, app: &mut App) {
 
     let paragraph = Paragraph::new(text)
         .block(block)
-        .scroll((app.notes_vertical_scroll as u16, 0))
+        .scroll((
+            u16::try_from(app.notes_vertical_scroll).unwrap_or(u16::MAX),
+            0,
+        ))
         .wrap(Wrap { trim: true });
 
-    app.resources_area[1] = resources_layout[1];
+    app.hits.register(HitId::Notes, resources_layout[1], Z_BASE);
     f.render_widget(paragraph, resources_layout[1]);
 
     // Render scrollbar
@@ -259,10 +268,14 @@ pub fn render_lessons_section(f: &mut Frame, area: Rect, app: &mut App) {
 
         let paragraph = Paragraph::new(text)
             .block(block)
-            .scroll((app.list_vertical_scroll[i] as u16, 0))
+            .scroll((
+                u16::try_from(app.list_vertical_scroll[i]).unwrap_or(u16::MAX),
+                0,
+            ))
             .wrap(Wrap { trim: true });
 
-        app.lections_area[i] = lessons_layout[i];
+        app.hits
+            .register(HitId::Lesson(i), lessons_layout[i], Z_BASE);
         f.render_widget(paragraph, lessons_layout[i]);
 
         // Render scrollbar
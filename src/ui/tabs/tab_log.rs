@@ -6,178 +6,244 @@ use ratatui::{
     widgets::{Block, BorderType, Borders, Paragraph, Scrollbar, ScrollbarOrientation, Wrap},
 };
 
-use crate::app::{App, BallType};
+use crate::app::{App, BallType, DrawHistory, HitId, Z_BASE, lines_height};
 
 pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
+    app.hits.register(HitId::Log, area, Z_BASE);
+    let entries = app.log_entries();
+    let title = if app.log_filter.is_active() {
+        format!(
+            " Log - {} prove filtrate (↑/↓ prova · PgUp/PgDn · / cerca · F filtri) ",
+            entries.len()
+        )
+    } else {
+        " Log - Cronologia Prove (↑/↓ prova · PgUp/PgDn · / cerca · F filtri) ".to_string()
+    };
     let block = Block::default()
-        .title(" Log - Cronologia Prove (↑/↓ per scorrere) ")
+        .title(title)
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded);
 
-    if app.history.is_empty() {
-        let text = Paragraph::new("Nessuna prova effettuata")
+    if entries.is_empty() {
+        let message = if app.log_filter.is_active() {
+            "Nessuna prova corrisponde ai filtri"
+        } else {
+            "Nessuna prova effettuata"
+        };
+        let text = Paragraph::new(message)
             .block(block)
             .alignment(Alignment::Center);
         f.render_widget(text, area);
+        app.set_log_layout(area, Vec::new(), 0);
         return;
     }
 
+    // Altezza reale di ogni prova, a capo compresi, per scorrere e saltare tra le prove
+    let width = area.width.saturating_sub(2);
+    let mut lines = vec![Line::from("")];
+    let mut offsets = Vec::with_capacity(entries.len());
+    let mut height = lines.len();
+    for (pos, (number, entry)) in entries.iter().enumerate() {
+        let entry_lines = entry_lines(app, *number, entry, pos == app.log_selected);
+        offsets.push(height);
+        height += lines_height(&entry_lines, width);
+        lines.extend(entry_lines);
+    }
+    app.set_log_layout(area, offsets, height);
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .scroll((u16::try_from(app.vertical_scroll).unwrap_or(u16::MAX), 0))
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(paragraph, area);
+
+    // Render scrollbar
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(Some("↑"))
+        .end_symbol(Some("↓"));
+
+    f.render_stateful_widget(scrollbar, area, &mut app.vertical_scroll_state);
+}
+
+/// Righe di una prova del log
+fn entry_lines(
+    app: &App,
+    number: usize,
+    entry: &DrawHistory,
+    selected: bool,
+) -> Vec<Line<'static>> {
     let mut lines = Vec::new();
+    let player = if entry.player.is_empty() {
+        String::new()
+    } else {
+        format!(" di {}", entry.player)
+    };
+    let mut heading = Style::default()
+        .fg(Color::LightYellow)
+        .add_modifier(Modifier::BOLD);
+    if selected {
+        heading = heading.add_modifier(Modifier::REVERSED);
+    }
+    lines.push(Line::from(Span::styled(
+        format!("{} - Prova #{}{}: ", entry.time_label(), number, player),
+        heading,
+    )));
     lines.push(Line::from(""));
 
-    for (i, entry) in app.history.iter().enumerate().rev() {
+    if !entry.description.is_empty() {
+        lines.push(Line::from(vec![
+            Span::styled("Prova: ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(entry.description.clone()),
+        ]));
+    }
+
+    if entry.manual {
         lines.push(Line::from(Span::styled(
-            format!("{} - Prova #{}: ", entry.time, i + 1),
-            Style::default()
-                .fg(Color::LightYellow)
-                .add_modifier(Modifier::BOLD),
+            "Estrazione manuale (sacchetto fisico)",
+            Style::default().fg(Color::Gray),
         )));
-        lines.push(Line::from(""));
+    }
 
-        let mut s: String = String::new();
-        if !entry.traits.is_empty() {
-            for idx in &entry.traits {
-                s.push_str(&format!("{}, ", app.honeycomb_nodes[*idx].text));
-            }
+    // Esito della verifica per le prove estratte dall'host
+    if let Some(verified) = app.draw_verified(entry) {
+        let (text, color) = if verified {
+            ("✓ estratta dall'host, seme verificato", Color::Green)
         } else {
-            s.push_str("Nessuno");
-        }
-
-        // Pallini bianchi usati
+            ("✗ non verificata", Color::Red)
+        };
         lines.push(Line::from(vec![
-            Span::styled(
-                "Totale Token messi in gioco: ",
-                Style::default().add_modifier(Modifier::BOLD),
-            ),
-            Span::raw(format!("{}", entry.white_balls)),
-        ]));
-        lines.push(Line::from(vec![
-            Span::styled(
-                "Tratti della scheda utilizzati: ",
-                Style::default().add_modifier(Modifier::BOLD),
-            ),
-            Span::raw(s.to_string()),
+            Span::styled("Verifica: ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled(text, Style::default().fg(color)),
         ]));
+    }
 
-        lines.push(Line::from(""));
-
-        let mut s: String = String::new();
-        let mut b: bool = false;
-        // idx contains the number of additional red balls where I need the position in the array
-        for (i, idx) in entry.misfortunes.iter().enumerate() {
-            if *idx != 0 {
-                b = true;
-                s.push_str(&format!("{}, ", app.list_data.misfortunes[i]));
-            }
-        }
-        if !b {
-            s.push_str("Nessuna");
+    let mut s: String = String::new();
+    if !entry.trait_names.is_empty() {
+        for name in &entry.trait_names {
+            s.push_str(&format!("{}, ", name));
         }
+    } else {
+        s.push_str("Nessuno");
+    }
 
-        // Pallini rossi usati
-        lines.push(Line::from(vec![
-            Span::styled(
-                "Difficoltà: ",
-                Style::default().add_modifier(Modifier::BOLD),
-            ),
-            Span::raw(format!("{}", entry.red_balls)),
-        ]));
+    // Pallini bianchi usati
+    lines.push(Line::from(vec![
+        Span::styled(
+            "Totale Token messi in gioco: ",
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(format!("{}", entry.white_balls)),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled(
+            "Tratti della scheda utilizzati: ",
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(s.to_string()),
+    ]));
+
+    lines.push(Line::from(""));
+
+    let mut s: String = String::new();
+    for name in &entry.misfortune_names {
+        s.push_str(&format!("{}, ", name));
+    }
+    if entry.misfortune_names.is_empty() {
+        s.push_str("Nessuna");
+    }
+
+    // Pallini rossi usati
+    lines.push(Line::from(vec![
+        Span::styled(
+            "Difficoltà: ",
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(format!("{}", entry.red_balls)),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled(
+            "Sventure messe in gioco: ",
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(s.to_string()),
+    ]));
+
+    lines.push(Line::from(""));
+
+    // Risultato prima pescata
+    let first_draw_str = entry.format_balls(&entry.first_draw);
+    lines.push(Line::from(vec![
+        Span::styled(
+            "Token pescati: ",
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(format!("{} ({})", entry.first_draw.len(), first_draw_str)),
+    ]));
+
+    // Visualizza i pallini della prima pescata
+    let mut first_draw_spans = vec![Span::raw("  ")];
+    for ball in &entry.first_draw {
+        let (symbol, color) = match ball {
+            BallType::White => ("● ", Color::White),
+            BallType::Red => ("● ", Color::Red),
+        };
+        first_draw_spans.push(Span::styled(symbol, Style::default().fg(color)));
+    }
+    lines.push(Line::from(first_draw_spans));
+
+    lines.push(Line::from(""));
+
+    // Rischio
+    if entry.risked {
+        let risk_draw_str = entry.format_balls(&entry.risk_draw);
         lines.push(Line::from(vec![
-            Span::styled(
-                "Sventure messe in gioco: ",
-                Style::default().add_modifier(Modifier::BOLD),
-            ),
-            Span::raw(s.to_string()),
+            Span::styled("Rischiato: ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled("Sì", Style::default().fg(Color::Green)),
         ]));
 
-        lines.push(Line::from(""));
-
-        // Risultato prima pescata
-        let first_draw_str = entry.format_balls(&entry.first_draw);
         lines.push(Line::from(vec![
             Span::styled(
-                "Token pescati: ",
+                "  Risultato rischio: ",
                 Style::default().add_modifier(Modifier::BOLD),
             ),
-            Span::raw(format!("{} ({})", entry.first_draw.len(), first_draw_str)),
+            Span::raw(format!("{} ({})", entry.risk_draw.len(), risk_draw_str)),
         ]));
 
-        // Visualizza i pallini della prima pescata
-        let mut first_draw_spans = vec![Span::raw("  ")];
-        for ball in &entry.first_draw {
+        // Visualizza i pallini del rischio
+        let mut risk_draw_spans = vec![Span::raw("    ")];
+        for ball in &entry.risk_draw {
             let (symbol, color) = match ball {
                 BallType::White => ("● ", Color::White),
                 BallType::Red => ("● ", Color::Red),
             };
-            first_draw_spans.push(Span::styled(symbol, Style::default().fg(color)));
+            risk_draw_spans.push(Span::styled(symbol, Style::default().fg(color)));
         }
-        lines.push(Line::from(first_draw_spans));
+        lines.push(Line::from(risk_draw_spans));
+    } else {
+        lines.push(Line::from(vec![
+            Span::styled("Rischiato: ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled("No", Style::default().fg(Color::Red)),
+        ]));
+    }
 
+    if entry.confused {
         lines.push(Line::from(""));
+        lines.push(Line::from(vec![Span::styled(
+            "Sotto effetto di Confusione",
+            Style::default().add_modifier(Modifier::BOLD),
+        )]));
+    }
 
-        // Rischio
-        if entry.risked {
-            let risk_draw_str = entry.format_balls(&entry.risk_draw);
-            lines.push(Line::from(vec![
-                Span::styled("Rischiato: ", Style::default().add_modifier(Modifier::BOLD)),
-                Span::styled("Sì", Style::default().fg(Color::Green)),
-            ]));
-
-            lines.push(Line::from(vec![
-                Span::styled(
-                    "  Risultato rischio: ",
-                    Style::default().add_modifier(Modifier::BOLD),
-                ),
-                Span::raw(format!("{} ({})", entry.risk_draw.len(), risk_draw_str)),
-            ]));
-
-            // Visualizza i pallini del rischio
-            let mut risk_draw_spans = vec![Span::raw("    ")];
-            for ball in &entry.risk_draw {
-                let (symbol, color) = match ball {
-                    BallType::White => ("● ", Color::White),
-                    BallType::Red => ("● ", Color::Red),
-                };
-                risk_draw_spans.push(Span::styled(symbol, Style::default().fg(color)));
-            }
-            lines.push(Line::from(risk_draw_spans));
-        } else {
-            lines.push(Line::from(vec![
-                Span::styled("Rischiato: ", Style::default().add_modifier(Modifier::BOLD)),
-                Span::styled("No", Style::default().fg(Color::Red)),
-            ]));
-        }
-
-        if entry.confused {
-            lines.push(Line::from(""));
-            lines.push(Line::from(vec![Span::styled(
-                "Sotto effetto di Confusione",
-                Style::default().add_modifier(Modifier::BOLD),
-            )]));
-        }
-
-        if entry.adrenalined {
-            lines.push(Line::from(""));
-            lines.push(Line::from(vec![Span::styled(
-                "Sotto effetto di Adrenalina",
-                Style::default().add_modifier(Modifier::BOLD),
-            )]));
-        }
-
+    if entry.adrenalined {
         lines.push(Line::from(""));
+        lines.push(Line::from(vec![Span::styled(
+            "Sotto effetto di Adrenalina",
+            Style::default().add_modifier(Modifier::BOLD),
+        )]));
     }
 
-    let paragraph = Paragraph::new(lines)
-        .block(block)
-        .scroll((app.vertical_scroll as u16, 0))
-        .wrap(Wrap { trim: true });
-
-    f.render_widget(paragraph, area);
-
-    // Render scrollbar
-    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
-        .begin_symbol(Some("↑"))
-        .end_symbol(Some("↓"));
+    lines.push(Line::from(""));
 
-    f.render_stateful_widget(scrollbar, area, &mut app.vertical_scroll_state);
+    lines
 }
@@ -0,0 +1,113 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph, Wrap},
+};
+
+use crate::app::{App, CastSection};
+
+/// Renderizza il tab del narratore con PNG e minacce
+pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
+    let layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+
+    let npcs: Vec<(String, String, String)> = app
+        .narrator
+        .npcs
+        .iter()
+        .map(|n| (n.name.clone(), format!("● {}", n.traits), n.notes.clone()))
+        .collect();
+    let threats: Vec<(String, String, String)> = app
+        .narrator
+        .threats
+        .iter()
+        .map(|t| {
+            let state = if t.active { "attiva" } else { "inattiva" };
+            (
+                t.name.clone(),
+                format!("● +{} {}", t.tokens, state),
+                t.notes.clone(),
+            )
+        })
+        .collect();
+
+    render_list(
+        f,
+        layout[0],
+        app,
+        CastSection::Npcs,
+        " PNG (● token bianchi) ",
+        &npcs,
+        Color::White,
+    );
+    render_list(
+        f,
+        layout[1],
+        app,
+        CastSection::Threats,
+        " Minacce (● token rossi, E per attivare) ",
+        &threats,
+        Color::Red,
+    );
+}
+
+/// Renderizza un elenco di PNG o minacce
+fn render_list(
+    f: &mut Frame,
+    area: Rect,
+    app: &App,
+    section: CastSection,
+    title: &str,
+    entries: &[(String, String, String)],
+    color: Color,
+) {
+    let is_focused = app.cast_section == section;
+    let block = Block::default()
+        .title(title.to_string())
+        .title_bottom(" A nuovo · X elimina · Enter nome · D note · +/- ")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .style(if is_focused {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default()
+        });
+
+    let mut lines = Vec::new();
+    if entries.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "[Vuoto] premi A per aggiungere",
+            Style::default().fg(Color::Gray),
+        )));
+    }
+    for (i, (name, tokens, notes)) in entries.iter().enumerate() {
+        let style = if is_focused && i == app.selected_cast {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        let name = if name.is_empty() { "[Vuoto]" } else { name };
+        lines.push(Line::from(vec![
+            Span::styled(name.to_string(), style),
+            Span::styled(format!("  {}", tokens), Style::default().fg(color)),
+        ]));
+        if !notes.is_empty() {
+            lines.push(Line::from(Span::styled(
+                format!("  {}", notes.replace('\n', " ")),
+                Style::default().fg(Color::Gray),
+            )));
+        }
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+
+    f.render_widget(paragraph, area);
+}
@@ -0,0 +1,200 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph, Wrap},
+};
+
+use super::super::utils::{create_empty_balls_display, create_filled_balls_display};
+use crate::app::{App, BallType, NarratorFocus};
+
+/// Renderizza il tab del narratore per impostare la sfida
+pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
+    let main_layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+
+    let left = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
+        .split(main_layout[0]);
+    let right = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage(40),
+            Constraint::Percentage(30),
+            Constraint::Percentage(30),
+        ])
+        .split(main_layout[1]);
+
+    render_difficulty_section(f, left[0], app);
+    render_threats_section(f, left[1], app);
+    render_npc_section(f, right[0], app);
+    render_draw_count_section(f, right[1], app);
+    render_result_section(f, right[2], app);
+}
+
+/// Stile del riquadro in base al focus
+fn focus_style(app: &App, focus: NarratorFocus) -> Style {
+    if app.narrator_focus == focus {
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+    }
+}
+
+/// Difficoltà base della prova
+fn render_difficulty_section(f: &mut Frame, area: Rect, app: &App) {
+    let block = Block::default()
+        .title(" Quanto è DIFFICILE la prova? (↑/↓ per selezionare) ")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .style(focus_style(app, NarratorFocus::Difficulty));
+
+    let paragraph = Paragraph::new(create_filled_balls_display(
+        app.narrator.difficulty,
+        Color::Red,
+    ))
+    .block(block)
+    .alignment(Alignment::Center);
+
+    f.render_widget(paragraph, area);
+}
+
+/// Minacce attive e difficoltà complessiva
+fn render_threats_section(f: &mut Frame, area: Rect, app: &App) {
+    let block = Block::default()
+        .title(" Minacce attive ")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded);
+
+    let mut lines: Vec<Line> = app
+        .narrator
+        .threats
+        .iter()
+        .filter(|t| t.active)
+        .map(|t| {
+            Line::from(vec![
+                Span::raw(t.name.clone()),
+                Span::styled(format!(" +{}", t.tokens), Style::default().fg(Color::Red)),
+            ])
+        })
+        .collect();
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "Nessuna (attivale dal tab PNG e Minacce)",
+            Style::default().fg(Color::Gray),
+        )));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!("Difficoltà totale: {}", app.narrator.challenge_difficulty()),
+        Style::default().add_modifier(Modifier::BOLD),
+    )));
+    lines.push(create_filled_balls_display(
+        app.narrator.challenge_difficulty(),
+        Color::Red,
+    ));
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(paragraph, area);
+}
+
+/// PNG che affronta la prova
+fn render_npc_section(f: &mut Frame, area: Rect, app: &App) {
+    let block = Block::default()
+        .title(" Chi affronta la prova? (↑/↓ per scegliere il PNG) ")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .style(focus_style(app, NarratorFocus::Npc));
+
+    let lines = match app.narrator.npcs.get(app.selected_npc) {
+        Some(npc) => vec![
+            Line::from(Span::styled(
+                format!(
+                    "{} ({}/{})",
+                    npc.name,
+                    app.selected_npc + 1,
+                    app.narrator.npcs.len()
+                ),
+                Style::default().add_modifier(Modifier::BOLD),
+            )),
+            create_filled_balls_display(npc.traits, Color::White),
+            Line::from(Span::styled(
+                npc.notes.clone(),
+                Style::default().fg(Color::Gray),
+            )),
+        ],
+        None => vec![Line::from(Span::styled(
+            "Nessun PNG (aggiungili dal tab PNG e Minacce)",
+            Style::default().fg(Color::Gray),
+        ))],
+    };
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(paragraph, area);
+}
+
+/// Token da estrarre nella prova del PNG
+fn render_draw_count_section(f: &mut Frame, area: Rect, app: &App) {
+    let block = Block::default()
+        .title(" Quanti TOKEN estrarre? (↑/↓ per selezionare, poi Enter) ")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .style(focus_style(app, NarratorFocus::DrawCount));
+
+    let paragraph = Paragraph::new(create_empty_balls_display(app.draw_count))
+        .block(block)
+        .alignment(Alignment::Center);
+
+    f.render_widget(paragraph, area);
+}
+
+/// Esito dell'ultima prova
+fn render_result_section(f: &mut Frame, area: Rect, app: &App) {
+    let block = Block::default()
+        .title(" Ultima prova (←/→ per muoverti) ")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded);
+
+    let mut lines = Vec::new();
+    if let Some(entry) = app.history.last() {
+        lines.push(Line::from(entry.description.clone()));
+        let spans: Vec<Span> = entry
+            .first_draw
+            .iter()
+            .chain(entry.risk_draw.iter())
+            .map(|ball| {
+                let color = match ball {
+                    BallType::White => Color::White,
+                    BallType::Red => Color::Red,
+                };
+                Span::styled("● ", Style::default().fg(color))
+            })
+            .collect();
+        lines.push(Line::from(spans));
+    } else {
+        lines.push(Line::from(Span::styled(
+            "Nessuna prova effettuata",
+            Style::default().fg(Color::Gray),
+        )));
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .alignment(Alignment::Center);
+
+    f.render_widget(paragraph, area);
+}
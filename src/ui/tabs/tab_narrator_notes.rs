@@ -0,0 +1,25 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Style},
+    text::Span,
+    widgets::{Block, BorderType, Borders, Paragraph, Wrap},
+};
+
+use crate::app::App;
+
+/// Renderizza il tab del narratore con le note di scena
+pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
+    let block = Block::default()
+        .title(" Note di Scena (Enter per modificare) ")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded);
+
+    let paragraph = if app.narrator.scene_notes.is_empty() {
+        Paragraph::new(Span::styled("[Vuoto]", Style::default().fg(Color::Gray)))
+    } else {
+        Paragraph::new(app.narrator.scene_notes.clone())
+    };
+
+    f.render_widget(paragraph.block(block).wrap(Wrap { trim: false }), area);
+}
@@ -0,0 +1,89 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph, Wrap},
+};
+
+use crate::app::{App, MAX_TOKEN};
+
+/// Renderizza il tab del narratore con sventure e condizioni dei giocatori
+pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
+    let block = Block::default()
+        .title(" Giocatori - Sventure e Condizioni ")
+        .title_bottom(" I importa scheda · U aggiorna · C confusione · A adrenalina · X rimuovi ")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded);
+
+    let mut lines = Vec::new();
+    if app.narrator.players.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "Nessun giocatore: premi I per importare una scheda",
+            Style::default().fg(Color::Gray),
+        )));
+    }
+
+    for (i, player) in app.narrator.players.iter().enumerate() {
+        let name_style = if i == app.selected_player {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+                .fg(Color::LightYellow)
+                .add_modifier(Modifier::BOLD)
+        };
+        let mut header = vec![Span::styled(
+            if player.name.is_empty() {
+                "[Senza nome]".to_string()
+            } else {
+                player.name.clone()
+            },
+            name_style,
+        )];
+        if player.confused {
+            header.push(Span::styled(
+                "  Confusione",
+                Style::default().fg(Color::Magenta),
+            ));
+        }
+        if player.adrenalined {
+            header.push(Span::styled(
+                "  Adrenalina",
+                Style::default().fg(Color::Green),
+            ));
+        }
+        lines.push(Line::from(header));
+
+        if !player.traits.is_empty() {
+            lines.push(Line::from(vec![
+                Span::styled("  Tratti in gioco: ", Style::default().fg(Color::Gray)),
+                Span::raw(player.traits.join(", ")),
+            ]));
+        }
+
+        if player.misfortunes.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "  Nessuna sventura aperta",
+                Style::default().fg(Color::Gray),
+            )));
+        }
+        for misfortune in &player.misfortunes {
+            lines.push(Line::from(vec![
+                Span::raw(format!("  {}", misfortune.text)),
+                Span::styled(
+                    format!("  {}", "● ".repeat(misfortune.tokens.min(MAX_TOKEN))),
+                    Style::default().fg(Color::Red),
+                ),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+
+    f.render_widget(paragraph, area);
+}
@@ -0,0 +1,177 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+};
+
+use super::tab_character::node_style;
+use super::tab_draw::render_stacked;
+use crate::app::{App, HitId, HoneycombNode, ListSection, MAX_TOKEN, PlayPane, Z_BASE};
+
+/// Colonne e mezze righe della griglia ridotta
+const GRID_COLUMNS: u16 = 5;
+const GRID_ROWS: u16 = 9;
+
+/// Righe del riquadro delle sventure, bordi compresi
+const MISFORTUNES_HEIGHT: u16 = 6;
+
+/// Schermata di gioco: tratti e sventure a sinistra, prova a destra
+pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
+    let main_layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+    let left_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Fill(1), Constraint::Length(MISFORTUNES_HEIGHT)])
+        .split(main_layout[0]);
+
+    render_grid(f, left_layout[0], app);
+    render_misfortunes(f, left_layout[1], app);
+
+    let block = pane_block(
+        " Prova (↑/↓ sezione, +/- valore, Enter conferma) ",
+        app,
+        PlayPane::Draw,
+    );
+    let inner = block.inner(main_layout[1]);
+    f.render_widget(block, main_layout[1]);
+    render_stacked(f, inner, app);
+}
+
+/// Riquadro evidenziato quando ha il focus
+fn pane_block<'a>(title: &'a str, app: &App, pane: PlayPane) -> Block<'a> {
+    let style = if app.play_focus == pane {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default()
+    };
+    Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(style)
+}
+
+/// Griglia esagonale ridotta a una riga per tratto
+fn render_grid(f: &mut Frame, area: Rect, app: &mut App) {
+    let block = pane_block(
+        " Tratti (↑/↓ scegli, E usa, Enter modifica) ",
+        app,
+        PlayPane::Traits,
+    )
+    .title_bottom(" ←/→ cambia riquadro ");
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let legal_slots = HoneycombNode::legal_slots(&app.honeycomb_nodes);
+    let invalid_abilities = HoneycombNode::invalid_abilities(&app.honeycomb_nodes);
+    let cell_width = inner.width / GRID_COLUMNS;
+    let top = inner.y + inner.height.saturating_sub(GRID_ROWS) / 2;
+
+    let mut node_areas = Vec::new();
+    for (i, node) in app.honeycomb_nodes.iter().enumerate() {
+        let (col, row) = HoneycombNode::grid_position(i);
+        let cell = Rect {
+            x: inner.x + (col + 2) as u16 * cell_width,
+            y: top + (row + 4) as u16,
+            width: cell_width,
+            height: 1,
+        }
+        .intersection(inner);
+        if cell.is_empty() {
+            continue;
+        }
+
+        let mut style = node_style(app, i, &legal_slots, &invalid_abilities);
+        if app.selected_node == Some(i) {
+            style = style.add_modifier(Modifier::REVERSED);
+        }
+        let symbol = if app.used_traits.contains(&i) {
+            "⬢"
+        } else {
+            "⬡"
+        };
+        let title = if node.text.is_empty() {
+            "·"
+        } else {
+            &node.text
+        };
+        let text: String = format!("{} {}", symbol, title)
+            .chars()
+            .take(cell_width.saturating_sub(1) as usize)
+            .collect();
+        f.render_widget(Paragraph::new(Span::styled(text, style)), cell);
+        node_areas.push((i, cell));
+    }
+
+    for (i, area) in node_areas {
+        app.hits.register(HitId::Node(i), area, Z_BASE);
+    }
+}
+
+/// Sventure con la loro difficoltà, una per riga
+fn render_misfortunes(f: &mut Frame, area: Rect, app: &mut App) {
+    let block = pane_block(
+        " Sventure (↑/↓ scegli, E attiva) ",
+        app,
+        PlayPane::Misfortunes,
+    );
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    for i in 0..4 {
+        let row = Rect {
+            y: inner.y + i as u16,
+            height: 1,
+            ..inner
+        }
+        .intersection(inner);
+        if row.is_empty() {
+            continue;
+        }
+
+        let selected = matches!(
+            app.selected_list_item,
+            Some((ListSection::Misfortunes | ListSection::MisfortunesDifficult, idx)) if idx == i
+        );
+        let style = if app.additional_red_balls[i] != 0 {
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD)
+        } else if selected {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else if app.is_hovered(HitId::Misfortune(i)) {
+            Style::default().fg(Color::LightYellow)
+        } else {
+            Style::default()
+        };
+
+        let name = &app.list_data.misfortunes[i];
+        let difficulty = app.list_data.misfortunes_red_balls[i]
+            .trim()
+            .parse::<usize>()
+            .unwrap_or(0);
+        let line = Line::from(vec![
+            Span::styled(if selected { "▶ " } else { "  " }, style),
+            Span::styled(
+                if name.is_empty() {
+                    "[Vuoto]"
+                } else {
+                    name.as_str()
+                },
+                style,
+            ),
+            Span::styled(
+                format!(" {}", "●".repeat(difficulty.min(MAX_TOKEN))),
+                Style::default().fg(Color::Red),
+            ),
+        ]);
+        f.render_widget(Paragraph::new(line), row);
+        app.hits.register(HitId::Misfortune(i), row, Z_BASE);
+    }
+}
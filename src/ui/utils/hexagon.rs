@@ -0,0 +1,210 @@
+use ratatui::{buffer::Buffer, layout::Rect, style::Style, widgets::Widget};
+
+/// Esagono con il lato superiore piatto, disegnato nel riquadro ricevuto:
+///
+/// ```text
+///   /‾‾ Titolo ‾‾\
+///  /  testo che   \
+/// /    va a capo   \
+/// \   seguendo i   /
+///  \     lati     /
+///   \____________/
+/// ```
+///
+/// Il testo va a capo riga per riga, sulla larghezza interna dell'esagono.
+pub struct Hexagon<'a> {
+    title: &'a str,
+    text: &'a str,
+    style: Style,
+}
+
+impl<'a> Hexagon<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Hexagon {
+            title: "",
+            text,
+            style: Style::default(),
+        }
+    }
+
+    pub fn title(mut self, title: &'a str) -> Self {
+        self.title = title;
+        self
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Parte di ogni riga del riquadro occupata dall'esagono, bordi compresi
+    pub fn rows(area: Rect) -> impl Iterator<Item = Rect> {
+        (0..area.height).map(move |row| {
+            let indent = indent(area, row);
+            Rect {
+                x: area.x + indent,
+                y: area.y + row,
+                width: area.width.saturating_sub(indent * 2),
+                height: 1,
+            }
+        })
+    }
+}
+
+/// Rientro del lato sinistro (e destro) nella riga indicata
+fn indent(area: Rect, row: u16) -> u16 {
+    let half = area.height / 2;
+    if row < half {
+        half - 1 - row
+    } else {
+        row - half
+    }
+}
+
+/// Divide il testo in righe delle larghezze indicate, spezzando le parole troppo lunghe;
+/// le righe larghe zero restano vuote
+fn wrap(text: &str, widths: &[usize]) -> Vec<String> {
+    let mut lines = vec![String::new()];
+    for word in text.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+        while !word.is_empty() {
+            let Some(&width) = widths.get(lines.len() - 1) else {
+                // Testo più lungo dell'esagono: l'ultima riga aggiunta resta vuota
+                lines.pop();
+                return lines;
+            };
+            let line = lines.last_mut().unwrap();
+            let used = line.chars().count();
+            let sep = usize::from(used > 0);
+            if used + sep + word.len() <= width {
+                if sep == 1 {
+                    line.push(' ');
+                }
+                line.extend(word.drain(..));
+            } else if used == 0 && width > 0 {
+                line.extend(word.drain(..width));
+            } else {
+                lines.push(String::new());
+            }
+        }
+    }
+    lines
+}
+
+impl Widget for Hexagon<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = area.intersection(buf.area);
+        if area.width < 6 || area.height < 4 {
+            return;
+        }
+        let last = area.height - 1;
+        let half = area.height / 2;
+
+        for (row, line) in Hexagon::rows(area).enumerate() {
+            let row = row as u16;
+            let (left, right) = if row < half { ('/', '\\') } else { ('\\', '/') };
+            buf[(line.left(), line.y)]
+                .set_char(left)
+                .set_style(self.style);
+            buf[(line.right() - 1, line.y)]
+                .set_char(right)
+                .set_style(self.style);
+            let edge = match row {
+                0 => Some('‾'),
+                r if r == last => Some('_'),
+                _ => None,
+            };
+            for x in line.left() + 1..line.right() - 1 {
+                let cell = &mut buf[(x, line.y)];
+                match edge {
+                    Some(c) => cell.set_char(c),
+                    None => cell.set_char(' '),
+                };
+                cell.set_style(self.style);
+            }
+        }
+
+        // Titolo centrato sul lato superiore
+        let top = Hexagon::rows(area).next().unwrap();
+        let inner = top.width.saturating_sub(2) as usize;
+        let title: String = self.title.chars().take(inner).collect();
+        let len = title.chars().count() as u16;
+        let x = top.x + 1 + (top.width - 2 - len) / 2;
+        buf.set_string(x, top.y, &title, self.style);
+
+        // Testo centrato riga per riga, dentro i lati
+        let text_rows: Vec<Rect> = Hexagon::rows(area)
+            .skip(1)
+            .take(area.height as usize - 2)
+            .map(|line| Rect {
+                x: line.x + 2,
+                width: line.width.saturating_sub(4),
+                ..line
+            })
+            .collect();
+        let widths: Vec<usize> = text_rows.iter().map(|r| r.width as usize).collect();
+        for (line, text) in text_rows.iter().zip(wrap(self.text, &widths)) {
+            let len = text.chars().count() as u16;
+            buf.set_string(line.x + (line.width - len) / 2, line.y, &text, self.style);
+        }
+    }
+}
+
+#[cfg(test)]
+mod hexagon_tests {
+    use crate::ui::utils::hexagon::*;
+
+    fn render(hexagon: Hexagon, width: u16, height: u16) -> Vec<String> {
+        let area = Rect::new(0, 0, width, height);
+        let mut buf = Buffer::empty(area);
+        hexagon.render(area, &mut buf);
+        (0..height)
+            .map(|y| (0..width).map(|x| buf[(x, y)].symbol()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_outline_and_text() {
+        let lines = render(
+            Hexagon::new("Agile come un gatto").title(" Abilità "),
+            18,
+            6,
+        );
+        assert_eq!(
+            lines,
+            vec![
+                "  /‾ Abilità ‾‾\\  ",
+                " /  Agile come  \\ ",
+                "/    un gatto    \\",
+                "\\                /",
+                " \\              / ",
+                "  \\____________/  ",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_long_words_are_split() {
+        let widths = [4, 6];
+        assert_eq!(wrap("Onnipotente", &widths), vec!["Onni", "potent"]);
+        assert_eq!(wrap("a b c", &[1, 1]), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_zero_width_rows_stay_empty() {
+        assert_eq!(wrap("Agile", &[0, 5]), vec!["", "Agile"]);
+        assert_eq!(wrap("a b", &[1, 0, 1]), vec!["a", "", "b"]);
+        assert_eq!(wrap("Agile", &[0, 0]), vec!["", ""]);
+    }
+
+    #[test]
+    fn test_rows_follow_the_sides() {
+        let rows: Vec<(u16, u16)> = Hexagon::rows(Rect::new(10, 0, 18, 6))
+            .map(|r| (r.x, r.width))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![(12, 14), (11, 16), (10, 18), (10, 18), (11, 16), (12, 14)]
+        );
+    }
+}
@@ -1,3 +1,5 @@
 mod display;
+mod hexagon;
 
 pub use display::{centered_rect, create_empty_balls_display, create_filled_balls_display};
+pub use hexagon::Hexagon;
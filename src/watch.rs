@@ -0,0 +1,146 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::time::{Duration, Instant};
+
+use notify::{Config, Event, PollWatcher, RecursiveMode, Watcher};
+
+use crate::app::{App, character_file};
+
+/// Intervallo di controllo quando il sistema non notifica le modifiche ai file
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Attesa dopo l'ultima modifica, per non leggere un file ancora in scrittura
+const SETTLE_TIME: Duration = Duration::from_millis(300);
+
+/// Osserva il file della scheda per accorgersi delle modifiche esterne,
+/// ad esempio a mano o da uno strumento di sincronizzazione.
+pub struct SheetWatcher {
+    path: PathBuf,
+    polling: bool,
+    rx: Receiver<()>,
+    pending: Option<Instant>,
+    _watcher: Box<dyn Watcher>,
+}
+
+impl SheetWatcher {
+    /// Osserva il file della scheda attiva; con `polling` lo controlla a intervalli
+    pub fn start(polling: bool) -> notify::Result<Self> {
+        Self::new(character_file(), polling)
+    }
+
+    fn new(path: PathBuf, polling: bool) -> notify::Result<Self> {
+        let (tx, rx) = mpsc::channel();
+        let name = path.file_name().map(|n| n.to_os_string());
+        let handler = move |event: notify::Result<Event>| {
+            if let Ok(event) = event
+                && event
+                    .paths
+                    .iter()
+                    .any(|p| p.file_name().map(|n| n.to_os_string()) == name)
+            {
+                let _ = tx.send(());
+            }
+        };
+
+        // Si osserva la cartella: molti editor sostituiscono il file invece di modificarlo
+        let dir = match path.parent() {
+            Some(dir) if dir != Path::new("") => dir.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        let poll_config = Config::default().with_poll_interval(POLL_INTERVAL);
+        let mut watcher: Box<dyn Watcher> = if polling {
+            Box::new(PollWatcher::new(handler, poll_config)?)
+        } else {
+            match notify::recommended_watcher(handler.clone()) {
+                Ok(watcher) => Box::new(watcher),
+                Err(_) => Box::new(PollWatcher::new(handler, poll_config)?),
+            }
+        };
+        watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+
+        Ok(SheetWatcher {
+            path,
+            polling,
+            rx,
+            pending: None,
+            _watcher: watcher,
+        })
+    }
+
+    /// Contenuto del file, se è cambiato dall'ultimo controllo e non cambia più
+    fn changed(&mut self) -> Option<String> {
+        if self.rx.try_iter().count() > 0 {
+            self.pending = Some(Instant::now());
+        }
+        if self.pending?.elapsed() < SETTLE_TIME {
+            return None;
+        }
+        self.pending = None;
+        fs::read_to_string(&self.path).ok()
+    }
+
+    /// Consegna all'applicazione le modifiche esterne alla scheda
+    pub fn sync(&mut self, app: &mut App) {
+        // Cambiando personaggio, campagna o formato cambia anche il file da osservare
+        if app.sheet_file != self.path {
+            if let Ok(watcher) = Self::new(app.sheet_file.clone(), self.polling) {
+                *self = watcher;
+            }
+            return;
+        }
+        if let Some(contents) = self.changed() {
+            app.sheet_changed_on_disk(contents);
+        }
+    }
+}
+
+#[cfg(test)]
+mod watch_tests {
+    use std::fs;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use crate::test_support::TempDir;
+    use crate::watch::*;
+
+    /// Attende che l'osservatore noti la modifica, con un limite di tempo
+    fn wait_for_change(watcher: &mut SheetWatcher) -> String {
+        let start = Instant::now();
+        loop {
+            if let Some(contents) = watcher.changed() {
+                return contents;
+            }
+            assert!(
+                start.elapsed() < Duration::from_secs(5),
+                "modifica non notata"
+            );
+            thread::sleep(Duration::from_millis(20));
+        }
+    }
+
+    fn check_backend(polling: bool) {
+        let dir = TempDir::new("watch");
+        let path = dir.join("character_sheet.toml");
+        fs::write(&path, "name = \"Lia\"\n").unwrap();
+
+        let mut watcher = SheetWatcher::new(path.clone(), polling).unwrap();
+        // Il controllo a intervalli confronta con lo stato al primo passaggio
+        thread::sleep(POLL_INTERVAL * 2);
+        assert_eq!(watcher.changed(), None);
+
+        fs::write(dir.join("altro.toml"), "x = 1\n").unwrap();
+        fs::write(&path, "name = \"Aria\"\n").unwrap();
+        assert_eq!(wait_for_change(&mut watcher), "name = \"Aria\"\n");
+    }
+
+    #[test]
+    fn test_native_watcher_notices_edits() {
+        check_backend(false);
+    }
+
+    #[test]
+    fn test_polling_watcher_notices_edits() {
+        check_backend(true);
+    }
+}
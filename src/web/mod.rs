@@ -0,0 +1,488 @@
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use serde_json::{Value, json};
+
+use crate::app::{App, DrawHistory};
+use crate::rpc::views;
+
+/// Porta predefinita del visualizzatore web
+pub const DEFAULT_WEB_PORT: u16 = 8080;
+
+/// Pagina servita su `/`, da usare come sorgente browser in OBS
+const VIEWER: &str = include_str!("viewer.html");
+
+/// Attesa massima su una richiesta o una risposta prima di chiudere la connessione
+const IO_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Attesa massima per consegnare un evento: oltre, l'ascoltatore viene scartato
+const EVENT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Ultimo stato pubblicato, già serializzato, letto dalle connessioni
+#[derive(Default)]
+struct Snapshot {
+    sheet: String,
+    challenge: String,
+    history: String,
+}
+
+/// Stato dell'applicazione all'ultima pubblicazione, per accorgersi dei cambiamenti
+#[derive(Default)]
+struct Published {
+    sheet: Value,
+    challenge: Value,
+    /// Prove già inviate agli ascoltatori degli eventi
+    draws: usize,
+    last_draw: Option<DrawHistory>,
+}
+
+/// Messaggi per il thread che scrive agli ascoltatori degli eventi
+enum Outgoing {
+    /// Nuovo ascoltatore, con l'intestazione della risposta ancora da inviare
+    Listener(TcpStream, String),
+    Events(String),
+}
+
+type Shared = Arc<Mutex<Snapshot>>;
+
+/// Server HTTP in sola lettura su localhost.
+///
+/// Espone scheda, cronologia e prova in corso come JSON, e le nuove prove
+/// come server-sent events su `/api/events`. Le pagine di altri siti non
+/// possono leggerlo, a meno di indicarne l'origine con `--web-origin`.
+pub struct WebServer {
+    addr: SocketAddr,
+    shared: Shared,
+    published: Mutex<Published>,
+    events: Sender<Outgoing>,
+}
+
+impl WebServer {
+    /// Apre il server sulla porta indicata, raggiungibile solo da questa macchina.
+    /// `origin` è l'unica pagina esterna autorizzata a leggere le risposte.
+    pub fn bind(port: u16, origin: Option<String>) -> io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", port))?;
+        let addr = listener.local_addr()?;
+        let shared: Shared = Arc::new(Mutex::new(Snapshot::default()));
+        let (events, rx) = mpsc::channel();
+
+        let accept_shared = Arc::clone(&shared);
+        let accept_events = events.clone();
+        let origin: Arc<Option<String>> = Arc::new(origin);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let shared = Arc::clone(&accept_shared);
+                let events = accept_events.clone();
+                let origin = Arc::clone(&origin);
+                thread::spawn(move || {
+                    let _ = handle_connection(stream, &shared, &events, origin.as_deref());
+                });
+            }
+        });
+        thread::spawn(move || write_events(rx));
+
+        Ok(WebServer {
+            addr,
+            shared,
+            published: Mutex::new(Published::default()),
+            events,
+        })
+    }
+
+    /// Legge `--web [porta]` dagli argomenti
+    pub fn port_from_args(args: &[String]) -> Option<u16> {
+        let pos = args.iter().position(|arg| arg == "--web")?;
+        Some(
+            args.get(pos + 1)
+                .and_then(|port| port.parse().ok())
+                .unwrap_or(DEFAULT_WEB_PORT),
+        )
+    }
+
+    /// Legge `--web-origin <origine>` dagli argomenti, es. `http://localhost:3000`
+    pub fn origin_from_args(args: &[String]) -> Result<Option<String>, String> {
+        let Some(pos) = args.iter().position(|arg| arg == "--web-origin") else {
+            return Ok(None);
+        };
+        match args.get(pos + 1) {
+            Some(origin) if is_origin(origin) => Ok(Some(origin.clone())),
+            _ => Err("--web-origin vuole un'origine come http://localhost:3000".into()),
+        }
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Aggiorna lo stato servito se è cambiato e passa le nuove prove agli ascoltatori
+    pub fn publish(&self, app: &App) {
+        let Ok(mut published) = self.published.lock() else {
+            return;
+        };
+        let sheet = views::sheet(app);
+        let challenge = views::challenge(app);
+        let history_changed = published.draws != app.history.len()
+            || published.last_draw.as_ref() != app.history.last();
+        if sheet == published.sheet && challenge == published.challenge && !history_changed {
+            return;
+        }
+
+        if let Ok(mut snapshot) = self.shared.lock() {
+            snapshot.sheet = sheet.to_string();
+            snapshot.challenge = challenge.to_string();
+            if history_changed || snapshot.history.is_empty() {
+                snapshot.history = views::history(app, None).to_string();
+            }
+        }
+        published.sheet = sheet;
+        published.challenge = challenge;
+
+        // La cronologia può essere stata sostituita, es. cambiando personaggio
+        if published.draws > app.history.len() {
+            published.draws = app.history.len();
+        }
+        let events: String = app.history[published.draws..]
+            .iter()
+            .map(|entry| format!("event: draw\ndata: {}\n\n", json!(entry)))
+            .collect();
+        published.draws = app.history.len();
+        published.last_draw = app.history.last().cloned();
+        if !events.is_empty() {
+            let _ = self.events.send(Outgoing::Events(events));
+        }
+    }
+}
+
+/// Consegna le nuove prove agli ascoltatori, fuori dal loop dell'interfaccia
+fn write_events(rx: Receiver<Outgoing>) {
+    let mut listeners: Vec<TcpStream> = Vec::new();
+    for outgoing in rx {
+        match outgoing {
+            Outgoing::Listener(mut stream, head) => {
+                // Scritta qui, così arriva prima di ogni evento successivo
+                if stream.write_all(head.as_bytes()).is_ok() {
+                    listeners.push(stream);
+                }
+            }
+            Outgoing::Events(events) => {
+                listeners.retain_mut(|stream| stream.write_all(events.as_bytes()).is_ok());
+            }
+        }
+    }
+}
+
+/// Risponde a una richiesta; le connessioni a `/api/events` restano aperte
+fn handle_connection(
+    mut stream: TcpStream,
+    shared: &Shared,
+    events: &Sender<Outgoing>,
+    origin: Option<&str>,
+) -> io::Result<()> {
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut local_host = true;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("host")
+        {
+            local_host = is_local_host(value.trim());
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+    let path = path.split('?').next().unwrap_or("");
+
+    // Protegge da pagine esterne che puntano un proprio dominio su 127.0.0.1
+    if !local_host {
+        return respond(
+            &mut stream,
+            origin,
+            "403 Forbidden",
+            "application/json",
+            &error("Host non consentito"),
+        );
+    }
+    if method != "GET" {
+        return respond(
+            &mut stream,
+            origin,
+            "405 Method Not Allowed",
+            "application/json",
+            &error("Solo lettura"),
+        );
+    }
+
+    let body = match path {
+        "/" | "/index.html" => {
+            return respond(
+                &mut stream,
+                origin,
+                "200 OK",
+                "text/html; charset=utf-8",
+                VIEWER,
+            );
+        }
+        "/api/events" => return subscribe(stream, events, origin),
+        "/api/sheet" | "/api/challenge" | "/api/history" => {
+            let Ok(snapshot) = shared.lock() else {
+                return Ok(());
+            };
+            let body = match path {
+                "/api/sheet" => &snapshot.sheet,
+                "/api/challenge" => &snapshot.challenge,
+                _ => &snapshot.history,
+            };
+            // Prima della prima pubblicazione non c'è ancora nulla da mostrare
+            if body.is_empty() {
+                "null".to_string()
+            } else {
+                body.clone()
+            }
+        }
+        _ => {
+            return respond(
+                &mut stream,
+                origin,
+                "404 Not Found",
+                "application/json",
+                &error("Risorsa inesistente"),
+            );
+        }
+    };
+    respond(&mut stream, origin, "200 OK", "application/json", &body)
+}
+
+/// Tiene aperta la connessione per inviarle le nuove prove
+fn subscribe(stream: TcpStream, events: &Sender<Outgoing>, origin: Option<&str>) -> io::Result<()> {
+    let head = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\n{}\r\n: collegato\n\n",
+        cors_header(origin)
+    );
+    // Un ascoltatore che non legge non deve fermare gli altri
+    stream.set_write_timeout(Some(EVENT_TIMEOUT))?;
+    let _ = events.send(Outgoing::Listener(stream, head));
+    Ok(())
+}
+
+/// `localhost` o `127.0.0.1`, con o senza porta
+fn is_local_host(host: &str) -> bool {
+    let name = match host.rsplit_once(':') {
+        Some((name, port)) if port.chars().all(|c| c.is_ascii_digit()) => name,
+        _ => host,
+    };
+    matches!(name, "localhost" | "127.0.0.1" | "[::1]")
+}
+
+/// Schema, host ed eventuale porta, senza percorso né caratteri di controllo
+fn is_origin(origin: &str) -> bool {
+    let Some((scheme, host)) = origin.split_once("://") else {
+        return false;
+    };
+    matches!(scheme, "http" | "https")
+        && !host.is_empty()
+        && host
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | ':' | '[' | ']'))
+}
+
+/// Intestazione che autorizza l'origine indicata a leggere le risposte
+fn cors_header(origin: Option<&str>) -> String {
+    match origin {
+        Some(origin) => format!(
+            "Access-Control-Allow-Origin: {}\r\nVary: Origin\r\n",
+            origin
+        ),
+        None => String::new(),
+    }
+}
+
+fn error(message: &str) -> String {
+    json!({ "error": message }).to_string()
+}
+
+fn respond(
+    stream: &mut TcpStream,
+    origin: Option<&str>,
+    status: &str,
+    content_type: &str,
+    body: &str,
+) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\n{}Connection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        cors_header(origin),
+        body
+    )?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod web_tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpStream;
+
+    use crate::app::{App, DrawHistory};
+    use crate::web::*;
+
+    /// Intestazioni e corpo della risposta
+    fn request(server: &WebServer, path: &str, host: &str) -> (String, String) {
+        let mut stream = TcpStream::connect(server.local_addr()).unwrap();
+        write!(stream, "GET {} HTTP/1.1\r\nHost: {}\r\n\r\n", path, host).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        (head.to_string(), body.to_string())
+    }
+
+    /// Riga di stato e corpo della risposta
+    fn get(server: &WebServer, path: &str, host: &str) -> (String, String) {
+        let (head, body) = request(server, path, host);
+        (head.lines().next().unwrap().to_string(), body)
+    }
+
+    #[test]
+    fn test_endpoints_serve_the_published_state() {
+        let server = WebServer::bind(0, None).unwrap();
+        let mut app = App::new();
+        app.character_base_info.name = "Lia".into();
+        app.red_balls = 3;
+        server.publish(&app);
+
+        let (status, body) = get(&server, "/api/sheet", "localhost");
+        assert_eq!(status, "HTTP/1.1 200 OK");
+        let sheet: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(sheet["name"], "Lia");
+
+        let (_, body) = get(&server, "/api/challenge", "127.0.0.1:8080");
+        let challenge: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(challenge["red_balls"], 3);
+
+        let (_, body) = get(&server, "/api/history", "localhost");
+        assert_eq!(body, "[]");
+        let (_, body) = get(&server, "/", "localhost");
+        assert!(body.contains("EventSource"));
+
+        let (status, _) = get(&server, "/scheda", "localhost");
+        assert_eq!(status, "HTTP/1.1 404 Not Found");
+        let (status, _) = get(&server, "/api/sheet", "attacker.example");
+        assert_eq!(status, "HTTP/1.1 403 Forbidden");
+
+        // Senza un'origine autorizzata le pagine di altri siti non leggono le risposte
+        let (head, _) = request(&server, "/api/sheet", "localhost");
+        assert!(!head.contains("Access-Control-Allow-Origin"));
+    }
+
+    #[test]
+    fn test_only_the_given_origin_is_allowed() {
+        let origin = "http://localhost:3000".to_string();
+        let server = WebServer::bind(0, Some(origin)).unwrap();
+        server.publish(&App::new());
+        let (head, _) = request(&server, "/api/sheet", "localhost");
+        assert!(head.contains("Access-Control-Allow-Origin: http://localhost:3000\r\n"));
+
+        let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            WebServer::origin_from_args(&args(&["nte", "--web"])),
+            Ok(None)
+        );
+        assert_eq!(
+            WebServer::origin_from_args(&args(&["nte", "--web-origin", "https://obs.local:8443"])),
+            Ok(Some("https://obs.local:8443".into()))
+        );
+        assert!(WebServer::origin_from_args(&args(&["nte", "--web-origin", "*"])).is_err());
+        assert!(
+            WebServer::origin_from_args(&args(&["nte", "--web-origin", "http://a\r\nX: 1"]))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_stalled_listener_does_not_block_publish() {
+        let server = WebServer::bind(0, None).unwrap();
+        let mut stream = TcpStream::connect(server.local_addr()).unwrap();
+        write!(
+            stream,
+            "GET /api/events HTTP/1.1\r\nHost: localhost\r\n\r\n"
+        )
+        .unwrap();
+
+        // L'ascoltatore non legge mai: gli eventi riempiono la connessione
+        let mut app = App::new();
+        let start = std::time::Instant::now();
+        for _ in 0..4 {
+            for _ in 0..20 {
+                app.history.push(DrawHistory {
+                    description: "x".repeat(100_000),
+                    ..Default::default()
+                });
+            }
+            server.publish(&app);
+        }
+        assert!(start.elapsed() < EVENT_TIMEOUT);
+    }
+
+    #[test]
+    fn test_new_draws_are_streamed() {
+        let server = WebServer::bind(0, None).unwrap();
+        let mut app = App::new();
+        app.history.push(DrawHistory::default());
+        server.publish(&app);
+
+        let mut stream = TcpStream::connect(server.local_addr()).unwrap();
+        write!(
+            stream,
+            "GET /api/events HTTP/1.1\r\nHost: localhost\r\n\r\n"
+        )
+        .unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        while !line.starts_with(": collegato") {
+            line.clear();
+            reader.read_line(&mut line).unwrap();
+        }
+
+        // Solo le prove successive al collegamento
+        app.history.push(DrawHistory {
+            description: "Scalare la torre".into(),
+            ..Default::default()
+        });
+        server.publish(&app);
+        let mut event = String::new();
+        while event.trim().is_empty() {
+            event.clear();
+            reader.read_line(&mut event).unwrap();
+        }
+        reader.read_line(&mut event).unwrap();
+        assert!(event.starts_with("event: draw\ndata: "));
+        assert!(event.contains("Scalare la torre"));
+    }
+
+    #[test]
+    fn test_port_from_args() {
+        let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            WebServer::port_from_args(&args(&["nte", "--web"])),
+            Some(DEFAULT_WEB_PORT)
+        );
+        assert_eq!(
+            WebServer::port_from_args(&args(&["nte", "--web", "9000"])),
+            Some(9000)
+        );
+        assert_eq!(WebServer::port_from_args(&args(&["nte"])), None);
+    }
+}